@@ -1,5 +1,6 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId, Throughput};
 use gh_actions::{User, UserManager, calculate_fibonacci, calculate_fibonacci_recursive, is_prime, factorial, validate_email};
+use gh_actions::utils::{adler32, calculate_fibonacci_u128, simple_checksum};
 
 fn bench_fibonacci(c: &mut Criterion) {
     let mut group = c.benchmark_group("fibonacci");
@@ -17,6 +18,22 @@ fn bench_fibonacci(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_fibonacci_u64_vs_u128(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fibonacci_width");
+    
+    for i in [20, 50, 93].iter() {
+        group.bench_with_input(BenchmarkId::new("u64", i), i, |b, i| {
+            b.iter(|| calculate_fibonacci(black_box(*i)))
+        });
+        
+        group.bench_with_input(BenchmarkId::new("u128", i), i, |b, i| {
+            b.iter(|| calculate_fibonacci_u128(black_box(*i)))
+        });
+    }
+    
+    group.finish();
+}
+
 fn bench_prime_checking(c: &mut Criterion) {
     let mut group = c.benchmark_group("prime_checking");
     
@@ -182,14 +199,27 @@ fn bench_bulk_operations(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_checksums(c: &mut Criterion) {
+    let mut group = c.benchmark_group("checksums");
+    let data: Vec<u8> = (0..1024 * 1024).map(|i| (i * 31 % 251) as u8).collect();
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    
+    group.bench_function("simple_checksum_1mb", |b| b.iter(|| simple_checksum(black_box(&data))));
+    group.bench_function("adler32_1mb", |b| b.iter(|| adler32(black_box(&data))));
+    
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_fibonacci,
+    bench_fibonacci_u64_vs_u128,
     bench_prime_checking,
     bench_factorial,
     bench_email_validation,
     bench_user_manager_operations,
-    bench_bulk_operations
+    bench_bulk_operations,
+    bench_checksums
 );
 
 criterion_main!(benches);
\ No newline at end of file
@@ -14,7 +14,7 @@ fi
 
 # Build the WASM package
 echo "📦 Building WASM package..."
-wasm-pack build --target web --out-dir www/pkg
+wasm-pack build --target web --out-dir www/pkg -- --features web
 
 # Check if build was successful
 if [ $? -eq 0 ]; then
@@ -0,0 +1,57 @@
+use std::env;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    // Rebuild when HEAD moves, so `--version` names the current commit.
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    println!("cargo:rustc-env=GH_ACTIONS_GIT_HASH={}", git_hash());
+    println!("cargo:rustc-env=GH_ACTIONS_BUILD_TIMESTAMP={}", build_timestamp());
+    println!("cargo:rustc-env=GH_ACTIONS_TARGET={}", env::var("TARGET").unwrap_or_else(|_| "unknown".to_string()));
+    println!("cargo:rustc-env=GH_ACTIONS_FEATURES={}", enabled_features());
+}
+
+/// Seconds since the Unix epoch, taken from `SOURCE_DATE_EPOCH` for
+/// reproducible builds and from the clock otherwise.
+///
+/// This script only reruns when git HEAD or a branch moves or
+/// `SOURCE_DATE_EPOCH` changes, so the clock time is when it last ran, not
+/// when the crate was last compiled.
+fn build_timestamp() -> u64 {
+    env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or(0)
+        })
+}
+
+/// Enabled cargo features, comma separated and sorted, without `default`.
+fn enabled_features() -> String {
+    let mut features: Vec<String> = env::vars()
+        // Cargo upper-cases names and turns `-` into `_`; this crate's features use `_`
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(str::to_lowercase))
+        .filter(|feature| feature != "default")
+        .collect();
+    features.sort();
+    features.join(",")
+}
+
+/// The short hash of HEAD, or `unknown` when git or the repository is missing.
+fn git_hash() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short=7", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
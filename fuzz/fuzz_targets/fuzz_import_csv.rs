@@ -0,0 +1,22 @@
+//! Feeds arbitrary text to [`TaskManager::import_from_csv`], which must
+//! return `Ok` or `Err` and never panic.
+//!
+//! # Running
+//!
+//! Needs a nightly toolchain and `cargo install cargo-fuzz`. From the
+//! repository root:
+//!
+//! ```text
+//! cargo +nightly fuzz run fuzz_import_csv
+//! ```
+//!
+//! Crashing inputs are saved under `fuzz/artifacts/fuzz_import_csv/` and can
+//! be replayed with `cargo +nightly fuzz run fuzz_import_csv <file>`.
+#![no_main]
+
+use gh_actions::TaskManager;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|csv: &str| {
+    let _ = TaskManager::new().import_from_csv(csv);
+});
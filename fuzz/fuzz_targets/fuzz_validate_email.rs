@@ -0,0 +1,13 @@
+//! Feeds arbitrary text to the email validators, which must never panic and
+//! must agree with each other.
+//!
+//! Run with `cargo +nightly fuzz run fuzz_validate_email`; see
+//! `fuzz_import_csv.rs` for setup.
+#![no_main]
+
+use gh_actions::{validate_email, validate_email_detailed};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|email: &str| {
+    assert_eq!(validate_email(email), validate_email_detailed(email).is_ok());
+});
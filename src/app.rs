@@ -1,25 +1,811 @@
 use egui::{Context, CentralPanel, Layout, Align, ScrollArea, Color32};
 use eframe::App;
+use std::collections::{HashSet, VecDeque};
+use std::rc::Rc;
+use std::time::Duration;
+// `std::time::Instant` panics on wasm32; this one reads the browser clock
+use web_time::Instant;
+use crate::task::{Priority, Task, TaskFilter, TaskManager, TaskOrder, TaskReport, TaskSummary};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use crate::utils::format_duration_human;
+use serde::Deserialize;
+
+// The tasks the app shows. On the web these are the tasks behind the
+// JavaScript bindings, so changes made here are saved and reported to the page.
+#[cfg(feature = "web")]
+pub(crate) use crate::wasm as store;
+
+/// Stands in for the `wasm` bindings in native builds: the same operations
+/// on an in-process task list that is never saved.
+#[cfg(not(feature = "web"))]
+pub(crate) mod store {
+    use crate::storage;
+    use crate::task::{Task, TaskId, TaskManager, TaskReport, TaskSpec, ToggleResult};
+    use chrono::{Local, Offset, Utc};
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Mutex, MutexGuard, PoisonError};
+    
+    lazy_static::lazy_static! {
+        static ref TASK_MANAGER: Mutex<TaskManager> = Mutex::new(TaskManager::new());
+    }
+    
+    /// Bumped by every change, like the revision in the `wasm` module
+    static REVISION: AtomicU32 = AtomicU32::new(0);
+    
+    thread_local! {
+        /// Reads of the tasks on this thread, for tests of the app's caches
+        static FETCHES: Cell<u32> = const { Cell::new(0) };
+    }
+    
+    fn lock_tasks() -> MutexGuard<'static, TaskManager> {
+        // Every operation changes the tasks in a single call, so they are
+        // still consistent after a panic
+        TASK_MANAGER.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+    
+    /// Like [`lock_tasks`], for a change: the revision moves on while the
+    /// lock is held, so no one reads the new revision with the old tasks
+    fn change_tasks() -> MutexGuard<'static, TaskManager> {
+        let manager = lock_tasks();
+        REVISION.fetch_add(1, Ordering::Relaxed);
+        manager
+    }
+    
+    pub(crate) fn with_tasks<R>(f: impl FnOnce(&TaskManager) -> R) -> R {
+        FETCHES.with(|fetches| fetches.set(fetches.get() + 1));
+        f(&lock_tasks())
+    }
+    
+    #[cfg(test)]
+    pub(crate) fn fetch_count() -> u32 {
+        FETCHES.with(Cell::get)
+    }
+    
+    /// Empties the store, for tests that share it
+    #[cfg(test)]
+    pub(crate) fn reset() {
+        *change_tasks() = TaskManager::new();
+    }
+    
+    pub(crate) fn revision() -> u32 {
+        REVISION.load(Ordering::Relaxed)
+    }
+    
+    pub(crate) fn add_task_full(json: &str) -> Result<String, String> {
+        let task = TaskSpec::from_json(json).and_then(TaskSpec::into_task)?;
+        let mut manager = change_tasks();
+        let id = manager.add_task_from(task)?;
+        serde_json::to_string(&manager[TaskId(id)]).map_err(|e| e.to_string())
+    }
+    
+    pub(crate) fn restore_task(json: &str) -> Result<String, String> {
+        let task: Task = serde_json::from_str(json).map_err(|e| format!("invalid task: {}", e))?;
+        let id = task.id;
+        let mut manager = change_tasks();
+        if manager.get_task(id).is_some() {
+            return Err(format!("task {} already exists", id));
+        }
+        manager.extend([task]);
+        serde_json::to_string(&manager[TaskId(id)]).map_err(|e| e.to_string())
+    }
+    
+    pub(crate) fn toggle(id: u32) -> ToggleResult {
+        change_tasks().toggle_task(id)
+    }
+    
+    pub(crate) fn remove_task(id: u32) -> Result<bool, String> {
+        Ok(change_tasks().remove_task(id))
+    }
+    
+    pub(crate) fn update_task(id: u32, title: String, description: String) -> Result<String, String> {
+        if title.trim().is_empty() {
+            return Err("task title cannot be empty".to_string());
+        }
+        let mut manager = change_tasks();
+        let task = manager.get_task_mut(id).ok_or_else(|| format!("task {} not found", id))?;
+        task.title = title;
+        task.description = description;
+        serde_json::to_string(&*task).map_err(|e| e.to_string())
+    }
+    
+    pub(crate) fn add_task_tag(id: u32, tag: &str) -> Result<String, String> {
+        edit_task(id, |task| task.add_tag(tag).map(|_| ()))
+    }
+    
+    pub(crate) fn remove_task_tag(id: u32, tag: &str) -> Result<String, String> {
+        edit_task(id, |task| {
+            task.remove_tag(tag);
+            Ok(())
+        })
+    }
+    
+    /// Changes task `id` with `f`, returning it as JSON like the `wasm` bindings
+    fn edit_task(id: u32, f: impl FnOnce(&mut Task) -> Result<(), String>) -> Result<String, String> {
+        let mut manager = change_tasks();
+        let task = manager.get_task_mut(id).ok_or_else(|| format!("task {} not found", id))?;
+        f(task)?;
+        serde_json::to_string(&*task).map_err(|e| e.to_string())
+    }
+    
+    pub(crate) fn retain_incomplete_tasks() -> u32 {
+        change_tasks().retain(|task| !task.completed) as u32
+    }
+    
+    pub(crate) fn export_backup() -> Result<String, String> {
+        storage::export_backup(&lock_tasks(), Utc::now())
+    }
+    
+    pub(crate) fn import_backup(data: &str, merge: bool) -> Result<String, String> {
+        let tasks = storage::parse_backup(data)?;
+        storage::restore_backup(&mut change_tasks(), tasks, merge)
+    }
+    
+    pub(crate) fn stats_report() -> TaskReport {
+        with_tasks(|manager| manager.get_report(Utc::now(), super::STATS_DAYS, Local::now().offset().fix()))
+    }
+}
+
+/// Window, in days, for the velocity and streak in the statistics panel
+pub(crate) const STATS_DAYS: u32 = 30;
+
+/// Age, in days, after which the KPI overview warns about incomplete tasks
+const STALE_DAYS: u32 = 30;
+
+/// Converts a daily series to `[day_offset, count]` points for plotting
+pub(crate) fn series_points(series: &[(DateTime<Utc>, usize)]) -> Vec<[f64; 2]> {
+    series.iter().enumerate()
+        .map(|(i, (_, count))| [i as f64, *count as f64])
+        .collect()
+}
+
+/// A task title as the list shows it: struck through and dimmed once done
+fn title_text(title: &str, completed: bool, visuals: &egui::Visuals) -> egui::RichText {
+    let text = egui::RichText::new(title).strong();
+    if completed {
+        text.strikethrough().color(visuals.weak_text_color())
+    } else {
+        text
+    }
+}
+
+/// Color of the dot before a task's priority in the list
+fn priority_color(priority: Priority) -> Color32 {
+    match priority {
+        Priority::High => Color32::from_rgb(220, 80, 80),
+        Priority::Medium => Color32::from_rgb(230, 160, 40),
+        Priority::Low => Color32::from_rgb(100, 200, 100),
+    }
+}
+
+/// When a task is due relative to `today`, in whole days: `due today`,
+/// `due tomorrow`, `due in 3 days` or `overdue by 2 days`. Due dates are
+/// entered as dates, so the time of day is left out.
+fn due_label(due: DateTime<Utc>, today: NaiveDate) -> String {
+    let days = (due.date_naive() - today).num_days();
+    let plural = |n: i64| if n == 1 { "" } else { "s" };
+    match days {
+        0 => "due today".to_string(),
+        1 => "due tomorrow".to_string(),
+        2.. => format!("due in {} days", days),
+        _ => format!("overdue by {} day{}", -days, plural(-days)),
+    }
+}
+
+/// The add form's due date field: `None` when it is blank, or the date typed
+/// as `YYYY-MM-DD`
+fn parse_due_input(input: &str) -> Result<Option<NaiveDate>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| format!("Invalid due date '{}', expected YYYY-MM-DD", input))
+}
+
+/// Background of a tag's badge. The color comes from the tag's name, ignoring
+/// case like tags do, so a tag looks the same in every row and session.
+fn tag_color(tag: &str) -> Color32 {
+    // FNV-1a, which unlike `DefaultHasher` is fixed across Rust versions
+    let hash = tag.to_lowercase().bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    egui::ecolor::Hsva::new((hash % 360) as f32 / 360.0, 0.45, 0.9, 1.0).into()
+}
+
+/// A small button showing `text` in the colors of `tag`
+fn tag_badge(ui: &mut egui::Ui, tag: &str, text: impl Into<String>) -> egui::Response {
+    let text = egui::RichText::new(text).small().color(Color32::BLACK);
+    ui.add(egui::Button::new(text).fill(tag_color(tag)).rounding(8.0).small())
+}
+
+/// Splits tags typed as `work, home` and adds the new ones to `tags`, ignoring
+/// case as [`Task::add_tag`] does
+fn add_typed_tags(tags: &mut Vec<String>, typed: &str) {
+    for tag in typed.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+}
+
+/// The fields of `task` the list leaves out
+fn show_task_details(ui: &mut egui::Ui, task: &Task) {
+    ui.separator();
+    if task.description.is_empty() {
+        ui.weak("No description");
+    } else {
+        ui.label(&task.description);
+    }
+    if !task.tags.is_empty() {
+        ui.small(format!("Tags: {}", task.tags.join(", ")));
+    }
+    if let Some(hours) = task.estimated_hours {
+        ui.small(format!("Estimated {} hours", hours));
+    }
+    ui.small(format!("Created {}", format_duration_human(task.elapsed_since_creation())));
+}
+
+/// How long a notification stays on screen
+const NOTIFICATION_LIFETIME: Duration = Duration::from_secs(3);
+
+/// `eframe::Storage` key under which the list order is kept between sessions
+const TASK_ORDER_KEY: &str = "gh_actions.task_order";
+
+/// The orders offered above the task list, with their labels
+const TASK_ORDERS: [(TaskOrder, &str); 7] = [
+    (TaskOrder::Id, "Order added"),
+    (TaskOrder::Newest, "Newest first"),
+    (TaskOrder::Oldest, "Oldest first"),
+    (TaskOrder::Title, "Title A–Z"),
+    (TaskOrder::IncompleteFirst, "Incomplete first"),
+    (TaskOrder::Priority, "Priority"),
+    (TaskOrder::Due, "Due date"),
+];
+
+/// How long a 🗑 button waits for the second click that deletes
+const DELETE_CONFIRM_WINDOW: Duration = Duration::from_secs(3);
 
 #[derive(Default)]
 pub struct TaskManagerApp {
     current_view: AppView,
+    // Views to go back to, the most recent last
+    view_stack: Vec<AppView>,
     // Task-related state
     new_task_title: String,
     new_task_description: String,
+    new_task_priority: Priority,
+    // Due date as typed, `YYYY-MM-DD` or empty
+    new_task_due: String,
+    // Tags added to the form, shown as chips
+    new_task_tags: Vec<String>,
+    // Tag being typed, added as a chip on Enter or a comma
+    new_task_tag: String,
+    // Tag being typed on the detail screen
+    detail_tag: String,
+    // Set by Ctrl+N until the title field has taken focus
+    focus_new_task: bool,
+    // Set by Ctrl+F until the search box has taken focus
+    focus_search: bool,
+    // Feedback for the last actions, oldest first
+    notifications: VecDeque<(String, NotificationKind, Instant)>,
+    // Task whose 🗑 button was clicked once, and when
+    pending_delete: Option<(u32, Instant)>,
+    // Backup pasted in by the user, waiting to be imported
+    backup_text: String,
+    // The task list as of a store revision, so frames without changes do
+    // not copy every task again
+    task_cache: Option<(u32, Rc<Vec<TaskSummary>>)>,
+    // The statistics header as of a store revision, like `task_cache`
+    stats_cache: Option<(u32, Rc<TaskStats>)>,
+    // The list tab: all, active or completed tasks
+    list_filter: TaskFilter,
+    // Order of the list, saved between sessions
+    task_order: TaskOrder,
+    // Search box above the list, matched against titles and descriptions
+    search_query: String,
+    // IDs matching `search_query` as of a store revision, so the tasks are
+    // searched again only when either changes
+    search_cache: Option<(u32, String, Rc<HashSet<u32>>)>,
+    // Tag whose badge was clicked; the list shows only tasks with it
+    tag_filter: Option<String>,
+    // IDs of the tasks with `tag_filter`, cached like `search_cache`
+    tag_cache: Option<(u32, String, Rc<HashSet<u32>>)>,
+    // Task whose details are shown under its row in the list
+    open_task: Option<u32>,
+    // The one row being edited, if any
+    editing: Option<TaskEdit>,
+    // What Ctrl+Z puts back
+    undo: Option<Undo>,
+    // The timeline groups tasks by completion date rather than creation date
+    timeline_by_completion: bool,
+}
+
+/// What the statistics header shows
+#[derive(Debug, Clone)]
+struct TaskStats {
+    report: TaskReport,
+    /// Incomplete high-priority tasks, oldest first
+    high_priority: Vec<Task>,
+}
+
+/// A task being edited in its row of the list
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TaskEdit {
+    id: u32,
+    title: String,
+    description: String,
+    /// Why the last save failed, shown under the fields
+    error: Option<String>,
+    /// Set when editing starts, until the title field has taken focus
+    focus: bool,
 }
 
-#[derive(Default, PartialEq)]
+/// The last change Ctrl+Z can take back, with the task as it was before
+#[derive(Debug, Clone)]
+enum Undo {
+    Removed(Task),
+    Edited(Task),
+}
+
+/// Keyboard shortcuts pressed during one frame
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Shortcuts {
+    /// Ctrl+N: focus the new task title
+    new_task: bool,
+    /// Ctrl+F: focus the search box
+    search: bool,
+    /// Escape: cancel an edit, or clear the search, or clear the add task form
+    cancel: bool,
+    /// Ctrl+Z: put back the last deleted or edited task
+    undo: bool,
+}
+
+/// Keys and what they do, for the help section. Ctrl is Cmd on a Mac.
+const SHORTCUTS_HELP: [(&str, &str); 5] = [
+    ("Ctrl+N", "Focus the new task title"),
+    ("Enter", "Add the task, in the title field"),
+    ("Ctrl+F", "Focus the search box"),
+    ("Esc", "Cancel an edit, else clear the search, else clear the form"),
+    ("Ctrl+Z", "Undo the last delete or edit, outside text fields"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Success,
+    Error,
+}
+
+impl NotificationKind {
+    fn color(self) -> Color32 {
+        match self {
+            NotificationKind::Success => Color32::from_rgb(100, 200, 100),
+            NotificationKind::Error => Color32::from_rgb(220, 80, 80),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 enum AppView {
     #[default]
     TaskManager,
     KpiDashboard,
+    Timeline,
+    /// Everything about one task, by ID
+    TaskDetail(u32),
+}
+
+/// The screen the app opens on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InitialView {
+    #[default]
+    Task,
+    Kpi,
+}
+
+/// How the app starts, given to `start_egui_app` as
+/// `{initial_view, dark_mode, follow_system_theme}`; every field is optional.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppOptions {
+    /// `task` or `kpi`
+    pub initial_view: InitialView,
+    /// Dark or light theme, unless `follow_system_theme` is true
+    pub dark_mode: Option<bool>,
+    /// Match the browser's light or dark preference. This is what happens
+    /// when neither theme option is given.
+    pub follow_system_theme: Option<bool>,
+}
+
+impl AppOptions {
+    pub fn theme(&self) -> egui::ThemePreference {
+        match (self.follow_system_theme, self.dark_mode) {
+            (Some(true), _) | (None, None) => egui::ThemePreference::System,
+            (_, Some(true)) => egui::ThemePreference::Dark,
+            (_, _) => egui::ThemePreference::Light,
+        }
+    }
 }
 
 impl TaskManagerApp {
     pub fn new() -> Self {
         Self::default()
     }
+    
+    /// The app as `options` ask for it to open. The theme belongs to the egui
+    /// context, so the caller applies [`AppOptions::theme`].
+    pub fn with_options(options: &AppOptions) -> Self {
+        TaskManagerApp {
+            current_view: match options.initial_view {
+                InitialView::Task => AppView::TaskManager,
+                InitialView::Kpi => AppView::KpiDashboard,
+            },
+            ..Self::default()
+        }
+    }
+    
+    /// Shows `view`, remembering the current one for [`Self::go_back`]
+    fn open_view(&mut self, view: AppView) {
+        if view != self.current_view {
+            self.view_stack.push(std::mem::replace(&mut self.current_view, view));
+        }
+    }
+    
+    /// Returns to the view shown before the current one, or to the task list
+    fn go_back(&mut self) {
+        self.current_view = self.view_stack.pop().unwrap_or_default();
+    }
+    
+    /// Restores what [`App::save`] kept from an earlier session
+    pub fn load_settings(&mut self, storage: &dyn eframe::Storage) {
+        if let Some(order) = storage.get_string(TASK_ORDER_KEY).and_then(|order| TaskOrder::parse(&order).ok()) {
+            self.task_order = order;
+        }
+    }
+    
+    fn apply_shortcuts(&mut self, shortcuts: Shortcuts) {
+        if shortcuts.new_task {
+            self.focus_new_task = true;
+        }
+        if shortcuts.search {
+            self.focus_search = true;
+        }
+        if shortcuts.cancel {
+            // One thing at a time, so an edit or a search never takes the
+            // add form as typed with it
+            if self.editing.take().is_none() {
+                if self.search_query.is_empty() {
+                    self.clear_form();
+                } else {
+                    self.search_query.clear();
+                }
+            }
+            self.focus_new_task = false;
+            self.focus_search = false;
+        }
+        if shortcuts.undo {
+            self.undo_last_change();
+        }
+    }
+    
+    /// Puts back the task the last delete or edit changed, once
+    fn undo_last_change(&mut self) {
+        let result = match self.undo.take() {
+            None => {
+                self.notify("Nothing to undo", NotificationKind::Error);
+                return;
+            }
+            Some(Undo::Removed(task)) => serde_json::to_string(&task)
+                .map_err(|e| e.to_string())
+                .and_then(|json| store::restore_task(&json).map_err(|e| e.to_string())),
+            Some(Undo::Edited(task)) => {
+                store::update_task(task.id, task.title, task.description).map_err(|e| e.to_string())
+            }
+        };
+        match result {
+            Ok(_) => self.notify("Undone", NotificationKind::Success),
+            Err(e) => self.notify(format!("Failed to undo: {}", e), NotificationKind::Error),
+        }
+    }
+    
+    /// Switches the row of task `id` to edit mode, leaving any other edit
+    fn start_editing(&mut self, id: u32) {
+        let Some(task) = store::with_tasks(|manager| manager.get_task(id).cloned()) else {
+            self.notify("Task not found", NotificationKind::Error);
+            return;
+        };
+        self.editing = Some(TaskEdit {
+            id,
+            title: task.title,
+            description: task.description,
+            error: None,
+            focus: true,
+        });
+    }
+    
+    /// Saves the edited title and description, or keeps the row in edit mode
+    /// with an error
+    fn save_edit(&mut self) {
+        let Some(edit) = self.editing.as_mut() else { return };
+        let title = edit.title.trim();
+        if title.is_empty() {
+            edit.error = Some("Task title cannot be empty".to_string());
+            return;
+        }
+        let before = store::with_tasks(|manager| manager.get_task(edit.id).cloned());
+        match store::update_task(edit.id, title.to_string(), edit.description.clone()) {
+            Ok(_) => {
+                self.undo = before.map(Undo::Edited);
+                self.editing = None;
+                self.notify("Task updated", NotificationKind::Success);
+            }
+            Err(e) => edit.error = Some(format!("Failed to save: {}", e)),
+        }
+    }
+    
+    fn clear_form(&mut self) {
+        self.new_task_title.clear();
+        self.new_task_description.clear();
+        self.new_task_priority = Priority::default();
+        self.new_task_due.clear();
+        self.new_task_tags.clear();
+        self.new_task_tag.clear();
+    }
+    
+    /// The chips of the add form, plus what is typed in the tag field
+    fn form_tags(&self) -> Vec<String> {
+        let mut tags = self.new_task_tags.clone();
+        add_typed_tags(&mut tags, &self.new_task_tag);
+        tags
+    }
+    
+    /// Turns what is typed in the tag field into chips
+    fn add_form_tags(&mut self) {
+        self.new_task_tags = self.form_tags();
+        self.new_task_tag.clear();
+    }
+    
+    /// Checks the due date field as it is typed: an error when it is not a
+    /// date, or a warning when the date has passed, which is still allowed
+    fn check_new_task_due(&self, today: NaiveDate) -> Result<Option<String>, String> {
+        let due = parse_due_input(&self.new_task_due)?;
+        Ok(due.filter(|date| *date < today).map(|_| "This date has passed; the task will be overdue".to_string()))
+    }
+    
+    /// The add form as JSON for `add_task_full`, or why it cannot be submitted
+    fn new_task_spec(&self) -> Result<String, String> {
+        if self.new_task_title.trim().is_empty() {
+            return Err("Task title cannot be empty".to_string());
+        }
+        let mut spec = serde_json::json!({
+            "title": self.new_task_title,
+            "description": self.new_task_description,
+            "priority": self.new_task_priority.to_string(),
+            "tags": self.form_tags(),
+        });
+        if let Some(date) = parse_due_input(&self.new_task_due)? {
+            spec["due_date"] = date.and_time(NaiveTime::MIN).and_utc().to_rfc3339().into();
+        }
+        Ok(spec.to_string())
+    }
+    
+    /// Every task, in the list's order, copied from the store only when it
+    /// changed
+    fn cached_tasks(&mut self) -> Rc<Vec<TaskSummary>> {
+        let revision = store::revision();
+        match &self.task_cache {
+            Some((cached, tasks)) if *cached == revision => Rc::clone(tasks),
+            _ => {
+                let tasks: Rc<Vec<TaskSummary>> = Rc::new(store::with_tasks(|manager| {
+                    manager.get_tasks_page(0, usize::MAX, self.task_order).into_iter().map(TaskSummary::from).collect()
+                }));
+                self.task_cache = Some((revision, Rc::clone(&tasks)));
+                tasks
+            }
+        }
+    }
+    
+    fn cached_stats(&mut self) -> Rc<TaskStats> {
+        let revision = store::revision();
+        match &self.stats_cache {
+            Some((cached, stats)) if *cached == revision => Rc::clone(stats),
+            _ => {
+                let stats = Rc::new(TaskStats {
+                    report: store::stats_report(),
+                    high_priority: store::with_tasks(|manager| {
+                        manager.get_high_priority_incomplete_tasks().into_iter().cloned().collect()
+                    }),
+                });
+                self.stats_cache = Some((revision, Rc::clone(&stats)));
+                stats
+            }
+        }
+    }
+    
+    /// IDs of the tasks `find` returns for `key`, kept in `cache` as of a
+    /// store revision so the tasks are read again only when either changes
+    fn cached_ids(
+        cache: &mut Option<(u32, String, Rc<HashSet<u32>>)>,
+        key: &str,
+        find: for<'a> fn(&'a TaskManager, &str) -> Vec<&'a Task>,
+    ) -> Rc<HashSet<u32>> {
+        let revision = store::revision();
+        match cache {
+            Some((cached, cached_key, ids)) if *cached == revision && cached_key == key => Rc::clone(ids),
+            _ => {
+                let ids: Rc<HashSet<u32>> =
+                    Rc::new(store::with_tasks(|manager| find(manager, key).iter().map(|task| task.id).collect()));
+                *cache = Some((revision, key.to_string(), Rc::clone(&ids)));
+                ids
+            }
+        }
+    }
+    
+    /// IDs of the tasks matching the search box, or `None` when it is empty
+    fn search_matches(&mut self) -> Option<Rc<HashSet<u32>>> {
+        let query = self.search_query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        Some(Self::cached_ids(&mut self.search_cache, query, TaskManager::search))
+    }
+    
+    /// IDs of the tasks with the tag filter's tag, or `None` without one
+    fn tag_matches(&mut self) -> Option<Rc<HashSet<u32>>> {
+        let tag = self.tag_filter.as_deref()?;
+        Some(Self::cached_ids(&mut self.tag_cache, tag, TaskManager::get_tasks_with_tag))
+    }
+    
+    /// The tasks of `tasks` that the list tab, the search box and the tag
+    /// filter let through
+    fn visible_tasks<'a>(&mut self, tasks: &'a [TaskSummary]) -> Vec<&'a TaskSummary> {
+        let matches = self.search_matches();
+        let tagged = self.tag_matches();
+        let now = Utc::now();
+        tasks
+            .iter()
+            .filter(|task| self.list_filter.matches_summary(task, now))
+            .filter(|task| matches.as_ref().is_none_or(|ids| ids.contains(&task.id)))
+            .filter(|task| tagged.as_ref().is_none_or(|ids| ids.contains(&task.id)))
+            .collect()
+    }
+    
+    /// Drops the cached tasks and statistics, so the next frame reads them
+    /// again. The caches follow the store revision, so this is only needed
+    /// for what the revision does not cover, such as the date changing.
+    fn refresh(&mut self) {
+        self.task_cache = None;
+        self.stats_cache = None;
+        self.search_cache = None;
+        self.tag_cache = None;
+    }
+    
+    /// Applies a change just made through the store to the cached list, so
+    /// the list is not read again. If the store moved on by more than that
+    /// one change, the cache is left to be refilled.
+    fn update_cached_tasks(&mut self, before: u32, change: impl FnOnce(&mut Vec<TaskSummary>)) {
+        let revision = store::revision();
+        if let Some((cached, tasks)) = &mut self.task_cache {
+            if *cached == before && revision == before.wrapping_add(1) {
+                change(Rc::make_mut(tasks));
+                *cached = revision;
+            }
+        }
+    }
+    
+    fn toggle_from_list(&mut self, id: u32, was_completed: bool) {
+        let before = store::revision();
+        let result = store::toggle(id);
+        match (result.toggled, was_completed) {
+            (true, false) => self.notify("Task completed", NotificationKind::Success),
+            (true, true) => self.notify("Task reopened", NotificationKind::Success),
+            (false, _) => self.notify("Task not found", NotificationKind::Error),
+        }
+        // A recurring task also adds its next occurrence, which the list reads
+        if result.toggled && result.new_task_id.is_none() {
+            self.update_cached_tasks(before, |tasks| {
+                if let Some(task) = tasks.iter_mut().find(|task| task.id == id) {
+                    task.completed = !task.completed;
+                }
+            });
+        }
+    }
+    
+    fn remove_from_list(&mut self, id: u32) {
+        let before = store::revision();
+        let task = store::with_tasks(|manager| manager.get_task(id).cloned());
+        match store::remove_task(id) {
+            Ok(true) => {
+                self.undo = task.map(Undo::Removed);
+                self.notify("Task removed", NotificationKind::Success);
+                self.update_cached_tasks(before, |tasks| tasks.retain(|task| task.id != id));
+            }
+            Ok(false) => self.notify("Task not found", NotificationKind::Error),
+            Err(e) => self.notify(format!("Failed to remove task: {}", e), NotificationKind::Error),
+        }
+    }
+    
+    /// Whether task `id` is waiting for the click that confirms its deletion
+    fn is_confirming_delete(&self, id: u32) -> bool {
+        self.pending_delete
+            .is_some_and(|(pending, clicked_at)| pending == id && clicked_at.elapsed() < DELETE_CONFIRM_WINDOW)
+    }
+    
+    /// Handles a click on the delete button of task `id`: the first click
+    /// asks for confirmation, and returns true only for a second click
+    /// within [`DELETE_CONFIRM_WINDOW`]
+    fn confirm_delete(&mut self, id: u32) -> bool {
+        if self.is_confirming_delete(id) {
+            self.pending_delete = None;
+            true
+        } else {
+            self.pending_delete = Some((id, Instant::now()));
+            false
+        }
+    }
+    
+    /// The delete button of task `id`, which reads "Confirm?" after the
+    /// first click. Returns whether the task should be deleted.
+    fn delete_button(&mut self, ui: &mut egui::Ui, id: u32) -> bool {
+        let response = if self.is_confirming_delete(id) {
+            let confirm = egui::RichText::new("Confirm?").color(NotificationKind::Error.color());
+            ui.button(confirm).on_hover_text("Click again to delete")
+        } else {
+            ui.button("🗑").on_hover_text("Delete")
+        };
+        if let Some((_, clicked_at)) = self.pending_delete {
+            // Turn back into 🗑 without waiting for input
+            ui.ctx().request_repaint_after(DELETE_CONFIRM_WINDOW.saturating_sub(clicked_at.elapsed()));
+        }
+        response.clicked() && self.confirm_delete(id)
+    }
+    
+    /// Drops a pending deletion when the user clicks anywhere but a delete
+    /// button, so call this after the buttons have been drawn
+    fn cancel_delete_on_click(&mut self, ctx: &Context, delete_clicked: bool) {
+        if !delete_clicked && ctx.input(|i| i.pointer.any_click()) {
+            self.pending_delete = None;
+        }
+    }
+    
+    /// Whether the title field should grab focus this frame; clears the request
+    fn take_focus_request(&mut self) -> bool {
+        std::mem::take(&mut self.focus_new_task)
+    }
+    
+    /// Queue a notification to show in the top-right corner
+    fn notify(&mut self, message: impl Into<String>, kind: NotificationKind) {
+        self.notifications.push_back((message.into(), kind, Instant::now()));
+    }
+    
+    /// Drop notifications that have been shown for long enough
+    fn expire_notifications(&mut self) {
+        self.notifications
+            .retain(|(_, _, shown_at)| shown_at.elapsed() < NOTIFICATION_LIFETIME);
+    }
+    
+    fn show_notifications(&mut self, ctx: &Context) {
+        self.expire_notifications();
+        if self.notifications.is_empty() {
+            return;
+        }
+        
+        egui::Area::new(egui::Id::new("notifications"))
+            .anchor(egui::Align2::RIGHT_TOP, [-10.0, 10.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (message, kind, _) in &self.notifications {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.colored_label(kind.color(), message);
+                    });
+                }
+            });
+        
+        // Keep repainting so notifications disappear without user input
+        ctx.request_repaint_after(Duration::from_millis(250));
+    }
 }
 
 impl App for TaskManagerApp {
@@ -31,18 +817,44 @@ impl App for TaskManagerApp {
             AppView::KpiDashboard => {
                 self.show_kpi_dashboard(ctx, frame);
             }
+            AppView::Timeline => {
+                self.show_timeline(ctx, frame);
+            }
+            AppView::TaskDetail(id) => {
+                self.show_task_detail(ctx, id);
+            }
         }
+        self.show_notifications(ctx);
+    }
+    
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        storage.set_string(TASK_ORDER_KEY, self.task_order.to_string());
     }
 }
 
 impl TaskManagerApp {
     fn show_task_manager(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        // `command` is Ctrl, or Cmd on a Mac. Both keys need the modifier,
+        // so typing N or F in a text field does not trigger them.
+        // Text fields have their own undo for what is typed in them
+        let typing = ctx.wants_keyboard_input();
+        let shortcuts = ctx.input(|i| Shortcuts {
+            new_task: i.key_pressed(egui::Key::N) && i.modifiers.command,
+            search: i.key_pressed(egui::Key::F) && i.modifiers.command,
+            cancel: i.key_pressed(egui::Key::Escape),
+            undo: i.key_pressed(egui::Key::Z) && i.modifiers.command && !typing,
+        });
+        self.apply_shortcuts(shortcuts);
+        
         CentralPanel::default().show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.heading("🚀 WASM Task Manager");
                 ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                     if ui.button("📊 View KPIs").clicked() {
-                        self.current_view = AppView::KpiDashboard;
+                        self.open_view(AppView::KpiDashboard);
+                    }
+                    if ui.button("🗓 Timeline").clicked() {
+                        self.open_view(AppView::Timeline);
                     }
                 });
             });
@@ -58,6 +870,21 @@ impl TaskManagerApp {
             // Add new task form
             self.show_add_task_form(ui);
             
+            ui.add_space(10.0);
+            
+            // Export and import backups
+            self.show_backup_section(ui);
+            
+            egui::CollapsingHeader::new("⌨ Shortcuts").show(ui, |ui| {
+                egui::Grid::new("shortcuts_help").num_columns(2).spacing([20.0, 4.0]).show(ui, |ui| {
+                    for (keys, action) in SHORTCUTS_HELP {
+                        ui.strong(keys);
+                        ui.label(action);
+                        ui.end_row();
+                    }
+                });
+            });
+            
             ui.add_space(20.0);
             
             // Task list
@@ -65,13 +892,455 @@ impl TaskManagerApp {
         });
     }
     
-    fn show_kpi_dashboard(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+    fn show_kpi_dashboard(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading("📊 Task Management KPIs");
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui.button("← Back to Tasks").clicked() {
+                        self.go_back();
+                    }
+                });
+            });
+            
+            ui.separator();
+            ui.add_space(10.0);
+            
+            // Show KPI content inline instead of using separate app
+            self.show_kpi_content(ui);
+        });
+    }
+    
+    /// Tasks under a header for each day they were created or completed on,
+    /// most recent day first
+    fn show_timeline(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading("🗓 Timeline");
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui.button("← Back to Tasks").clicked() {
+                        self.go_back();
+                    }
+                });
+            });
+            
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Group by:");
+                ui.selectable_value(&mut self.timeline_by_completion, false, "Created");
+                ui.selectable_value(&mut self.timeline_by_completion, true, "Completed");
+            });
+            ui.add_space(10.0);
+            
+            let by_completion = self.timeline_by_completion;
+            let (days, active_days): (Vec<(NaiveDate, Vec<Task>)>, usize) = store::with_tasks(|manager| {
+                let groups = if by_completion {
+                    manager.group_by_day_completed()
+                } else {
+                    manager.group_by_day_created()
+                };
+                let days = groups
+                    .into_iter()
+                    .rev()
+                    .map(|(date, tasks)| (date, tasks.into_iter().cloned().collect()))
+                    .collect();
+                (days, manager.get_active_days_count())
+            });
+            ui.weak(format!("Tasks created on {} different days", active_days));
+            ui.add_space(5.0);
+            
+            if days.is_empty() {
+                ui.label(if by_completion { "No completed tasks yet." } else { "No tasks yet." });
+            }
+            ScrollArea::vertical().show(ui, |ui| {
+                for (date, tasks) in &days {
+                    ui.strong(format!("{} ({})", date.format("%A, %B %-d, %Y"), tasks.len()));
+                    for task in tasks {
+                        ui.horizontal(|ui| {
+                            ui.label(if task.completed { "✅" } else { "⬜" });
+                            ui.label(&task.title);
+                        });
+                    }
+                    ui.add_space(8.0);
+                }
+            });
+        });
+    }
+    
+    fn show_task_statistics(&mut self, ui: &mut egui::Ui) {
+        ui.heading("📈 Task Statistics");
+        ui.add_space(5.0);
+        
+        let stats = self.cached_stats();
+        let report = &stats.report;
+        
+        ui.horizontal(|ui| {
+            ui.group(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Total Tasks");
+                    ui.heading(report.total.to_string());
+                });
+            });
+            
+            ui.group(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Completed");
+                    ui.heading(report.completed.to_string());
+                });
+            });
+            
+            ui.group(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Remaining");
+                    ui.heading(report.pending.to_string());
+                });
+            });
+            
+            if report.total > 0 {
+                ui.group(|ui| {
+                    ui.vertical(|ui| {
+                        ui.label("Completion Rate");
+                        ui.heading(format!("{}%", (report.completion_rate * 100.0) as u32));
+                    });
+                });
+            }
+            
+            if report.current_streak > 0 {
+                ui.group(|ui| {
+                    ui.vertical(|ui| {
+                        ui.label("Streak");
+                        ui.heading(format!("{} days", report.current_streak));
+                    });
+                });
+            }
+        });
+        
+        ui.add_space(10.0);
+        self.show_hot_list(ui, &stats.high_priority);
+    }
+    
+    /// Incomplete high-priority tasks, oldest first
+    fn show_hot_list(&self, ui: &mut egui::Ui, hot: &[Task]) {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(Color32::RED, "⚠");
+                ui.strong(format!("High priority ({})", hot.len()));
+            });
+            if hot.is_empty() {
+                ui.label("Nothing urgent.");
+            }
+            for task in hot {
+                ui.horizontal(|ui| {
+                    ui.colored_label(Color32::RED, "⚠");
+                    ui.label(&task.title);
+                    ui.weak(format!("since {}", task.created_at.format("%Y-%m-%d")));
+                });
+            }
+        });
+    }
+    
+    fn show_add_task_form(&mut self, ui: &mut egui::Ui) {
+        ui.heading("➕ Add New Task");
+        ui.add_space(5.0);
+        
+        let mut submit = false;
+        ui.horizontal(|ui| {
+            ui.label("Title:");
+            let title = ui.text_edit_singleline(&mut self.new_task_title);
+            if self.take_focus_request() {
+                title.request_focus();
+            }
+            // A single-line field gives up focus when Enter is pressed
+            submit = title.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        });
+        
+        ui.horizontal(|ui| {
+            ui.label("Description:");
+            ui.text_edit_multiline(&mut self.new_task_description);
+        });
+        
+        ui.horizontal(|ui| {
+            ui.label("Priority:");
+            egui::ComboBox::from_id_salt("new_task_priority")
+                .selected_text(self.new_task_priority.to_string())
+                .show_ui(ui, |ui| {
+                    for priority in [Priority::Low, Priority::Medium, Priority::High] {
+                        ui.selectable_value(&mut self.new_task_priority, priority, priority.to_string());
+                    }
+                });
+            ui.label("Due:");
+            ui.add(egui::TextEdit::singleline(&mut self.new_task_due).hint_text("YYYY-MM-DD").desired_width(90.0));
+        });
+        
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Tags:");
+            let mut removed = None;
+            for (i, tag) in self.new_task_tags.iter().enumerate() {
+                if tag_badge(ui, tag, format!("{} ✕", tag)).on_hover_text("Remove").clicked() {
+                    removed = Some(i);
+                }
+            }
+            if let Some(i) = removed {
+                self.new_task_tags.remove(i);
+            }
+            let input = ui.add(
+                egui::TextEdit::singleline(&mut self.new_task_tag).hint_text("Add a tag...").desired_width(100.0),
+            );
+            if input.changed() && self.new_task_tag.contains(',') {
+                self.add_form_tags();
+            }
+            // Enter adds the chip and keeps the field for the next tag
+            if input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                self.add_form_tags();
+                input.request_focus();
+            }
+        });
+        match self.check_new_task_due(chrono::Local::now().date_naive()) {
+            Ok(None) => {}
+            Ok(Some(warning)) => {
+                ui.colored_label(Color32::from_rgb(230, 160, 40), format!("⚠ {}", warning));
+            }
+            Err(error) => {
+                ui.colored_label(NotificationKind::Error.color(), error);
+            }
+        }
+        
+        ui.add_space(5.0);
+        
+        if ui.button("Add Task").clicked() || submit {
+            self.submit_new_task();
+        }
+        // Enter leaves the title field; take it back for the next task
+        if submit {
+            self.focus_new_task = true;
+        }
+    }
+    
+    fn submit_new_task(&mut self) {
+        let added = self.new_task_spec().and_then(|spec| {
+            store::add_task_full(&spec).map_err(|e| format!("Failed to add task: {}", e))
+        });
+        match added {
+            Ok(_) => {
+                self.clear_form();
+                self.notify("Task added", NotificationKind::Success);
+            }
+            Err(message) => self.notify(message, NotificationKind::Error),
+        }
+    }
+    
+    fn show_backup_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("💾 Backup").show(ui, |ui| {
+            if ui.button("Export").on_hover_text("Copy a backup of all tasks to the clipboard").clicked() {
+                match store::export_backup() {
+                    Ok(backup) => {
+                        ui.ctx().copy_text(backup);
+                        self.notify("Backup copied to the clipboard", NotificationKind::Success);
+                    }
+                    Err(e) => self.notify(format!("Failed to export: {}", e), NotificationKind::Error),
+                }
+            }
+            
+            ui.add(
+                egui::TextEdit::multiline(&mut self.backup_text)
+                    .hint_text("Paste a backup here...")
+                    .desired_rows(3),
+            );
+            ui.horizontal(|ui| {
+                let has_backup = !self.backup_text.trim().is_empty();
+                let merge = ui.add_enabled(has_backup, egui::Button::new("Import (merge)")).clicked();
+                let replace = ui
+                    .add_enabled(has_backup, egui::Button::new("Import (replace)"))
+                    .on_hover_text("Replace all current tasks with the backup")
+                    .clicked();
+                if merge || replace {
+                    match store::import_backup(&self.backup_text, merge) {
+                        Ok(summary) => {
+                            self.backup_text.clear();
+                            self.notify(summary, NotificationKind::Success);
+                        }
+                        Err(e) => self.notify(format!("Failed to import: {}", e), NotificationKind::Error),
+                    }
+                }
+            });
+        });
+    }
+    
+    fn show_task_list(&mut self, ui: &mut egui::Ui) {
+        let tasks = self.cached_tasks();
+        ui.horizontal(|ui| {
+            ui.heading("📋 Tasks");
+            if ui.small_button("⟳").on_hover_text("Reload the tasks and statistics").clicked() {
+                self.refresh();
+            }
+            let any_completed = tasks.iter().any(|task| task.completed);
+            if ui.add_enabled(any_completed, egui::Button::new("🧹 Clear completed")).clicked() {
+                let removed = store::retain_incomplete_tasks();
+                self.notify(format!("Removed {} completed tasks", removed), NotificationKind::Success);
+            }
+        });
+        ui.horizontal(|ui| {
+            for (filter, label) in [
+                (TaskFilter::All, "All"),
+                (TaskFilter::Active, "Active"),
+                (TaskFilter::Completed, "Completed"),
+            ] {
+                ui.selectable_value(&mut self.list_filter, filter, label);
+            }
+            ui.separator();
+            let order = self.task_order;
+            let label = |order| TASK_ORDERS.iter().find(|(o, _)| *o == order).map_or("", |(_, label)| *label);
+            egui::ComboBox::from_id_salt("task_order").selected_text(label(order)).show_ui(ui, |ui| {
+                for (order, label) in TASK_ORDERS {
+                    ui.selectable_value(&mut self.task_order, order, label);
+                }
+            });
+            if self.task_order != order {
+                self.task_cache = None;
+            }
+            let search =
+                ui.add(egui::TextEdit::singleline(&mut self.search_query).hint_text("🔍 Search...").desired_width(160.0));
+            if std::mem::take(&mut self.focus_search) {
+                search.request_focus();
+            }
+            if !self.search_query.is_empty() && ui.small_button("✖").on_hover_text("Clear the search").clicked() {
+                self.search_query.clear();
+            }
+            if let Some(tag) = self.tag_filter.clone() {
+                ui.label("Tag:");
+                if tag_badge(ui, &tag, format!("{} ✕", tag)).on_hover_text("Clear the tag filter").clicked() {
+                    self.tag_filter = None;
+                }
+            }
+        });
+        ui.add_space(5.0);
+        
+        let visible = self.visible_tasks(&tasks);
+        let today = chrono::Local::now().date_naive();
+        let mut delete_clicked = false;
+        if tasks.is_empty() {
+            ui.label("No tasks yet. Add one above!");
+        } else if visible.is_empty() {
+            ui.label("No tasks match");
+        } else {
+            ScrollArea::vertical().show(ui, |ui| {
+                for task in visible {
+                    // The row's own copy, so a click shows on this frame; the
+                    // cached list catches up in `toggle_from_list`
+                    let mut completed = task.completed;
+                    let editing = self.editing.as_ref().is_some_and(|edit| edit.id == task.id);
+                    ui.group(|ui| {
+                        // Both modes keep the checkbox and the button column where
+                        // they are, so the row does not jump
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut completed, "").changed() {
+                                self.toggle_from_list(task.id, task.completed);
+                            }
+                            
+                            ui.vertical(|ui| {
+                                if editing {
+                                    self.show_edit_fields(ui);
+                                } else {
+                                    let title = egui::Label::new(title_text(&task.title, completed, ui.visuals()))
+                                        .sense(egui::Sense::click());
+                                    if ui.add(title).on_hover_cursor(egui::CursorIcon::PointingHand).clicked() {
+                                        self.open_view(AppView::TaskDetail(task.id));
+                                    }
+                                    ui.horizontal(|ui| {
+                                        ui.colored_label(priority_color(task.priority), "●");
+                                        ui.small(format!("{} priority", task.priority));
+                                        if let Some(due) = task.due_date {
+                                            let mut label = egui::RichText::new(due_label(due, today)).small();
+                                            // Overdue only matters while the task is open
+                                            if !completed && due.date_naive() < today {
+                                                label = label.color(NotificationKind::Error.color());
+                                            }
+                                            ui.label(label).on_hover_text(due.format("%Y-%m-%d").to_string());
+                                        }
+                                        for tag in &task.tags {
+                                            let hover = format!("Show only tasks tagged {}", tag);
+                                            if tag_badge(ui, tag, tag).on_hover_text(hover).clicked() {
+                                                self.tag_filter = Some(tag.clone());
+                                            }
+                                        }
+                                    });
+                                }
+                            });
+                            
+                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                if editing {
+                                    if ui.button("Cancel").clicked() {
+                                        self.editing = None;
+                                    }
+                                    if ui.button("Save").clicked() {
+                                        self.save_edit();
+                                    }
+                                    return;
+                                }
+                                let pending = self.pending_delete;
+                                if self.delete_button(ui, task.id) {
+                                    self.remove_from_list(task.id);
+                                }
+                                delete_clicked |= self.pending_delete != pending;
+                                let open = self.open_task == Some(task.id);
+                                if ui.button(if open { "▲" } else { "▼" }).on_hover_text("Details").clicked() {
+                                    self.open_task = if open { None } else { Some(task.id) };
+                                }
+                                if ui.button("✏").on_hover_text("Edit").clicked() {
+                                    self.start_editing(task.id);
+                                }
+                            });
+                        });
+                        
+                        // Only the open task is fetched in full
+                        if self.open_task == Some(task.id) {
+                            if let Some(full) = store::with_tasks(|manager| manager.get_task(task.id).cloned()) {
+                                show_task_details(ui, &full);
+                            }
+                        }
+                    });
+                    ui.add_space(5.0);
+                }
+            });
+        }
+        self.cancel_delete_on_click(ui.ctx(), delete_clicked);
+    }
+    
+    /// Title and description fields for the row being edited. Enter in the
+    /// title saves; Escape cancels through the shortcuts.
+    fn show_edit_fields(&mut self, ui: &mut egui::Ui) {
+        let Some(edit) = self.editing.as_mut() else { return };
+        let title = ui.add(egui::TextEdit::singleline(&mut edit.title).hint_text("Title"));
+        if std::mem::take(&mut edit.focus) {
+            title.request_focus();
+        }
+        ui.add(egui::TextEdit::multiline(&mut edit.description).hint_text("Description").desired_rows(2));
+        if let Some(error) = &edit.error {
+            ui.colored_label(NotificationKind::Error.color(), error);
+        }
+        // A single-line field gives up focus when Enter is pressed
+        if title.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            self.save_edit();
+        }
+    }
+    
+    /// Every field of task `id`, with buttons to edit, delete, duplicate and
+    /// complete it. Escape leaves an edit, then the screen.
+    fn show_task_detail(&mut self, ctx: &Context, id: u32) {
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) && self.editing.take().is_none() {
+            self.go_back();
+            return;
+        }
+        let task = store::with_tasks(|manager| manager.get_task(id).cloned());
+        let editing = self.editing.as_ref().is_some_and(|edit| edit.id == id);
+        let mut delete_clicked = false;
+        
         CentralPanel::default().show(ctx, |ui| {
             ui.horizontal(|ui| {
-                ui.heading("📊 Task Management KPIs");
+                ui.heading(format!("Task #{}", id));
                 ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                    if ui.button("← Back to Tasks").clicked() {
-                        self.current_view = AppView::TaskManager;
+                    if ui.button("← Back").clicked() {
+                        self.go_back();
                     }
                 });
             });
@@ -79,124 +1348,174 @@ impl TaskManagerApp {
             ui.separator();
             ui.add_space(10.0);
             
-            // Show KPI content inline instead of using separate app
-            self.show_kpi_content(ui);
-        });
-    }
-    
-    fn show_task_statistics(&self, ui: &mut egui::Ui) {
-        ui.heading("📈 Task Statistics");
-        ui.add_space(5.0);
-        
-        // Get task counts from WASM functions
-        let total_count = crate::wasm::get_task_count();
-        let completed_count = crate::wasm::get_completed_count();
-        let remaining_count = total_count - completed_count;
-        
-        ui.horizontal(|ui| {
-            ui.group(|ui| {
-                ui.vertical(|ui| {
-                    ui.label("Total Tasks");
-                    ui.heading(total_count.to_string());
-                });
-            });
+            // Removed elsewhere, or by the Delete button on an earlier frame
+            let Some(task) = task else {
+                ui.label("This task no longer exists.");
+                return;
+            };
             
-            ui.group(|ui| {
-                ui.vertical(|ui| {
-                    ui.label("Completed");
-                    ui.heading(completed_count.to_string());
+            if editing {
+                self.show_edit_fields(ui);
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        self.save_edit();
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.editing = None;
+                    }
                 });
-            });
+            } else {
+                ui.heading(title_text(&task.title, task.completed, ui.visuals()));
+                if task.description.is_empty() {
+                    ui.weak("No description");
+                } else {
+                    ui.label(&task.description);
+                }
+            }
             
-            ui.group(|ui| {
-                ui.vertical(|ui| {
-                    ui.label("Remaining");
-                    ui.heading(remaining_count.to_string());
+            ui.add_space(10.0);
+            egui::Grid::new("task_detail_fields").num_columns(2).spacing([20.0, 4.0]).show(ui, |ui| {
+                let date = |date: Option<DateTime<Utc>>| {
+                    date.map_or("—".to_string(), |date| date.format("%Y-%m-%d %H:%M UTC").to_string())
+                };
+                let fields = [
+                    ("Status", if task.completed { "Completed" } else { "Open" }.to_string()),
+                    ("Priority", task.priority.to_string()),
+                    ("Due", date(task.due_date)),
+                    ("Recurrence", task.recurrence.map_or("None".to_string(), |recurrence| recurrence.to_string())),
+                    ("Estimate", task.estimated_hours.map_or("—".to_string(), |hours| format!("{} hours", hours))),
+                    ("Created", date(Some(task.created_at))),
+                    ("Completed", date(task.completed_at)),
+                ];
+                for (label, value) in fields {
+                    ui.strong(label);
+                    ui.label(value);
+                    ui.end_row();
+                }
+                ui.strong("Tags");
+                ui.horizontal_wrapped(|ui| {
+                    for tag in &task.tags {
+                        if tag_badge(ui, tag, format!("{} ✕", tag)).on_hover_text("Remove").clicked() {
+                            self.remove_detail_tag(id, tag);
+                        }
+                    }
+                    let input = ui.add(
+                        egui::TextEdit::singleline(&mut self.detail_tag).hint_text("Add a tag...").desired_width(100.0),
+                    );
+                    if input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        self.add_detail_tags(id);
+                        input.request_focus();
+                    }
                 });
+                ui.end_row();
             });
             
-            if total_count > 0 {
-                let completion_rate = (completed_count as f32 / total_count as f32 * 100.0) as u32;
-                ui.group(|ui| {
-                    ui.vertical(|ui| {
-                        ui.label("Completion Rate");
-                        ui.heading(format!("{}%", completion_rate));
-                    });
-                });
-            }
+            ui.add_space(10.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!editing, egui::Button::new("✏ Edit")).clicked() {
+                    self.start_editing(id);
+                }
+                let pending = self.pending_delete;
+                if self.delete_button(ui, id) {
+                    self.delete_from_detail(id);
+                }
+                delete_clicked = self.pending_delete != pending;
+                if ui.button("⧉ Duplicate").clicked() {
+                    self.duplicate_task(&task);
+                }
+                let toggle = if task.completed { "↺ Reopen" } else { "✔ Mark Complete" };
+                if ui.button(toggle).clicked() {
+                    self.toggle_from_list(id, task.completed);
+                }
+            });
         });
+        self.cancel_delete_on_click(ctx, delete_clicked);
     }
     
-    fn show_add_task_form(&mut self, ui: &mut egui::Ui) {
-        ui.heading("➕ Add New Task");
-        ui.add_space(5.0);
-        
-        ui.horizontal(|ui| {
-            ui.label("Title:");
-            ui.text_edit_singleline(&mut self.new_task_title);
-        });
-        
-        ui.horizontal(|ui| {
-            ui.label("Description:");
-            ui.text_edit_multiline(&mut self.new_task_description);
+    /// Adds the tags typed on the detail screen to task `id`, keeping the
+    /// text when the store rejects one
+    fn add_detail_tags(&mut self, id: u32) {
+        let mut tags = Vec::new();
+        add_typed_tags(&mut tags, &self.detail_tag);
+        for tag in tags {
+            if let Err(e) = store::add_task_tag(id, &tag) {
+                self.notify(format!("Failed to add tag: {}", e), NotificationKind::Error);
+                return;
+            }
+        }
+        self.detail_tag.clear();
+    }
+    
+    fn remove_detail_tag(&mut self, id: u32, tag: &str) {
+        if let Err(e) = store::remove_task_tag(id, tag) {
+            self.notify(format!("Failed to remove tag: {}", e), NotificationKind::Error);
+        }
+    }
+    
+    /// Removes task `id` and leaves its detail screen
+    fn delete_from_detail(&mut self, id: u32) {
+        self.remove_from_list(id);
+        if self.editing.as_ref().is_some_and(|edit| edit.id == id) {
+            self.editing = None;
+        }
+        if self.current_view == AppView::TaskDetail(id) {
+            self.go_back();
+        }
+    }
+    
+    /// Adds a copy of `task` as a new, open task and shows it in place of
+    /// the original. The copy does not recur, as `add_task_full` takes no
+    /// recurrence.
+    fn duplicate_task(&mut self, task: &Task) {
+        let spec = serde_json::json!({
+            "title": task.title,
+            "description": task.description,
+            "priority": task.priority.to_string(),
+            "due_date": task.due_date.map(|due| due.to_rfc3339()),
+            "tags": task.tags,
+            "estimated_hours": task.estimated_hours,
         });
-        
-        ui.add_space(5.0);
-        
-        if ui.button("Add Task").clicked() {
-            if !self.new_task_title.trim().is_empty() {
-                crate::wasm::add_task(
-                    self.new_task_title.clone(),
-                    self.new_task_description.clone()
-                );
-                self.new_task_title.clear();
-                self.new_task_description.clear();
+        let added = store::add_task_full(&spec.to_string())
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str::<Task>(&json).map_err(|e| e.to_string()));
+        match added {
+            Ok(copy) => {
+                self.notify("Task duplicated", NotificationKind::Success);
+                if self.current_view == AppView::TaskDetail(task.id) {
+                    self.current_view = AppView::TaskDetail(copy.id);
+                }
             }
+            Err(e) => self.notify(format!("Failed to duplicate task: {}", e), NotificationKind::Error),
         }
     }
     
-    fn show_task_list(&self, ui: &mut egui::Ui) {
-        ui.heading("📋 Tasks");
-        ui.add_space(5.0);
+    fn show_weekly_summary(&self, ui: &mut egui::Ui) {
+        let summary = store::with_tasks(|manager| manager.weekly_summary());
         
-        // Get tasks from WASM
-        let tasks_json = crate::wasm::get_all_tasks_json();
-        match serde_json::from_str::<Vec<crate::task::Task>>(&tasks_json) {
-            Ok(tasks) => {
-                if tasks.is_empty() {
-                    ui.label("No tasks yet. Add one above!");
+        ui.heading("This week");
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            let cards = [
+                ("Created", summary.created.to_string()),
+                ("Completed", summary.completed.to_string()),
+                ("Removed (this session)", summary.removed.to_string()),
+                ("Completion Rate", format!("{:.0}%", summary.completion_rate * 100.0)),
+                ("Avg. Time (hours)", if summary.completed > 0 {
+                    format!("{:.1}", summary.avg_completion_hours)
                 } else {
-                    ScrollArea::vertical().show(ui, |ui| {
-                        for task in tasks.iter() {
-                            ui.group(|ui| {
-                                ui.horizontal(|ui| {
-                                    if ui.checkbox(&mut task.completed.clone(), "").clicked() {
-                                        crate::wasm::toggle_task(task.id);
-                                    }
-                                    
-                                    ui.vertical(|ui| {
-                                        ui.strong(&task.title);
-                                        if !task.description.is_empty() {
-                                            ui.label(&task.description);
-                                        }
-                                    });
-                                    
-                                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                                        if ui.button("🗑").clicked() {
-                                            crate::wasm::remove_task(task.id);
-                                        }
-                                    });
-                                });
-                            });
-                            ui.add_space(5.0);
-                        }
+                    "N/A".to_string()
+                }),
+            ];
+            for (label, value) in cards {
+                ui.group(|ui| {
+                    ui.vertical(|ui| {
+                        ui.label(label);
+                        ui.heading(value);
                     });
-                }
-            }
-            Err(_) => {
-                ui.label("Error loading tasks");
+                });
             }
-        }
+        });
     }
     
     fn show_kpi_content(&self, ui: &mut egui::Ui) {
@@ -213,17 +1532,32 @@ impl TaskManagerApp {
         ui.add_space(10.0);
         
         // Get real task data for KPIs
-        let total_tasks = crate::wasm::get_task_count();
-        let completed_tasks = crate::wasm::get_completed_count();
+        let (total_tasks, completed_tasks) =
+            store::with_tasks(|manager| (manager.get_total_count(), manager.get_completed_count()));
         let incomplete_tasks = total_tasks - completed_tasks;
         let completion_rate = if total_tasks > 0 {
             (completed_tasks as f32 / total_tasks as f32 * 100.0) as u32
         } else {
             0
         };
-        let avg_completion_time = crate::wasm::get_average_completion_time();
+        let (avg_completion_time, streak, estimation_error) = store::with_tasks(|manager| {
+            (
+                manager.get_average_completion_time_hours().unwrap_or(0.0),
+                manager.get_completion_streak(),
+                manager.get_estimation_accuracy().unwrap_or(f64::NAN),
+            )
+        });
         
         ui.heading("KPI Overview");
+        let stale = store::with_tasks(|manager| manager.get_tasks_stale_for_n_days(STALE_DAYS).len());
+        if stale > 0 {
+            let tasks = if stale == 1 { "task" } else { "tasks" };
+            ui.colored_label(
+                Color32::from_rgb(230, 160, 40),
+                format!("⚠ {} {} untouched for > {} days", stale, tasks, STALE_DAYS),
+            )
+            .on_hover_text("Incomplete tasks created before then; finish, reschedule or remove them");
+        }
         ui.add_space(10.0);
         
         ui.horizontal(|ui| {
@@ -258,8 +1592,30 @@ impl TaskManagerApp {
                     });
                 });
             });
+            
+            ui.group(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Streak");
+                    ui.heading(format!("🔥 {} {}", streak, if streak == 1 { "day" } else { "days" }));
+                });
+            });
+            
+            ui.group(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Estimation Accuracy")
+                        .on_hover_text("Average difference between estimated and actual hours of completed tasks");
+                    ui.heading(if estimation_error.is_nan() {
+                        "N/A".to_string()
+                    } else {
+                        format!("±{:.0}%", estimation_error * 100.0)
+                    });
+                });
+            });
         });
         
+        ui.add_space(10.0);
+        self.show_weekly_summary(ui);
+        
         ui.add_space(20.0);
         
         // Time series charts with real data
@@ -269,44 +1625,49 @@ impl TaskManagerApp {
         use egui_plot::{Line, Plot, PlotPoints};
         
         // Get real time series data
-        let completed_series_json = crate::wasm::get_completed_tasks_time_series(30);
-        let incomplete_series_json = crate::wasm::get_incomplete_tasks_time_series(30);
-        let cumulative_series_json = crate::wasm::get_cumulative_completed_time_series(30);
+        let (completed_data, incomplete_data, cumulative_data) = store::with_tasks(|manager| {
+            (
+                series_points(&manager.get_completed_tasks_time_series(30)),
+                series_points(&manager.get_incomplete_tasks_time_series(30)),
+                series_points(&manager.get_cumulative_completed_time_series(30)),
+            )
+        });
         
         Plot::new("time_series_plot")
             .height(250.0)
             .show(ui, |plot_ui| {
-                // Parse and plot completed tasks per day
-                if let Ok(completed_data) = serde_json::from_str::<Vec<[f64; 2]>>(&completed_series_json) {
-                    if !completed_data.is_empty() {
-                        plot_ui.line(
-                            Line::new(PlotPoints::from(completed_data))
-                                .color(Color32::from_rgb(100, 200, 100))
-                                .name("Tasks Completed/Day")
-                        );
-                    }
+                let all_points = [
+                    completed_data.as_slice(),
+                    incomplete_data.as_slice(),
+                    cumulative_data.as_slice(),
+                ].concat();
+                crate::kpi_app::show_point_tooltip(plot_ui, &all_points, "tasks");
+                
+                // Plot completed tasks per day
+                if !completed_data.is_empty() {
+                    plot_ui.line(
+                        Line::new(PlotPoints::from(completed_data))
+                            .color(Color32::from_rgb(100, 200, 100))
+                            .name("Tasks Completed/Day")
+                    );
                 }
                 
-                // Parse and plot incomplete tasks
-                if let Ok(incomplete_data) = serde_json::from_str::<Vec<[f64; 2]>>(&incomplete_series_json) {
-                    if !incomplete_data.is_empty() {
-                        plot_ui.line(
-                            Line::new(PlotPoints::from(incomplete_data))
-                                .color(Color32::from_rgb(200, 100, 100))
-                                .name("Incomplete Tasks")
-                        );
-                    }
+                // Plot incomplete tasks
+                if !incomplete_data.is_empty() {
+                    plot_ui.line(
+                        Line::new(PlotPoints::from(incomplete_data))
+                            .color(Color32::from_rgb(200, 100, 100))
+                            .name("Incomplete Tasks")
+                    );
                 }
                 
-                // Parse and plot cumulative completed tasks
-                if let Ok(cumulative_data) = serde_json::from_str::<Vec<[f64; 2]>>(&cumulative_series_json) {
-                    if !cumulative_data.is_empty() {
-                        plot_ui.line(
-                            Line::new(PlotPoints::from(cumulative_data))
-                                .color(Color32::from_rgb(100, 100, 200))
-                                .name("Cumulative Completed")
-                        );
-                    }
+                // Plot cumulative completed tasks
+                if !cumulative_data.is_empty() {
+                    plot_ui.line(
+                        Line::new(PlotPoints::from(cumulative_data))
+                            .color(Color32::from_rgb(100, 100, 200))
+                            .name("Cumulative Completed")
+                    );
                 }
             });
             
@@ -315,48 +1676,53 @@ impl TaskManagerApp {
         
         ui.add_space(20.0);
         
+        ui.heading("🗓️ Task Creation by Weekday");
+        ui.add_space(10.0);
+        
+        let heatmap = store::with_tasks(|manager| manager.get_tasks_created_by_day_of_week());
+        crate::kpi_app::show_day_of_week_heatmap(ui, &heatmap);
+        
+        ui.add_space(20.0);
+        
+        ui.heading("⏱ Completion Time Distribution");
+        ui.add_space(10.0);
+        
+        let bucket_hours = crate::kpi_app::COMPLETION_HISTOGRAM_BUCKET_HOURS;
+        let histogram = store::with_tasks(|manager| manager.get_completion_histogram(bucket_hours));
+        crate::kpi_app::show_completion_histogram_chart(ui, &histogram, bucket_hours);
+        
+        ui.add_space(20.0);
+        
         // Task Completion Predictions
         ui.heading("🔮 Task Completion Predictions");
         ui.add_space(10.0);
         
-        let predictions_json = crate::wasm::get_task_completion_predictions();
-        match serde_json::from_str::<Vec<serde_json::Value>>(&predictions_json) {
-            Ok(predictions) => {
-                if predictions.is_empty() {
-                    ui.label("No incomplete tasks to predict");
-                } else {
-                    ui.label(format!("Predictions for {} incomplete tasks:", predictions.len()));
-                    ui.add_space(5.0);
-                    
-                    // Show predictions in a scrollable area
-                    ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
-                        for prediction in predictions.iter().take(10) { // Show max 10 predictions
-                            if let (Some(task_id), Some(predicted_hours)) = (
-                                prediction["task_id"].as_u64(),
-                                prediction["predicted_hours"].as_f64()
-                            ) {
-                                ui.horizontal(|ui| {
-                                    ui.label(format!("Task #{}: ", task_id));
-                                    if predicted_hours < 1.0 {
-                                        ui.label(format!("{:.0} minutes", predicted_hours * 60.0));
-                                    } else if predicted_hours < 24.0 {
-                                        ui.label(format!("{:.1} hours", predicted_hours));
-                                    } else {
-                                        ui.label(format!("{:.1} days", predicted_hours / 24.0));
-                                    }
-                                });
-                            }
-                        }
-                        
-                        if predictions.len() > 10 {
-                            ui.label(format!("... and {} more", predictions.len() - 10));
+        let predictions = store::with_tasks(|manager| manager.predict_task_completion_times());
+        if predictions.is_empty() {
+            ui.label("No incomplete tasks to predict");
+        } else {
+            ui.label(format!("Predictions for {} incomplete tasks:", predictions.len()));
+            ui.add_space(5.0);
+            
+            // Show predictions in a scrollable area
+            ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+                for &(task_id, predicted_hours) in predictions.iter().take(10) { // Show max 10 predictions
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Task #{}: ", task_id));
+                        if predicted_hours < 1.0 {
+                            ui.label(format!("{:.0} minutes", predicted_hours * 60.0));
+                        } else if predicted_hours < 24.0 {
+                            ui.label(format!("{:.1} hours", predicted_hours));
+                        } else {
+                            ui.label(format!("{:.1} days", predicted_hours / 24.0));
                         }
                     });
                 }
-            }
-            Err(_) => {
-                ui.label("Error loading predictions");
-            }
+                
+                if predictions.len() > 10 {
+                    ui.label(format!("... and {} more", predictions.len() - 10));
+                }
+            });
         }
         
         ui.add_space(20.0);
@@ -373,4 +1739,536 @@ impl TaskManagerApp {
             ui.label(format!("• You have {} incomplete tasks - consider prioritizing older ones", incomplete_tasks));
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_app_options() {
+        let parse = |json: &str| serde_json::from_str::<AppOptions>(json);
+        let defaults = parse("{}").unwrap();
+        assert_eq!(defaults, AppOptions::default());
+        assert_eq!(defaults.theme(), egui::ThemePreference::System);
+        assert!(TaskManagerApp::with_options(&defaults).current_view == AppView::TaskManager);
+        
+        let options = parse(r#"{"initial_view": "kpi", "dark_mode": true}"#).unwrap();
+        assert!(TaskManagerApp::with_options(&options).current_view == AppView::KpiDashboard);
+        assert_eq!(options.theme(), egui::ThemePreference::Dark);
+        assert_eq!(parse(r#"{"dark_mode": false}"#).unwrap().theme(), egui::ThemePreference::Light);
+        assert_eq!(parse(r#"{"follow_system_theme": false}"#).unwrap().theme(), egui::ThemePreference::Light);
+        let both = parse(r#"{"dark_mode": true, "follow_system_theme": true}"#).unwrap();
+        assert_eq!(both.theme(), egui::ThemePreference::System);
+        
+        assert!(parse(r#"{"initial_view": "settings"}"#).is_err());
+        assert!(parse(r#"{"darkMode": true}"#).is_err());
+    }
+    
+    #[test]
+    fn test_new_task_shortcut_requests_focus_once() {
+        let mut app = TaskManagerApp::new();
+        assert!(!app.take_focus_request());
+        
+        app.apply_shortcuts(Shortcuts { new_task: true, ..Shortcuts::default() });
+        assert!(app.take_focus_request());
+        assert!(!app.take_focus_request());
+    }
+    
+    #[test]
+    fn test_escape_clears_form_and_focus_request() {
+        let mut app = TaskManagerApp::new();
+        app.new_task_title = "Draft".to_string();
+        app.new_task_description = "Notes".to_string();
+        app.apply_shortcuts(Shortcuts { new_task: true, ..Shortcuts::default() });
+        
+        app.apply_shortcuts(Shortcuts { cancel: true, ..Shortcuts::default() });
+        assert!(app.new_task_title.is_empty());
+        assert!(app.new_task_description.is_empty());
+        assert!(!app.take_focus_request());
+        
+        // Ctrl+N and Escape in the same frame: the form is cleared, not focused
+        app.apply_shortcuts(Shortcuts { new_task: true, cancel: true, ..Shortcuts::default() });
+        assert!(!app.take_focus_request());
+    }
+    
+    #[test]
+    fn test_search_shortcuts() {
+        let mut app = TaskManagerApp::new();
+        app.apply_shortcuts(Shortcuts { search: true, ..Shortcuts::default() });
+        assert!(app.focus_search);
+        
+        // Escape clears the search before the form, and one of them per press
+        app.search_query = "milk".to_string();
+        app.new_task_title = "Draft".to_string();
+        app.apply_shortcuts(Shortcuts { cancel: true, ..Shortcuts::default() });
+        assert!(app.search_query.is_empty());
+        assert!(!app.focus_search);
+        assert_eq!(app.new_task_title, "Draft");
+        app.apply_shortcuts(Shortcuts { cancel: true, ..Shortcuts::default() });
+        assert!(app.new_task_title.is_empty());
+    }
+    
+    /// Held by tests that change the native store, so they do not move
+    /// each other's revisions
+    #[cfg(not(feature = "web"))]
+    static STORE: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    
+    /// Takes [`STORE`] and empties the native store. Tests hold the returned
+    /// guard throughout.
+    #[cfg(not(feature = "web"))]
+    fn reset_globals() -> std::sync::MutexGuard<'static, ()> {
+        let store = STORE.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        store::reset();
+        store
+    }
+    
+    // The web build reads the `wasm` bindings, which count nothing
+    #[cfg(not(feature = "web"))]
+    #[test]
+    fn test_caches_read_the_store_only_after_changes() {
+        let _store = reset_globals();
+        let mut app = TaskManagerApp::new();
+        let start = store::fetch_count();
+        let fetches = || store::fetch_count() - start;
+        for _ in 0..10 {
+            app.cached_tasks();
+            app.cached_stats();
+        }
+        // The list, the report and the high-priority tasks, once each
+        assert_eq!(fetches(), 3);
+        
+        // A change made elsewhere moves the revision on
+        let added = store::add_task_full(r#"{"title":"Elsewhere","priority":"high"}"#).unwrap();
+        let id = serde_json::from_str::<Task>(&added).unwrap().id;
+        app.cached_tasks();
+        app.cached_stats();
+        assert_eq!(fetches(), 6);
+        
+        // Changes from the list update it in place, leaving only the statistics to read
+        let find = |app: &mut TaskManagerApp| app.cached_tasks().iter().find(|task| task.id == id).cloned();
+        app.toggle_from_list(id, false);
+        assert!(find(&mut app).unwrap().completed);
+        assert_eq!(fetches(), 6);
+        app.cached_stats();
+        assert_eq!(fetches(), 8);
+        // Removing reads only the task itself, kept for Ctrl+Z
+        app.remove_from_list(id);
+        assert!(find(&mut app).is_none());
+        assert_eq!(fetches(), 9);
+        
+        app.refresh();
+        app.cached_tasks();
+        app.cached_stats();
+        assert_eq!(fetches(), 12);
+    }
+    
+    #[cfg(not(feature = "web"))]
+    #[test]
+    fn test_list_toggles_keep_cache_and_store_in_step() {
+        let _store = reset_globals();
+        let mut app = TaskManagerApp::new();
+        let added = store::add_task_full(r#"{"title":"Tick me"}"#).unwrap();
+        let id = serde_json::from_str::<Task>(&added).unwrap().id;
+        let cached = |app: &mut TaskManagerApp| {
+            app.cached_tasks().iter().find(|task| task.id == id).map(|task| task.completed)
+        };
+        let stored = || store::with_tasks(|manager| manager.get_task(id).map(|task| task.completed));
+        assert_eq!(cached(&mut app), Some(false));
+        
+        // What the row saw before the click is passed on, as the checkbox does
+        for was_completed in [false, true, false] {
+            app.toggle_from_list(id, was_completed);
+            assert_eq!(cached(&mut app), Some(!was_completed));
+            assert_eq!(stored(), Some(!was_completed));
+        }
+        
+        app.remove_from_list(id);
+        assert_eq!(cached(&mut app), None);
+        app.toggle_from_list(id, true);
+        assert_eq!(app.notifications.back().unwrap().0, "Task not found");
+        assert_eq!(cached(&mut app), None);
+    }
+    
+    #[cfg(not(feature = "web"))]
+    #[test]
+    fn test_inline_editing() {
+        let _store = reset_globals();
+        let mut app = TaskManagerApp::new();
+        let added = store::add_task_full(r#"{"title":"Tpyo","description":"Notes"}"#).unwrap();
+        let id = serde_json::from_str::<Task>(&added).unwrap().id;
+        let stored_title = || store::with_tasks(|manager| manager.get_task(id).unwrap().title.clone());
+        
+        app.start_editing(id);
+        let edit = app.editing.as_mut().unwrap();
+        assert_eq!((edit.title.as_str(), edit.description.as_str()), ("Tpyo", "Notes"));
+        edit.title = "  ".to_string();
+        app.save_edit();
+        assert_eq!(app.editing.as_ref().unwrap().error.as_deref(), Some("Task title cannot be empty"));
+        assert_eq!(stored_title(), "Tpyo");
+        
+        app.editing.as_mut().unwrap().title = " Typo ".to_string();
+        app.save_edit();
+        assert!(app.editing.is_none());
+        assert_eq!(stored_title(), "Typo");
+        
+        // Escape leaves the edit but keeps the add form
+        app.new_task_title = "Draft".to_string();
+        app.start_editing(id);
+        app.apply_shortcuts(Shortcuts { cancel: true, ..Shortcuts::default() });
+        assert!(app.editing.is_none());
+        assert_eq!(app.new_task_title, "Draft");
+        
+        // Only one row at a time
+        let other = serde_json::from_str::<Task>(&store::add_task_full(r#"{"title":"Other"}"#).unwrap()).unwrap().id;
+        app.start_editing(id);
+        app.start_editing(other);
+        assert_eq!(app.editing.as_ref().unwrap().id, other);
+        assert!(store::remove_task(other).unwrap());
+        app.save_edit();
+        assert!(app.editing.as_ref().unwrap().error.as_deref().unwrap().contains("not found"));
+        store::remove_task(id).unwrap();
+    }
+    
+    #[cfg(not(feature = "web"))]
+    #[test]
+    fn test_undo_delete_and_edit() {
+        let _store = reset_globals();
+        let mut app = TaskManagerApp::new();
+        let undo = Shortcuts { undo: true, ..Shortcuts::default() };
+        let added = store::add_task_full(r#"{"title":"Call Bob","tags":["home"],"priority":"high"}"#).unwrap();
+        let original = serde_json::from_str::<Task>(&added).unwrap();
+        let stored = || store::with_tasks(|manager| manager.get_task(original.id).cloned());
+        
+        app.remove_from_list(original.id);
+        assert!(stored().is_none());
+        app.apply_shortcuts(undo);
+        let restored = stored().unwrap();
+        assert_eq!((restored.title.as_str(), restored.priority), ("Call Bob", Priority::High));
+        assert_eq!((&restored.tags, restored.created_at), (&original.tags, original.created_at));
+        
+        app.start_editing(original.id);
+        app.editing.as_mut().unwrap().title = "Call Alice".to_string();
+        app.save_edit();
+        app.apply_shortcuts(undo);
+        assert_eq!(stored().unwrap().title, "Call Bob");
+        
+        // Each change is undone once
+        app.notifications.clear();
+        app.apply_shortcuts(undo);
+        assert_eq!(app.notifications[0].0, "Nothing to undo");
+        
+        // A task put back under the same ID in the meantime is kept
+        app.remove_from_list(original.id);
+        store::restore_task(&added).unwrap();
+        app.apply_shortcuts(undo);
+        assert!(app.notifications.back().unwrap().0.contains("already exists"));
+        assert!(stored().is_some());
+    }
+    
+    #[cfg(not(feature = "web"))]
+    #[test]
+    fn test_task_detail_actions() {
+        let _store = reset_globals();
+        let mut app = TaskManagerApp::new();
+        let added = store::add_task_full(r#"{"title":"Plan trip","tags":["travel"],"priority":"high"}"#).unwrap();
+        let id = serde_json::from_str::<Task>(&added).unwrap().id;
+        
+        app.open_view(AppView::KpiDashboard);
+        app.open_view(AppView::TaskDetail(id));
+        assert_eq!(app.view_stack, vec![AppView::TaskManager, AppView::KpiDashboard]);
+        
+        app.toggle_from_list(id, false);
+        assert!(store::with_tasks(|manager| manager.get_task(id).unwrap().completed));
+        
+        // The copy is open and takes the original's place on screen
+        let original = store::with_tasks(|manager| manager.get_task(id).cloned()).unwrap();
+        app.duplicate_task(&original);
+        let AppView::TaskDetail(copy_id) = app.current_view else { panic!("{:?}", app.current_view) };
+        let copy = store::with_tasks(|manager| manager.get_task(copy_id).cloned()).unwrap();
+        assert_ne!(copy_id, id);
+        assert_eq!((copy.title.as_str(), copy.priority, &copy.tags), ("Plan trip", Priority::High, &original.tags));
+        assert!(!copy.completed);
+        
+        app.delete_from_detail(copy_id);
+        assert!(store::with_tasks(|manager| manager.get_task(copy_id).is_none()));
+        assert_eq!(app.current_view, AppView::KpiDashboard);
+        app.go_back();
+        app.go_back();
+        assert_eq!(app.current_view, AppView::TaskManager);
+        store::remove_task(id).unwrap();
+    }
+    
+    #[cfg(not(feature = "web"))]
+    #[test]
+    fn test_list_filter_and_search() {
+        let _store = reset_globals();
+        let mut app = TaskManagerApp::new();
+        let add = |json: &str| serde_json::from_str::<Task>(&store::add_task_full(json).unwrap()).unwrap().id;
+        let milk = add(r#"{"title":"Buy MILK"}"#);
+        let bread = add(r#"{"title":"Bakery","description":"fresh bread and milk"}"#);
+        let taxes = add(r#"{"title":"File taxes"}"#);
+        store::toggle(bread);
+        let ours = [milk, bread, taxes];
+        let visible = |app: &mut TaskManagerApp| -> Vec<u32> {
+            let tasks = app.cached_tasks();
+            app.visible_tasks(&tasks).iter().map(|task| task.id).filter(|id| ours.contains(id)).collect()
+        };
+        
+        assert_eq!(visible(&mut app), vec![milk, bread, taxes]);
+        app.list_filter = TaskFilter::Active;
+        assert_eq!(visible(&mut app), vec![milk, taxes]);
+        app.list_filter = TaskFilter::Completed;
+        assert_eq!(visible(&mut app), vec![bread]);
+        
+        // Titles and descriptions, ignoring case
+        app.list_filter = TaskFilter::All;
+        app.search_query = " Milk ".to_string();
+        assert_eq!(visible(&mut app), vec![milk, bread]);
+        app.list_filter = TaskFilter::Active;
+        assert_eq!(visible(&mut app), vec![milk]);
+        
+        // The same query is searched once, until the tasks change
+        let start = store::fetch_count();
+        visible(&mut app);
+        visible(&mut app);
+        assert_eq!(store::fetch_count(), start);
+        app.search_query = "taxes".to_string();
+        assert_eq!(visible(&mut app), vec![taxes]);
+        assert_eq!(store::fetch_count(), start + 1);
+        
+        // The statistics still count every task
+        let total = app.cached_stats().report.total;
+        assert_eq!(total, store::with_tasks(|manager| manager.get_total_count()));
+        for id in ours {
+            store::remove_task(id).unwrap();
+        }
+    }
+    
+    #[cfg(not(feature = "web"))]
+    #[test]
+    fn test_tag_filter_and_detail_tags() {
+        let _store = reset_globals();
+        let mut app = TaskManagerApp::new();
+        let add = |json: &str| serde_json::from_str::<Task>(&store::add_task_full(json).unwrap()).unwrap().id;
+        let report = add(r#"{"title":"Write report","tags":["Work"]}"#);
+        let groceries = add(r#"{"title":"Groceries","tags":["home"]}"#);
+        let ours = [report, groceries];
+        let visible = |app: &mut TaskManagerApp| -> Vec<u32> {
+            let tasks = app.cached_tasks();
+            app.visible_tasks(&tasks).iter().map(|task| task.id).filter(|id| ours.contains(id)).collect()
+        };
+        
+        // Tags match ignoring case, and the list follows tags added later
+        app.tag_filter = Some("work".to_string());
+        assert_eq!(visible(&mut app), vec![report]);
+        app.detail_tag = " work, errands ".to_string();
+        app.add_detail_tags(groceries);
+        assert!(app.detail_tag.is_empty());
+        assert_eq!(visible(&mut app), vec![report, groceries]);
+        let tags = store::with_tasks(|manager| manager.get_task(groceries).unwrap().tags.clone());
+        assert_eq!(tags, ["home", "work", "errands"]);
+        
+        app.remove_detail_tag(report, "WORK");
+        assert_eq!(visible(&mut app), vec![groceries]);
+        app.tag_filter = None;
+        assert_eq!(visible(&mut app), vec![report, groceries]);
+        
+        app.detail_tag = "travel".to_string();
+        app.add_detail_tags(u32::MAX);
+        assert_eq!(app.detail_tag, "travel");
+        assert_eq!(app.notifications.back().unwrap().1, NotificationKind::Error);
+        for id in ours {
+            store::remove_task(id).unwrap();
+        }
+    }
+    
+    /// `eframe::Storage` backed by a map, like the browser's localStorage
+    #[derive(Default)]
+    struct MapStorage(std::collections::HashMap<String, String>);
+    
+    impl eframe::Storage for MapStorage {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+        
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_string(), value);
+        }
+        
+        fn flush(&mut self) {}
+    }
+    
+    #[test]
+    fn test_task_order_persists() {
+        let mut storage = MapStorage::default();
+        let mut app = TaskManagerApp::new();
+        app.load_settings(&storage);
+        assert_eq!(app.task_order, TaskOrder::Id);
+        
+        app.task_order = TaskOrder::IncompleteFirst;
+        app.save(&mut storage);
+        let mut restored = TaskManagerApp::new();
+        restored.load_settings(&storage);
+        assert_eq!(restored.task_order, TaskOrder::IncompleteFirst);
+        
+        // A value this build does not know keeps the default
+        storage.0.insert(TASK_ORDER_KEY.to_string(), "shuffled".to_string());
+        let mut restored = TaskManagerApp::new();
+        restored.load_settings(&storage);
+        assert_eq!(restored.task_order, TaskOrder::Id);
+    }
+    
+    #[cfg(not(feature = "web"))]
+    #[test]
+    fn test_list_follows_task_order() {
+        let _store = reset_globals();
+        let mut app = TaskManagerApp::new();
+        let add = |json: &str| serde_json::from_str::<Task>(&store::add_task_full(json).unwrap()).unwrap().id;
+        let ours = [add(r#"{"title":"b"}"#), add(r#"{"title":"A"}"#), add(r#"{"title":"c"}"#)];
+        let order = |app: &mut TaskManagerApp| -> Vec<u32> {
+            app.cached_tasks().iter().map(|task| task.id).filter(|id| ours.contains(id)).collect()
+        };
+        
+        assert_eq!(order(&mut app), ours);
+        app.task_order = TaskOrder::Title;
+        app.task_cache = None;
+        assert_eq!(order(&mut app), vec![ours[1], ours[0], ours[2]]);
+        // The same order on every frame
+        assert_eq!(order(&mut app), vec![ours[1], ours[0], ours[2]]);
+        for id in ours {
+            store::remove_task(id).unwrap();
+        }
+    }
+    
+    #[test]
+    fn test_series_points() {
+        let day = |d: u32| chrono::NaiveDate::from_ymd_opt(2024, 3, d).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let series = vec![(day(8), 2), (day(9), 0), (day(10), 5)];
+        assert_eq!(series_points(&series), vec![[0.0, 2.0], [1.0, 0.0], [2.0, 5.0]]);
+    }
+    
+    #[test]
+    fn test_new_task_spec() {
+        let mut app = TaskManagerApp::new();
+        assert_eq!(app.new_task_spec().unwrap_err(), "Task title cannot be empty");
+        
+        app.new_task_title = "File taxes".to_string();
+        app.new_task_priority = Priority::High;
+        app.new_task_due = " 2024-04-15 ".to_string();
+        let spec: serde_json::Value = serde_json::from_str(&app.new_task_spec().unwrap()).unwrap();
+        assert_eq!(spec["priority"], "high");
+        assert_eq!(spec["due_date"], "2024-04-15T00:00:00+00:00");
+        
+        app.new_task_due = "15/04/2024".to_string();
+        assert!(app.new_task_spec().unwrap_err().contains("expected YYYY-MM-DD"));
+        app.apply_shortcuts(Shortcuts { cancel: true, ..Shortcuts::default() });
+        assert_eq!(app.new_task_priority, Priority::Medium);
+        assert!(app.new_task_due.is_empty());
+    }
+    
+    #[test]
+    fn test_new_task_tags() {
+        let mut app = TaskManagerApp::new();
+        app.new_task_tag = "work, Home,".to_string();
+        app.add_form_tags();
+        assert_eq!(app.new_task_tags, ["work", "Home"]);
+        assert!(app.new_task_tag.is_empty());
+        
+        // What is still typed is added with the chips, without repeats
+        app.new_task_title = "Plan week".to_string();
+        app.new_task_tag = " WORK , errands".to_string();
+        let spec: serde_json::Value = serde_json::from_str(&app.new_task_spec().unwrap()).unwrap();
+        assert_eq!(spec["tags"], serde_json::json!(["work", "Home", "errands"]));
+        
+        app.clear_form();
+        assert!(app.new_task_tags.is_empty() && app.new_task_tag.is_empty());
+    }
+    
+    #[test]
+    fn test_tag_color() {
+        assert_eq!(tag_color("Work"), tag_color("work"));
+        assert_ne!(tag_color("work"), tag_color("home"));
+    }
+    
+    #[test]
+    fn test_new_task_due_check() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let mut app = TaskManagerApp::new();
+        assert_eq!(app.check_new_task_due(today), Ok(None));
+        app.new_task_due = "2024-03-10".to_string();
+        assert_eq!(app.check_new_task_due(today), Ok(None));
+        // A past date warns but can still be added
+        app.new_task_due = "2024-03-09".to_string();
+        assert!(app.check_new_task_due(today).unwrap().unwrap().contains("has passed"));
+        app.new_task_title = "Late already".to_string();
+        assert!(app.new_task_spec().is_ok());
+        app.new_task_due = "2024-3-40".to_string();
+        assert!(app.check_new_task_due(today).unwrap_err().contains("expected YYYY-MM-DD"));
+        assert_eq!(app.new_task_spec().unwrap_err(), app.check_new_task_due(today).unwrap_err());
+        assert_eq!(parse_due_input(" 2024-03-10 "), Ok(Some(today)));
+        assert_eq!(parse_due_input("  "), Ok(None));
+    }
+    
+    #[test]
+    fn test_due_label() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let due = |date: &str| DateTime::parse_from_rfc3339(date).unwrap().with_timezone(&Utc);
+        assert_eq!(due_label(due("2024-03-10T00:00:00Z"), today), "due today");
+        assert_eq!(due_label(due("2024-03-10T23:59:00Z"), today), "due today");
+        assert_eq!(due_label(due("2024-03-11T00:00:00Z"), today), "due tomorrow");
+        assert_eq!(due_label(due("2024-03-17T00:00:00Z"), today), "due in 7 days");
+        assert_eq!(due_label(due("2024-03-09T00:00:00Z"), today), "overdue by 1 day");
+        assert_eq!(due_label(due("2024-02-29T00:00:00Z"), today), "overdue by 10 days");
+    }
+    
+    #[test]
+    fn test_notifications_queue_in_order() {
+        let mut app = TaskManagerApp::new();
+        app.notify("Task added", NotificationKind::Success);
+        app.notify("Task title cannot be empty", NotificationKind::Error);
+        
+        let queued: Vec<(&str, NotificationKind)> = app.notifications.iter()
+            .map(|(message, kind, _)| (message.as_str(), *kind))
+            .collect();
+        assert_eq!(queued, vec![
+            ("Task added", NotificationKind::Success),
+            ("Task title cannot be empty", NotificationKind::Error),
+        ]);
+    }
+    
+    #[test]
+    fn test_delete_needs_a_second_click() {
+        let mut app = TaskManagerApp::new();
+        assert!(!app.confirm_delete(1));
+        assert!(app.is_confirming_delete(1));
+        assert!(!app.is_confirming_delete(2));
+        assert!(app.confirm_delete(1));
+        assert!(app.pending_delete.is_none());
+        
+        // Another row's button starts over
+        assert!(!app.confirm_delete(1));
+        assert!(!app.confirm_delete(2));
+        assert!(!app.is_confirming_delete(1));
+        assert!(app.confirm_delete(2));
+        
+        // Too late: the click asks again
+        let expired = Instant::now() - DELETE_CONFIRM_WINDOW - Duration::from_millis(1);
+        app.pending_delete = Some((1, expired));
+        assert!(!app.is_confirming_delete(1));
+        assert!(!app.confirm_delete(1));
+        assert!(app.confirm_delete(1));
+    }
+    
+    #[test]
+    fn test_notifications_expire() {
+        let mut app = TaskManagerApp::new();
+        let expired = Instant::now() - NOTIFICATION_LIFETIME - Duration::from_millis(1);
+        app.notifications.push_back(("Old".to_string(), NotificationKind::Success, expired));
+        app.notify("New", NotificationKind::Success);
+        
+        app.expire_notifications();
+        assert_eq!(app.notifications.len(), 1);
+        assert_eq!(app.notifications[0].0, "New");
+    }
+}
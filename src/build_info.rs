@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+
+/// Facts about the build, embedded by `build.rs` at compile time.
+///
+/// Builds without git report the commit as `unknown`, so a deployed WASM
+/// bundle and a local binary can always be told apart by their commit and
+/// build time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub commit: &'static str,
+    /// When `build.rs` last ran, which is only when git HEAD or a branch
+    /// moved or `SOURCE_DATE_EPOCH` changed, not on every compile
+    pub built_at: Option<DateTime<Utc>>,
+    pub target: &'static str,
+    pub features: Vec<&'static str>,
+}
+
+impl BuildInfo {
+    /// Build info for the running binary.
+    pub fn current() -> Self {
+        let built_at = env!("GH_ACTIONS_BUILD_TIMESTAMP")
+            .parse()
+            .ok()
+            .and_then(|secs| DateTime::from_timestamp(secs, 0));
+        BuildInfo {
+            version: env!("CARGO_PKG_VERSION"),
+            commit: env!("GH_ACTIONS_GIT_HASH"),
+            built_at,
+            target: env!("GH_ACTIONS_TARGET"),
+            features: env!("GH_ACTIONS_FEATURES").split(',').filter(|f| !f.is_empty()).collect(),
+        }
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let built_at = self
+            .built_at
+            .map(|d| d.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let features = if self.features.is_empty() { "none".to_string() } else { self.features.join(", ") };
+        writeln!(f, "version:   {}", self.version)?;
+        writeln!(f, "commit:    {}", self.commit)?;
+        writeln!(f, "built:     {}", built_at)?;
+        writeln!(f, "target:    {}", self.target)?;
+        write!(f, "features:  {}", features)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_build_info() {
+        let info = BuildInfo::current();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(!info.commit.is_empty());
+        assert!(!info.target.is_empty());
+        assert!(info.built_at.is_some());
+        assert_eq!(info.features.contains(&"json"), cfg!(feature = "json"));
+        assert!(!info.features.contains(&"default"));
+    }
+
+    #[test]
+    fn test_display() {
+        let info = BuildInfo {
+            version: "0.1.0",
+            commit: "abc1234",
+            built_at: DateTime::from_timestamp(1_710_072_000, 0),
+            target: "wasm32-unknown-unknown",
+            features: vec![],
+        };
+        assert_eq!(
+            info.to_string(),
+            "version:   0.1.0\n\
+             commit:    abc1234\n\
+             built:     2024-03-10 12:00:00 UTC\n\
+             target:    wasm32-unknown-unknown\n\
+             features:  none"
+        );
+    }
+}
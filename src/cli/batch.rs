@@ -0,0 +1,62 @@
+use std::io::BufRead;
+
+/// The lines of a batch input worth processing, with their 1-based line
+/// numbers. Blank lines and lines starting with `#` are skipped.
+pub fn lines(input: impl BufRead) -> impl Iterator<Item = (usize, Result<String, String>)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.map_err(|e| e.to_string())))
+        .filter(|(_, line)| match line {
+            Ok(line) => {
+                let line = line.trim();
+                !line.is_empty() && !line.starts_with('#')
+            }
+            Err(_) => true,
+        })
+}
+
+/// Splits a `name,email` line.
+pub fn parse_user_line(line: &str) -> Result<(String, String), String> {
+    match line.split_once(',') {
+        Some((name, email)) => Ok((name.trim().to_string(), email.trim().to_string())),
+        None => Err("expected `name,email`".to_string()),
+    }
+}
+
+/// Splits a `title|description` line; the description is optional.
+pub fn parse_task_line(line: &str) -> (String, String) {
+    match line.split_once('|') {
+        Some((title, description)) => (title.trim().to_string(), description.trim().to_string()),
+        None => (line.trim().to_string(), String::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_skip_blanks_and_comments() {
+        let input = "# users\nAnn,ann@example.com\n\n   \nBob,bob@example.com\n";
+        let numbered: Vec<(usize, String)> = lines(input.as_bytes())
+            .map(|(number, line)| (number, line.unwrap()))
+            .collect();
+        assert_eq!(
+            numbered,
+            vec![(2, "Ann,ann@example.com".to_string()), (5, "Bob,bob@example.com".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(
+            parse_user_line(" Ann , ann@example.com").unwrap(),
+            ("Ann".to_string(), "ann@example.com".to_string())
+        );
+        assert!(parse_user_line("Ann").is_err());
+
+        assert_eq!(parse_task_line("Ship|Tag v1"), ("Ship".to_string(), "Tag v1".to_string()));
+        assert_eq!(parse_task_line("Ship"), ("Ship".to_string(), String::new()));
+    }
+}
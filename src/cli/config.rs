@@ -0,0 +1,314 @@
+use chrono::FixedOffset;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Output format for commands that print records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!("unknown output format '{}', expected 'text' or 'json'", other)),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Where an effective setting came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File,
+    Flag,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File => write!(f, "file"),
+            Source::Flag => write!(f, "flag"),
+        }
+    }
+}
+
+/// A resolved setting together with its provenance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    /// Picks the flag value over the file value over the default.
+    fn resolve(default: T, file: Option<T>, flag: Option<T>) -> Self {
+        match (flag, file) {
+            (Some(value), _) => Setting { value, source: Source::Flag },
+            (None, Some(value)) => Setting { value, source: Source::File },
+            (None, None) => Setting { value: default, source: Source::Default },
+        }
+    }
+}
+
+/// Optional CLI settings, as read from a config file or collected from flags.
+///
+/// Every field is optional so that a file (or command line) only needs to
+/// mention the settings it wants to change.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    pub data_dir: Option<PathBuf>,
+    pub output_format: Option<OutputFormat>,
+    pub timezone_offset: Option<String>,
+}
+
+impl Config {
+    /// Parses a TOML config file.
+    ///
+    /// Keys are checked one by one so that errors name the offending key
+    /// instead of surfacing a raw deserializer message.
+    pub fn from_toml_str(content: &str) -> Result<Self, String> {
+        let table: toml::Table = content
+            .parse()
+            .map_err(|e: toml::de::Error| format!("invalid TOML: {}", e.message()))?;
+
+        let mut config = Config::default();
+        for (key, value) in table {
+            match key.as_str() {
+                "data_dir" => config.data_dir = Some(parse_key(&key, value)?),
+                "output_format" => config.output_format = Some(parse_key(&key, value)?),
+                "timezone_offset" => {
+                    let offset: String = parse_key(&key, value)?;
+                    parse_utc_offset(&offset)
+                        .map_err(|e| format!("invalid value for `{}`: {}", key, e))?;
+                    config.timezone_offset = Some(offset);
+                }
+                _ => return Err(format!("unknown key `{}`", key)),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Loads the config file at `path`, returning `None` if it does not exist.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read config file {}: {}", path.display(), e))?;
+        Config::from_toml_str(&content)
+            .map(Some)
+            .map_err(|e| format!("config file {}: {}", path.display(), e))
+    }
+}
+
+fn parse_key<T: DeserializeOwned>(key: &str, value: toml::Value) -> Result<T, String> {
+    T::deserialize(value).map_err(|e| format!("invalid value for `{}`: {}", key, e.message()))
+}
+
+/// Parses a UTC offset such as `+02:00` or `-05:30`.
+pub fn parse_utc_offset(s: &str) -> Result<FixedOffset, String> {
+    s.parse::<FixedOffset>()
+        .map_err(|_| format!("'{}' is not a UTC offset like +02:00", s))
+}
+
+/// The settings in effect for one CLI invocation.
+#[derive(Debug, Clone)]
+pub struct EffectiveConfig {
+    /// Config file that was consulted, whether or not it exists.
+    pub path: PathBuf,
+    pub file_loaded: bool,
+    pub data_dir: Setting<PathBuf>,
+    pub output_format: Setting<OutputFormat>,
+    pub timezone_offset: Setting<FixedOffset>,
+    /// Tasks file given with `--file`, used instead of the data directory.
+    pub tasks_path: Option<PathBuf>,
+    /// Users file given with `--users-file`, used instead of the data directory.
+    pub users_path: Option<PathBuf>,
+}
+
+impl EffectiveConfig {
+    /// Layers flags over the config file over the built-in defaults.
+    pub fn resolve(path: PathBuf, file: Option<Config>, flags: Config) -> Result<Self, String> {
+        let file_loaded = file.is_some();
+        let file = file.unwrap_or_default();
+        let file_offset = file.timezone_offset.as_deref().map(parse_utc_offset).transpose()?;
+        let flag_offset = flags.timezone_offset.as_deref().map(parse_utc_offset).transpose()?;
+
+        Ok(EffectiveConfig {
+            path,
+            file_loaded,
+            data_dir: Setting::resolve(default_data_dir(), file.data_dir, flags.data_dir),
+            output_format: Setting::resolve(
+                OutputFormat::default(),
+                file.output_format,
+                flags.output_format,
+            ),
+            timezone_offset: Setting::resolve(utc(), file_offset, flag_offset),
+            tasks_path: None,
+            users_path: None,
+        })
+    }
+
+    pub fn users_file(&self) -> PathBuf {
+        self.users_path
+            .clone()
+            .unwrap_or_else(|| self.data_dir.value.join("users.json"))
+    }
+
+    pub fn tasks_file(&self) -> PathBuf {
+        self.tasks_path
+            .clone()
+            .unwrap_or_else(|| self.data_dir.value.join("tasks.json"))
+    }
+}
+
+fn utc() -> FixedOffset {
+    FixedOffset::east_opt(0).expect("zero is a valid offset")
+}
+
+/// `$XDG_CONFIG_HOME/gh_actions/config.toml`, defaulting to `~/.config`.
+pub fn default_config_path() -> PathBuf {
+    env_dir("XDG_CONFIG_HOME")
+        .unwrap_or_else(|| home_dir().join(".config"))
+        .join("gh_actions")
+        .join("config.toml")
+}
+
+/// `$XDG_DATA_HOME/gh_actions`, defaulting to `~/.local/share`.
+pub fn default_data_dir() -> PathBuf {
+    env_dir("XDG_DATA_HOME")
+        .unwrap_or_else(|| home_dir().join(".local").join("share"))
+        .join("gh_actions")
+}
+
+fn home_dir() -> PathBuf {
+    env_dir("HOME")
+        .or_else(|| env_dir("USERPROFILE"))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn env_dir(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// A commented config file listing every key with its default value.
+pub fn template() -> String {
+    format!(
+        r#"# gh_actions configuration
+#
+# Settings here become the defaults for every command. Flags given on the
+# command line still take precedence. Uncomment a line to change it.
+
+# Directory holding the data files (users.json, tasks.json).
+# data_dir = "{}"
+
+# Output format for list commands: "text" or "json".
+# output_format = "text"
+
+# UTC offset used when grouping task statistics by day.
+# timezone_offset = "+00:00"
+"#,
+        default_data_dir().display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_config() {
+        let config = Config::from_toml_str(
+            "data_dir = \"/tmp/data\"\noutput_format = \"json\"\ntimezone_offset = \"+02:00\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.data_dir, Some(PathBuf::from("/tmp/data")));
+        assert_eq!(config.output_format, Some(OutputFormat::Json));
+        assert_eq!(config.timezone_offset.as_deref(), Some("+02:00"));
+    }
+
+    #[test]
+    fn test_parse_empty_config() {
+        assert_eq!(Config::from_toml_str("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_template_parses_to_defaults() {
+        assert_eq!(Config::from_toml_str(&template()).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_errors_name_the_offending_key() {
+        let err = Config::from_toml_str("output_format = \"xml\"").unwrap_err();
+        assert!(err.contains("`output_format`"), "{}", err);
+
+        let err = Config::from_toml_str("timezone_offset = 2").unwrap_err();
+        assert!(err.contains("`timezone_offset`"), "{}", err);
+
+        let err = Config::from_toml_str("timezone_offset = \"soon\"").unwrap_err();
+        assert!(err.contains("`timezone_offset`"), "{}", err);
+
+        let err = Config::from_toml_str("colour = true").unwrap_err();
+        assert!(err.contains("unknown key `colour`"), "{}", err);
+    }
+
+    #[test]
+    fn test_flags_win_over_file() {
+        let file = Config {
+            data_dir: Some(PathBuf::from("/from/file")),
+            output_format: Some(OutputFormat::Json),
+            timezone_offset: None,
+        };
+        let flags = Config {
+            output_format: Some(OutputFormat::Text),
+            ..Config::default()
+        };
+
+        let effective = EffectiveConfig::resolve(PathBuf::from("config.toml"), Some(file), flags).unwrap();
+
+        assert_eq!(effective.data_dir.value, PathBuf::from("/from/file"));
+        assert_eq!(effective.data_dir.source, Source::File);
+        assert_eq!(effective.output_format.value, OutputFormat::Text);
+        assert_eq!(effective.output_format.source, Source::Flag);
+        assert_eq!(effective.timezone_offset.value, utc());
+        assert_eq!(effective.timezone_offset.source, Source::Default);
+        assert!(effective.file_loaded);
+    }
+
+    #[test]
+    fn test_explicit_files_override_data_dir() {
+        let file = Config {
+            data_dir: Some(PathBuf::from("/from/file")),
+            ..Config::default()
+        };
+        let mut effective =
+            EffectiveConfig::resolve(PathBuf::from("config.toml"), Some(file), Config::default()).unwrap();
+        assert_eq!(effective.tasks_file(), PathBuf::from("/from/file/tasks.json"));
+
+        effective.tasks_path = Some(PathBuf::from("project-a/tasks.json"));
+        assert_eq!(effective.tasks_file(), PathBuf::from("project-a/tasks.json"));
+        assert_eq!(effective.users_file(), PathBuf::from("/from/file/users.json"));
+    }
+}
@@ -0,0 +1,133 @@
+use super::error::CliError;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Asks the user a yes/no question.
+pub trait Prompt {
+    fn ask(&mut self, question: &str) -> io::Result<bool>;
+}
+
+/// Asks on stderr and reads the answer from stdin.
+pub struct StdinPrompt;
+
+impl Prompt for StdinPrompt {
+    fn ask(&mut self, question: &str) -> io::Result<bool> {
+        let mut err = io::stderr().lock();
+        write!(err, "{} [y/N] ", question)?;
+        err.flush()?;
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+        Ok(is_yes(&answer))
+    }
+}
+
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Gatekeeper for destructive commands.
+///
+/// `--yes` skips the question. Otherwise the user is asked, but only when
+/// there is someone to ask: with no prompt (stdin is not a terminal) the
+/// command is refused, so scripts never delete data by accident.
+pub struct Confirmation {
+    yes: bool,
+    prompt: Option<Box<dyn Prompt>>,
+}
+
+impl Confirmation {
+    pub fn new(yes: bool, prompt: Option<Box<dyn Prompt>>) -> Self {
+        Confirmation { yes, prompt }
+    }
+
+    /// Prompts on the terminal when stdin is one.
+    pub fn from_terminal(yes: bool) -> Self {
+        let prompt: Option<Box<dyn Prompt>> = if io::stdin().is_terminal() {
+            Some(Box::new(StdinPrompt))
+        } else {
+            None
+        };
+        Confirmation::new(yes, prompt)
+    }
+
+    /// Succeeds if the action may go ahead.
+    pub fn confirm(&mut self, question: &str) -> Result<(), CliError> {
+        if self.yes {
+            return Ok(());
+        }
+        match &mut self.prompt {
+            Some(prompt) => match prompt.ask(question) {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(CliError::Aborted("Aborted, nothing was deleted".to_string())),
+                Err(e) => Err(CliError::Io(format!("Failed to read answer: {}", e))),
+            },
+            None => Err(CliError::Aborted(
+                "Refusing to delete without confirmation; pass --yes to run non-interactively".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Gives canned answers and records the questions asked.
+    struct Scripted {
+        answer: bool,
+        asked: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Prompt for Scripted {
+        fn ask(&mut self, question: &str) -> io::Result<bool> {
+            self.asked.borrow_mut().push(question.to_string());
+            Ok(self.answer)
+        }
+    }
+
+    fn scripted(answer: bool) -> (Confirmation, Rc<RefCell<Vec<String>>>) {
+        let asked = Rc::new(RefCell::new(Vec::new()));
+        let prompt = Scripted { answer, asked: Rc::clone(&asked) };
+        (Confirmation::new(false, Some(Box::new(prompt))), asked)
+    }
+
+    #[test]
+    fn test_confirmed_answer_proceeds() {
+        let (mut confirmation, asked) = scripted(true);
+        assert_eq!(confirmation.confirm("Delete task 12 'Fix login bug'?"), Ok(()));
+        assert_eq!(*asked.borrow(), vec!["Delete task 12 'Fix login bug'?"]);
+    }
+
+    #[test]
+    fn test_declined_answer_aborts() {
+        let (mut confirmation, _) = scripted(false);
+        let err = confirmation.confirm("Delete task 12 'Fix login bug'?").unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(matches!(err, CliError::Aborted(_)));
+    }
+
+    #[test]
+    fn test_yes_skips_the_prompt() {
+        let asked = Rc::new(RefCell::new(Vec::new()));
+        let prompt = Scripted { answer: false, asked: Rc::clone(&asked) };
+        let mut confirmation = Confirmation::new(true, Some(Box::new(prompt)));
+        assert_eq!(confirmation.confirm("Delete?"), Ok(()));
+        assert!(asked.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_without_prompt_requires_yes() {
+        let err = Confirmation::new(false, None).confirm("Delete?").unwrap_err();
+        assert!(err.to_string().contains("--yes"), "{}", err);
+    }
+
+    #[test]
+    fn test_is_yes() {
+        assert!(is_yes("y\n"));
+        assert!(is_yes(" YES "));
+        assert!(!is_yes("\n"));
+        assert!(!is_yes("no"));
+        assert!(!is_yes("yep"));
+    }
+}
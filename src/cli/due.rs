@@ -0,0 +1,132 @@
+use super::listing::{format_date, Column};
+use chrono::{DateTime, Duration, Utc};
+use gh_actions::utils::humanize_duration;
+use gh_actions::Task;
+use serde::Serialize;
+
+/// A task with a due date, as shown by `task due`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DueTask {
+    pub id: u32,
+    pub title: String,
+    pub due_date: DateTime<Utc>,
+    pub when: String,
+    pub overdue: bool,
+}
+
+impl DueTask {
+    /// Returns `None` for tasks without a due date.
+    pub fn new(task: &Task, now: DateTime<Utc>) -> Option<Self> {
+        let due_date = task.due_date?;
+        Some(DueTask {
+            id: task.id,
+            title: task.title.clone(),
+            due_date,
+            when: relative_label(due_date, now),
+            overdue: due_date < now,
+        })
+    }
+}
+
+pub const DUE_COLUMNS: [Column<DueTask>; 4] = [
+    Column {
+        name: "id",
+        header: "ID",
+        value: |t| t.id.to_string(),
+        compare: |a, b| a.id.cmp(&b.id),
+    },
+    Column {
+        name: "title",
+        header: "Title",
+        value: |t| t.title.clone(),
+        compare: |a, b| a.title.cmp(&b.title),
+    },
+    Column {
+        name: "due",
+        header: "Due",
+        value: |t| format_date(Some(t.due_date)),
+        compare: |a, b| a.due_date.cmp(&b.due_date),
+    },
+    Column {
+        name: "when",
+        header: "When",
+        value: |t| t.when.clone(),
+        compare: |a, b| a.due_date.cmp(&b.due_date),
+    },
+];
+
+/// Parses a `--within` window such as `30m`, `48h`, `3d` or `1w`.
+pub fn parse_window(s: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration '{}', expected a number followed by m, h, d or w (e.g. 48h)", s);
+    let unit_start = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let (amount, unit) = s.split_at(unit_start);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    match unit {
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+/// Parses the hidden `--now` override, which pins the clock in tests.
+pub fn parse_timestamp(s: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|e| format!("invalid RFC 3339 timestamp '{}': {}", s, e))
+}
+
+/// Describes `due` relative to `now`, such as `due in 3h` or `overdue by 2 days`.
+pub fn relative_label(due: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let delta = due - now;
+    if delta > Duration::zero() {
+        format!("due in {}", humanize_duration(delta.to_std().unwrap_or_default()))
+    } else if delta < Duration::zero() {
+        format!("overdue by {}", humanize_duration((-delta).to_std().unwrap_or_default()))
+    } else {
+        "due now".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(parse_window("48h"), Ok(Duration::hours(48)));
+        assert_eq!(parse_window("30m"), Ok(Duration::minutes(30)));
+        assert_eq!(parse_window("3d"), Ok(Duration::days(3)));
+        assert_eq!(parse_window("1w"), Ok(Duration::weeks(1)));
+        for bad in ["", "h", "48", "48x", "-2h", "1.5h"] {
+            assert!(parse_window(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_relative_label() {
+        let now = at("2024-03-10T12:00:00Z");
+        assert_eq!(relative_label(at("2024-03-10T15:30:00Z"), now), "due in 3h");
+        assert_eq!(relative_label(at("2024-03-08T09:00:00Z"), now), "overdue by 2 days");
+        assert_eq!(relative_label(at("2024-03-10T11:59:30Z"), now), "overdue by 30s");
+        assert_eq!(relative_label(now, now), "due now");
+    }
+
+    #[test]
+    fn test_due_task() {
+        let now = at("2024-03-10T12:00:00Z");
+        let mut task = Task::new(4, "Pay rent".to_string(), String::new());
+        assert_eq!(DueTask::new(&task, now), None);
+
+        task.due_date = Some(at("2024-03-09T12:00:00Z"));
+        let due = DueTask::new(&task, now).unwrap();
+        assert!(due.overdue);
+        assert_eq!(due.when, "overdue by 24h");
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+/// An error that ends a CLI invocation, classified by its exit code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    /// A malformed argument or setting, such as a non-numeric ID.
+    InvalidInput(String),
+    /// The requested user, task or file does not exist.
+    NotFound(String),
+    /// The operation clashes with existing data, such as a duplicate ID.
+    Conflict(String),
+    /// Reading or writing a file failed.
+    Io(String),
+    /// Well-formed input that breaks a rule, such as an invalid email.
+    Validation(String),
+    /// A destructive command was declined or not confirmed with `--yes`.
+    Aborted(String),
+    /// A check such as `string palindrome` or `email validate` answered
+    /// "no". The answer has already been printed, so there is no message.
+    CheckFailed,
+}
+
+impl CliError {
+    /// The process exit code for this error.
+    ///
+    /// Usage errors share code 2 with the argument errors reported by clap,
+    /// and a failed check exits with 1 like `test` and `grep` do.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Io(_) | CliError::Aborted(_) | CliError::CheckFailed => 1,
+            CliError::InvalidInput(_) => 2,
+            CliError::NotFound(_) => 3,
+            CliError::Conflict(_) => 4,
+            CliError::Validation(_) => 5,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::InvalidInput(message)
+            | CliError::NotFound(message)
+            | CliError::Conflict(message)
+            | CliError::Io(message)
+            | CliError::Validation(message)
+            | CliError::Aborted(message) => write!(f, "{}", message),
+            CliError::CheckFailed => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            CliError::Io(String::new()),
+            CliError::InvalidInput(String::new()),
+            CliError::NotFound(String::new()),
+            CliError::Conflict(String::new()),
+            CliError::Validation(String::new()),
+        ];
+        let mut codes: Vec<i32> = errors.iter().map(CliError::exit_code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_failed_check_is_silent() {
+        assert_eq!(CliError::CheckFailed.exit_code(), 1);
+        assert_eq!(CliError::CheckFailed.to_string(), "");
+    }
+
+    #[test]
+    fn test_display_is_the_message() {
+        assert_eq!(CliError::NotFound("Task with ID 4 not found".to_string()).to_string(), "Task with ID 4 not found");
+    }
+}
@@ -0,0 +1,201 @@
+use chrono::{DateTime, Utc};
+use gh_actions::utils::render_table;
+use gh_actions::{Task, User};
+use std::cmp::Ordering;
+
+/// Cells wider than this many terminal columns are truncated with `…`.
+pub const MAX_COLUMN_WIDTH: usize = 40;
+
+/// A list column that can be picked with `--columns` and `--sort`.
+pub struct Column<T> {
+    pub name: &'static str,
+    pub header: &'static str,
+    pub value: fn(&T) -> String,
+    pub compare: fn(&T, &T) -> Ordering,
+}
+
+pub const USER_COLUMNS: &[Column<User>] = &[
+    Column {
+        name: "id",
+        header: "ID",
+        value: |u| u.id.to_string(),
+        compare: |a, b| a.id.cmp(&b.id),
+    },
+    Column {
+        name: "name",
+        header: "Name",
+        value: |u| u.name.clone(),
+        compare: |a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+    },
+    Column {
+        name: "email",
+        header: "Email",
+        value: |u| u.email.clone(),
+        compare: |a, b| a.email.to_lowercase().cmp(&b.email.to_lowercase()),
+    },
+    Column {
+        name: "active",
+        header: "Active",
+        value: |u| u.active.to_string(),
+        compare: |a, b| a.active.cmp(&b.active),
+    },
+];
+
+pub const DEFAULT_USER_COLUMNS: &[&str] = &["id", "name", "email", "active"];
+
+pub const TASK_COLUMNS: &[Column<Task>] = &[
+    Column {
+        name: "id",
+        header: "ID",
+        value: |t| t.id.to_string(),
+        compare: |a, b| a.id.cmp(&b.id),
+    },
+    Column {
+        name: "done",
+        header: "Done",
+        value: |t| if t.completed { "x".to_string() } else { String::new() },
+        compare: |a, b| a.completed.cmp(&b.completed),
+    },
+    Column {
+        name: "title",
+        header: "Title",
+        value: |t| t.title.clone(),
+        compare: |a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+    },
+    Column {
+        name: "description",
+        header: "Description",
+        value: |t| t.description.clone(),
+        compare: |a, b| a.description.to_lowercase().cmp(&b.description.to_lowercase()),
+    },
+    Column {
+        name: "created",
+        header: "Created",
+        value: |t| format_date(Some(t.created_at)),
+        compare: |a, b| a.created_at.cmp(&b.created_at),
+    },
+    Column {
+        name: "completed",
+        header: "Completed",
+        value: |t| format_date(t.completed_at),
+        compare: |a, b| a.completed_at.cmp(&b.completed_at),
+    },
+    Column {
+        name: "due",
+        header: "Due",
+        value: |t| format_date(t.due_date),
+        compare: |a, b| a.due_date.cmp(&b.due_date),
+    },
+];
+
+pub const DEFAULT_TASK_COLUMNS: &[&str] = &["id", "done", "title", "description"];
+
+pub fn format_date(date: Option<DateTime<Utc>>) -> String {
+    date.map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+fn find_column<'a, T>(name: &str, all: &'a [Column<T>]) -> Result<&'a Column<T>, String> {
+    all.iter().find(|column| column.name == name).ok_or_else(|| {
+        let names: Vec<&str> = all.iter().map(|column| column.name).collect();
+        format!("unknown column '{}' (available: {})", name, names.join(", "))
+    })
+}
+
+/// Resolves a comma-separated `--columns` value, or the defaults when absent.
+pub fn select_columns<'a, T>(
+    spec: Option<&str>,
+    all: &'a [Column<T>],
+    default: &[&str],
+) -> Result<Vec<&'a Column<T>>, String> {
+    match spec {
+        Some(spec) => spec
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| find_column(name, all))
+            .collect(),
+        None => default.iter().map(|name| find_column(name, all)).collect(),
+    }
+}
+
+/// Orders `items` by the named column; ties keep their existing order.
+pub fn sort_by_column<T>(items: &mut [&T], column: &str, all: &[Column<T>]) -> Result<(), String> {
+    let column = find_column(column, all)?;
+    items.sort_by(|a, b| (column.compare)(a, b));
+    Ok(())
+}
+
+/// Renders `items` as a table with one row per item, in order. Line breaks
+/// in a cell are written as `\n` and `\r` so each row stays on one line.
+pub fn render<T>(items: &[&T], columns: &[&Column<T>]) -> String {
+    let headers: Vec<&str> = columns.iter().map(|column| column.header).collect();
+    let rows: Vec<Vec<String>> = items
+        .iter()
+        .map(|item| columns.iter().map(|column| escape_line_breaks(&(column.value)(item))).collect())
+        .collect();
+    render_table(&headers, &rows, MAX_COLUMN_WIDTH)
+}
+
+fn escape_line_breaks(cell: &str) -> String {
+    cell.replace('\r', "\\r").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: u32, name: &str) -> User {
+        User {
+            id,
+            name: name.to_string(),
+            email: format!("{}@example.com", name.to_lowercase()),
+            active: id % 2 == 1,
+        }
+    }
+
+    #[test]
+    fn test_select_columns() {
+        let columns = select_columns(Some("email, id"), USER_COLUMNS, DEFAULT_USER_COLUMNS).unwrap();
+        let names: Vec<&str> = columns.iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["email", "id"]);
+
+        let defaults = select_columns(None, TASK_COLUMNS, DEFAULT_TASK_COLUMNS).unwrap();
+        assert_eq!(defaults.len(), DEFAULT_TASK_COLUMNS.len());
+
+        let err = select_columns(Some("id,phone"), USER_COLUMNS, DEFAULT_USER_COLUMNS).err().unwrap();
+        assert!(err.contains("unknown column 'phone'"));
+    }
+
+    #[test]
+    fn test_sort_and_render() {
+        let users = [user(2, "bob"), user(10, "Alice"), user(1, "carol")];
+        let mut refs: Vec<&User> = users.iter().collect();
+
+        sort_by_column(&mut refs, "id", USER_COLUMNS).unwrap();
+        let columns = select_columns(Some("id,name"), USER_COLUMNS, DEFAULT_USER_COLUMNS).unwrap();
+        assert_eq!(
+            render(&refs, &columns),
+            "ID  Name\n--  -----\n1   carol\n2   bob\n10  Alice\n"
+        );
+
+        sort_by_column(&mut refs, "name", USER_COLUMNS).unwrap();
+        assert_eq!(
+            render(&refs, &columns),
+            "ID  Name\n--  -----\n10  Alice\n2   bob\n1   carol\n"
+        );
+    }
+
+    #[test]
+    fn test_render_keeps_one_line_per_row() {
+        let users = [user(1, "Multi\nline"), user(2, "Carriage\r\nreturn")];
+        let refs: Vec<&User> = users.iter().collect();
+        let columns = select_columns(Some("id,name"), USER_COLUMNS, DEFAULT_USER_COLUMNS).unwrap();
+        let table = render(&refs, &columns);
+        assert_eq!(table.lines().count(), refs.len() + 2);
+        assert_eq!(
+            table,
+            "ID  Name\n--  ------------------\n1   Multi\\nline\n2   Carriage\\r\\nreturn\n"
+        );
+    }
+}
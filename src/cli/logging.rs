@@ -0,0 +1,62 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Writes log records to stderr, one line each.
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{}{}", prefix(record.level()), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn prefix(level: Level) -> &'static str {
+    match level {
+        Level::Error => "Error: ",
+        Level::Warn => "Warning: ",
+        Level::Info => "[info] ",
+        Level::Debug => "[debug] ",
+        Level::Trace => "[trace] ",
+    }
+}
+
+/// The level for `-v` repeated `verbosity` times; `--quiet` wins over `-v`.
+pub fn level_for(verbosity: u8, quiet: bool) -> LevelFilter {
+    match (quiet, verbosity) {
+        (true, _) => LevelFilter::Error,
+        (false, 0) => LevelFilter::Warn,
+        (false, 1) => LevelFilter::Info,
+        (false, 2) => LevelFilter::Debug,
+        (false, _) => LevelFilter::Trace,
+    }
+}
+
+/// Installs the stderr logger for the rest of the process.
+pub fn init(level: LevelFilter) {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_for() {
+        assert_eq!(level_for(0, false), LevelFilter::Warn);
+        assert_eq!(level_for(1, false), LevelFilter::Info);
+        assert_eq!(level_for(2, false), LevelFilter::Debug);
+        assert_eq!(level_for(5, false), LevelFilter::Trace);
+        assert_eq!(level_for(2, true), LevelFilter::Error);
+    }
+}
@@ -0,0 +1,15 @@
+//! Building blocks for the `gh_actions` command line binary.
+//!
+//! These modules are only compiled into the binary (`src/main.rs`), not into
+//! the library or the WASM package.
+
+pub mod batch;
+pub mod config;
+pub mod confirm;
+pub mod due;
+pub mod error;
+pub mod listing;
+pub mod logging;
+pub mod search;
+pub mod stats;
+pub mod style;
@@ -0,0 +1,51 @@
+/// Byte length of the match of `query` at the start of `text`, comparing
+/// characters case-insensitively.
+fn match_len(text: &str, query: &str) -> Option<usize> {
+    let mut text_chars = text.char_indices();
+    for q in query.chars() {
+        let (_, c) = text_chars.next()?;
+        if !c.to_lowercase().eq(q.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(text_chars.next().map_or(text.len(), |(index, _)| index))
+}
+
+/// Passes every case-insensitive occurrence of `query` in `line` through
+/// `paint`, leaving the rest of the line untouched.
+pub fn highlight(line: &str, query: &str, paint: impl Fn(&str) -> String) -> String {
+    if query.is_empty() {
+        return line.to_string();
+    }
+
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+    while !rest.is_empty() {
+        if let Some(len) = match_len(rest, query) {
+            result.push_str(&paint(&rest[..len]));
+            rest = &rest[len..];
+        } else {
+            let next = rest.chars().next().map_or(rest.len(), char::len_utf8);
+            result.push_str(&rest[..next]);
+            rest = &rest[next..];
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brackets(text: &str) -> String {
+        format!("[{}]", text)
+    }
+
+    #[test]
+    fn test_highlight_is_case_insensitive() {
+        assert_eq!(highlight("Write Docs, docs", "DOCS", brackets), "Write [Docs], [docs]");
+        assert_eq!(highlight("Crème brûlée", "BRÛ", brackets), "Crème [brû]lée");
+        assert_eq!(highlight("no match", "xyz", brackets), "no match");
+        assert_eq!(highlight("anything", "", brackets), "anything");
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::Duration;
+use gh_actions::utils::format_duration_human;
+use gh_actions::TaskReport;
+
+/// Characters for increasing bar heights; an empty day is always `_`.
+const SPARK_LEVELS: &[char] = &['_', '.', ':', '-', '=', '+', '*', '#'];
+
+/// Draws one ASCII character per value, scaled so the largest value is `#`.
+pub fn sparkline(values: &[usize]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    let top = SPARK_LEVELS.len() - 1;
+    values
+        .iter()
+        .map(|&value| {
+            if value == 0 {
+                SPARK_LEVELS[0]
+            } else {
+                SPARK_LEVELS[(value * top).div_ceil(max)]
+            }
+        })
+        .collect()
+}
+
+fn hours(value: Option<f64>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |h| format!("{:.1} hours", h))
+}
+
+/// The label/value lines printed by `task stats`, aligned on the values.
+pub fn report_lines(report: &TaskReport) -> Vec<(&'static str, String)> {
+    let counts: Vec<usize> = report.completions_per_day.iter().map(|day| day.count).collect();
+    let range = match (report.completions_per_day.first(), report.completions_per_day.last()) {
+        (Some(first), Some(last)) => format!("  {} .. {}", first.date, last.date),
+        _ => String::new(),
+    };
+
+    vec![
+        ("Total tasks", report.total.to_string()),
+        ("Completed", report.completed.to_string()),
+        ("Pending", report.pending.to_string()),
+        ("Completion rate", format!("{:.1}%", report.completion_rate * 100.0)),
+        ("Average completion", hours(report.average_completion_hours)),
+        ("Median completion", hours(report.median_completion_hours)),
+        ("Velocity", format!("{:.2} tasks/day", report.velocity)),
+        ("Current streak", format!("{} days", report.current_streak)),
+        ("Completions per day", format!("{}{}", sparkline(&counts), range)),
+        ("Top tags", top_tags(report)),
+        ("Oldest incomplete", oldest_incomplete(report)),
+    ]
+}
+
+fn top_tags(report: &TaskReport) -> String {
+    if report.top_tags.is_empty() {
+        return "none".to_string();
+    }
+    let tags: Vec<String> = report.top_tags.iter().map(|t| format!("{} ({})", t.tag, t.count)).collect();
+    tags.join(", ")
+}
+
+fn oldest_incomplete(report: &TaskReport) -> String {
+    report.oldest_incomplete.as_ref().map_or_else(
+        || "none".to_string(),
+        |task| {
+            let age = Duration::seconds((task.age_hours * 3600.0) as i64);
+            format!("{} (#{}, created {})", task.title, task.id, format_duration_human(age))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[0, 0]), "__");
+        assert_eq!(sparkline(&[0, 1, 7, 3]), "_.#-");
+        assert_eq!(sparkline(&[1, 100]), ".#");
+    }
+}
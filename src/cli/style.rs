@@ -0,0 +1,107 @@
+use std::io::{self, IsTerminal};
+use std::sync::OnceLock;
+
+static NO_COLOR_FLAG: OnceLock<bool> = OnceLock::new();
+
+/// Records the `--no-color` flag for the rest of the process.
+pub fn init(no_color_flag: bool) {
+    let _ = NO_COLOR_FLAG.set(no_color_flag);
+}
+
+/// Styling for text written to stdout.
+pub fn stdout() -> Style {
+    Style::detect(io::stdout().is_terminal())
+}
+
+/// Styling for text written to stderr.
+pub fn stderr() -> Style {
+    Style::detect(io::stderr().is_terminal())
+}
+
+/// Wraps text in ANSI escape sequences when color output is enabled.
+///
+/// Color is disabled by `--no-color`, by a non-empty `NO_COLOR` environment
+/// variable (see <https://no-color.org>), and whenever the stream is not a
+/// terminal, so piped output stays free of escape sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    enabled: bool,
+}
+
+impl Style {
+    pub fn new(enabled: bool) -> Self {
+        Style { enabled }
+    }
+
+    fn detect(is_terminal: bool) -> Self {
+        let flag = NO_COLOR_FLAG.get().copied().unwrap_or(false);
+        let env = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Style::new(is_terminal && !flag && !env)
+    }
+
+    fn paint(&self, codes: &str, text: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{}\x1b[0m", codes, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub fn success(&self, text: &str) -> String {
+        self.paint("32", text)
+    }
+
+    pub fn error(&self, text: &str) -> String {
+        self.paint("1;31", text)
+    }
+
+    pub fn heading(&self, text: &str) -> String {
+        self.paint("1", text)
+    }
+
+    pub fn dim(&self, text: &str) -> String {
+        self.paint("2", text)
+    }
+
+    /// Bold yellow, used for search matches.
+    pub fn highlight(&self, text: &str) -> String {
+        self.paint("1;33", text)
+    }
+
+    /// Dimmed and struck through, used for completed tasks.
+    pub fn done(&self, text: &str) -> String {
+        self.paint("2;9", text)
+    }
+
+    /// Red, used for tasks past their due date.
+    pub fn overdue(&self, text: &str) -> String {
+        self.paint("31", text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_style_is_plain() {
+        let style = Style::new(false);
+        assert_eq!(style.success("ok"), "ok");
+        assert_eq!(style.error("Error:"), "Error:");
+        assert_eq!(style.done("task"), "task");
+    }
+
+    #[test]
+    fn test_enabled_style_wraps_in_escapes() {
+        let style = Style::new(true);
+        assert_eq!(style.success("ok"), "\x1b[32mok\x1b[0m");
+        assert_eq!(style.dim("#1"), "\x1b[2m#1\x1b[0m");
+        assert_eq!(style.done("task"), "\x1b[2;9mtask\x1b[0m");
+        assert_eq!(style.overdue("task"), "\x1b[31mtask\x1b[0m");
+    }
+
+    #[test]
+    fn test_non_terminal_disables_color() {
+        assert!(!Style::detect(false).enabled);
+    }
+}
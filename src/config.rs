@@ -0,0 +1,155 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Prefix of the environment variables read by [`AppConfig::from_env`].
+pub const ENV_PREFIX: &str = "GHACTIONS_";
+
+/// Settings for programs embedding the library.
+///
+/// Values come from built-in defaults, then an optional TOML file, then
+/// `GHACTIONS_*` environment variables, each layer overriding the previous
+/// one. A file only needs the keys it wants to change:
+///
+/// ```toml
+/// tasks_file = "/var/lib/tasks.json"
+/// max_users = 50
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    /// `GHACTIONS_USERS_FILE`, default `users.json`
+    pub users_file: String,
+    /// `GHACTIONS_TASKS_FILE`, default `tasks.json`
+    pub tasks_file: String,
+    /// `GHACTIONS_LOG_LEVEL`: `off`, `error`, `warn`, `info`, `debug` or
+    /// `trace`, default `warn`
+    pub log_level: String,
+    /// `GHACTIONS_MAX_USERS`, default 1000
+    pub max_users: usize,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            users_file: "users.json".to_string(),
+            tasks_file: "tasks.json".to_string(),
+            log_level: "warn".to_string(),
+            max_users: 1000,
+        }
+    }
+}
+
+impl AppConfig {
+    /// The defaults with any `GHACTIONS_*` environment variables applied.
+    pub fn from_env() -> Self {
+        AppConfig::default().overlay(|name| std::env::var(name).ok())
+    }
+
+    /// Reads a TOML file; keys it leaves out keep their defaults.
+    pub fn from_toml(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read config file {}: {}", path.display(), e))?;
+        let config: AppConfig = toml::from_str(&content)
+            .map_err(|e| format!("config file {}: {}", path.display(), e.message()))?;
+        validate_log_level(&config.log_level)
+            .map_err(|e| format!("config file {}: {}", path.display(), e))?;
+        Ok(config)
+    }
+
+    /// The file at `config_path`, if given, with environment variables on top.
+    ///
+    /// This never fails: an unreadable file or a bad variable is logged and
+    /// skipped, so a program can always start with usable settings.
+    pub fn load(config_path: Option<&Path>) -> Self {
+        let base = match config_path.map(AppConfig::from_toml) {
+            Some(Ok(config)) => config,
+            Some(Err(e)) => {
+                log::warn!("{}, using defaults", e);
+                AppConfig::default()
+            }
+            None => AppConfig::default(),
+        };
+        base.overlay(|name| std::env::var(name).ok())
+    }
+
+    /// Applies the variables that `var` returns, by full name.
+    fn overlay(mut self, var: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |key: &str| {
+            let name = format!("{}{}", ENV_PREFIX, key);
+            var(&name).filter(|value| !value.is_empty()).map(|value| (name, value))
+        };
+        if let Some((_, value)) = var("USERS_FILE") {
+            self.users_file = value;
+        }
+        if let Some((_, value)) = var("TASKS_FILE") {
+            self.tasks_file = value;
+        }
+        if let Some((name, value)) = var("LOG_LEVEL") {
+            match validate_log_level(&value) {
+                Ok(()) => self.log_level = value,
+                Err(e) => log::warn!("ignoring {}: {}", name, e),
+            }
+        }
+        if let Some((name, value)) = var("MAX_USERS") {
+            match value.parse() {
+                Ok(max_users) => self.max_users = max_users,
+                Err(_) => log::warn!("ignoring {}: '{}' is not a whole number", name, value),
+            }
+        }
+        self
+    }
+}
+
+fn validate_log_level(level: &str) -> Result<(), String> {
+    log::LevelFilter::from_str(level).map(|_| ()).map_err(|_| {
+        format!(
+            "unknown log level '{}', expected 'off', 'error', 'warn', 'info', 'debug' or 'trace'",
+            level
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> =
+            vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_overlay() {
+        let config = AppConfig::default().overlay(env(&[
+            ("GHACTIONS_TASKS_FILE", "/tmp/tasks.json"),
+            ("GHACTIONS_USERS_FILE", ""),
+            ("GHACTIONS_LOG_LEVEL", "loud"),
+            ("GHACTIONS_MAX_USERS", "25"),
+        ]));
+        assert_eq!(config.tasks_file, "/tmp/tasks.json");
+        assert_eq!(config.users_file, "users.json");
+        assert_eq!(config.log_level, "warn");
+        assert_eq!(config.max_users, 25);
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "log_level = \"debug\"\nmax_users = 5\n").unwrap();
+        let config = AppConfig::from_toml(&path).unwrap();
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.max_users, 5);
+        assert_eq!(config.tasks_file, "tasks.json");
+
+        fs::write(&path, "colour = \"blue\"\n").unwrap();
+        assert!(AppConfig::from_toml(&path).unwrap_err().contains("unknown field `colour`"));
+        fs::write(&path, "log_level = \"loud\"\n").unwrap();
+        assert!(AppConfig::from_toml(&path).unwrap_err().contains("unknown log level 'loud'"));
+        assert!(AppConfig::from_toml(&dir.path().join("missing.toml")).is_err());
+    }
+}
@@ -0,0 +1,376 @@
+use crate::task::{Priority, Recurrence, Task};
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+/// File formats tasks can be exported to.
+///
+/// JSON round-trips every field. CSV holds the same fields as text, with
+/// timestamps in RFC 3339. Markdown is a readable checklist and cannot be
+/// imported back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+const CSV_HEADER: [&str; 11] = [
+    "id",
+    "title",
+    "description",
+    "completed",
+    "created_at",
+    "completed_at",
+    "due_date",
+    "recurrence",
+    "priority",
+    "estimated_hours",
+    "tags",
+];
+
+/// Columns of the oldest CSV exports. Later exports append the remaining
+/// `CSV_HEADER` columns, so any header between the two is a prefix of it.
+const CSV_FIRST_COLUMNS: usize = 8;
+
+impl ExportFormat {
+    /// Picks the format from a file extension: `.json`, `.csv`, `.md` or `.markdown`.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+        match extension.as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            _ => Err(format!(
+                "cannot tell the format of '{}', expected a .json, .csv or .md file",
+                path.display()
+            )),
+        }
+    }
+
+    /// Renders `tasks` in this format, in the order given.
+    pub fn write(&self, tasks: &[&Task]) -> Result<String, String> {
+        match self {
+            ExportFormat::Json => serde_json::to_string_pretty(tasks).map_err(|e| e.to_string()),
+            ExportFormat::Csv => Ok(to_csv(tasks)),
+            ExportFormat::Markdown => Ok(to_markdown(tasks)),
+        }
+    }
+
+    /// Parses tasks previously written in this format.
+    pub fn read(&self, content: &str) -> Result<Vec<Task>, String> {
+        match self {
+            ExportFormat::Json => serde_json::from_str(content).map_err(|e| format!("invalid JSON: {}", e)),
+            ExportFormat::Csv => from_csv(content),
+            ExportFormat::Markdown => {
+                Err("Markdown exports cannot be imported, use a JSON or CSV export".to_string())
+            }
+        }
+    }
+}
+
+/// Lets a CLI flag name the format as `json`, `csv` or `markdown` (or `md`).
+impl clap::ValueEnum for ExportFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[ExportFormat::Json, ExportFormat::Csv, ExportFormat::Markdown]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(match self {
+            ExportFormat::Json => clap::builder::PossibleValue::new("json"),
+            ExportFormat::Csv => clap::builder::PossibleValue::new("csv"),
+            ExportFormat::Markdown => clap::builder::PossibleValue::new("markdown").alias("md"),
+        })
+    }
+}
+
+fn to_csv(tasks: &[&Task]) -> String {
+    let mut out = CSV_HEADER.join(",");
+    out.push('\n');
+    for task in tasks {
+        let fields = [
+            task.id.to_string(),
+            task.title.clone(),
+            task.description.clone(),
+            task.completed.to_string(),
+            task.created_at.to_rfc3339(),
+            task.completed_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            task.due_date.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            task.recurrence.map(|recurrence| recurrence.to_string()).unwrap_or_default(),
+            task.priority.to_string(),
+            task.estimated_hours.map(|hours| hours.to_string()).unwrap_or_default(),
+            task.tags.join(","),
+        ];
+        let escaped: Vec<String> = fields.iter().map(|f| escape_csv_field(f)).collect();
+        out.push_str(&escaped.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn from_csv(content: &str) -> Result<Vec<Task>, String> {
+    let mut records = parse_csv(content)?.into_iter();
+    let columns = match records.next() {
+        Some(header)
+            if (CSV_FIRST_COLUMNS..=CSV_HEADER.len()).contains(&header.len())
+                && header == CSV_HEADER[..header.len()] =>
+        {
+            header.len()
+        }
+        _ => return Err(format!("CSV must start with the header `{}`", CSV_HEADER.join(","))),
+    };
+
+    records
+        .enumerate()
+        .map(|(index, record)| {
+            // The header is record 1
+            let number = index + 2;
+            parse_csv_task(&record, columns).map_err(|e| format!("CSV record {}: {}", number, e))
+        })
+        .collect()
+}
+
+/// Parses a record with the first `columns` fields of `CSV_HEADER`. The
+/// columns an older export lacks take their defaults.
+fn parse_csv_task(record: &[String], columns: usize) -> Result<Task, String> {
+    if record.len() != columns {
+        return Err(format!("expected {} fields, found {}", columns, record.len()));
+    }
+    let field = |index: usize| record.get(index).map_or("", String::as_str);
+    let id = record[0]
+        .parse()
+        .map_err(|_| format!("invalid id '{}'", record[0]))?;
+    let completed = record[3]
+        .parse()
+        .map_err(|_| format!("invalid completed value '{}', expected true or false", record[3]))?;
+
+    Ok(Task {
+        id,
+        title: record[1].clone(),
+        description: record[2].clone(),
+        completed,
+        created_at: parse_timestamp(&record[4])?,
+        completed_at: parse_optional(&record[5], parse_timestamp)?,
+        due_date: parse_optional(&record[6], parse_timestamp)?,
+        recurrence: parse_optional(&record[7], recurrence_from_str)?,
+        priority: record.get(8).map_or(Ok(Priority::default()), |value| Priority::parse(value))?,
+        estimated_hours: parse_optional(field(9), parse_hours)?,
+        tags: parse_tags(field(10))?,
+        next_occurrence_spawned: false,
+    })
+}
+
+fn parse_optional<T>(field: &str, parse: fn(&str) -> Result<T, String>) -> Result<Option<T>, String> {
+    if field.is_empty() {
+        Ok(None)
+    } else {
+        parse(field).map(Some)
+    }
+}
+
+fn parse_timestamp(field: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(field)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|_| format!("invalid RFC 3339 timestamp '{}'", field))
+}
+
+fn parse_hours(field: &str) -> Result<f64, String> {
+    field
+        .parse()
+        .ok()
+        .filter(|hours: &f64| hours.is_finite() && *hours > 0.0)
+        .ok_or_else(|| format!("invalid estimated_hours '{}', expected a positive number", field))
+}
+
+fn parse_tags(field: &str) -> Result<Vec<String>, String> {
+    let mut task = Task::new(0, String::new(), String::new());
+    for tag in field.split(',').filter(|tag| !tag.trim().is_empty()) {
+        task.add_tag(tag)?;
+    }
+    Ok(task.tags)
+}
+
+fn recurrence_from_str(field: &str) -> Result<Recurrence, String> {
+    match field {
+        "daily" => Ok(Recurrence::Daily),
+        "weekly" => Ok(Recurrence::Weekly),
+        "monthly" => Ok(Recurrence::Monthly),
+        _ => field
+            .strip_prefix("every ")
+            .and_then(|rest| rest.strip_suffix(" days"))
+            .and_then(|days| days.parse().ok())
+            .map(Recurrence::Custom)
+            .ok_or_else(|| format!("invalid recurrence '{}'", field)),
+    }
+}
+
+/// Quotes a field when it contains a comma, quote or line break.
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Splits CSV text into records, following RFC 4180 quoting.
+pub(crate) fn parse_csv(content: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn to_markdown(tasks: &[&Task]) -> String {
+    let mut out = String::from("# Tasks\n\n");
+    for task in tasks {
+        let check = if task.completed { "x" } else { " " };
+        out.push_str(&format!("- [{}] #{} {}", check, task.id, task.title));
+        if !task.description.is_empty() {
+            out.push_str(&format!(" — {}", task.description.replace('\n', " ")));
+        }
+        if let Some(due) = task.due_date {
+            out.push_str(&format!(" (due {})", due.format("%Y-%m-%d")));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_tasks() -> Vec<Task> {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let mut done = Task::new(1, "Write docs".to_string(), "Usage, \"quick\" guide\nand FAQ".to_string());
+        done.created_at = at("2024-03-01T08:00:00Z");
+        done.completed = true;
+        done.completed_at = Some(at("2024-03-02T09:30:00Z"));
+        let mut open = Task::new(2, "Water plants".to_string(), String::new());
+        open.created_at = at("2024-03-03T10:00:00Z");
+        open.due_date = Some(at("2024-03-10T00:00:00Z"));
+        open.recurrence = Some(Recurrence::Custom(3));
+        open.priority = Priority::High;
+        open.estimated_hours = Some(2.5);
+        open.tags = vec!["home".to_string(), "weekly chores".to_string()];
+        vec![done, open]
+    }
+
+    fn assert_same_tasks(a: &[Task], b: &[Task]) {
+        let json = |tasks: &[Task]| serde_json::to_value(tasks).unwrap();
+        assert_eq!(json(a), json(b));
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(ExportFormat::from_path(Path::new("out/tasks.JSON")), Ok(ExportFormat::Json));
+        assert_eq!(ExportFormat::from_path(Path::new("tasks.csv")), Ok(ExportFormat::Csv));
+        assert_eq!(ExportFormat::from_path(Path::new("TODO.md")), Ok(ExportFormat::Markdown));
+        assert!(ExportFormat::from_path(Path::new("tasks.txt")).is_err());
+        assert!(ExportFormat::from_path(Path::new("tasks")).is_err());
+    }
+
+    #[test]
+    fn test_format_from_flag_value() {
+        use clap::ValueEnum;
+        assert_eq!(ExportFormat::from_str("csv", false), Ok(ExportFormat::Csv));
+        assert_eq!(ExportFormat::from_str("JSON", true), Ok(ExportFormat::Json));
+        assert_eq!(ExportFormat::from_str("md", false), Ok(ExportFormat::Markdown));
+        assert_eq!(ExportFormat::from_str("markdown", false), Ok(ExportFormat::Markdown));
+        assert!(ExportFormat::from_str("txt", false).is_err());
+    }
+
+    #[test]
+    fn test_json_and_csv_round_trip() {
+        let tasks = sample_tasks();
+        let refs: Vec<&Task> = tasks.iter().collect();
+        for format in [ExportFormat::Json, ExportFormat::Csv] {
+            let written = format.write(&refs).unwrap();
+            assert_same_tasks(&format.read(&written).unwrap(), &tasks);
+        }
+    }
+
+    #[test]
+    fn test_csv_quotes_special_characters() {
+        let tasks = sample_tasks();
+        let csv = to_csv(&[&tasks[0]]);
+        assert!(csv.contains("\"Usage, \"\"quick\"\" guide\nand FAQ\""), "{}", csv);
+        assert!(csv.contains("2024-03-01T08:00:00+00:00"), "{}", csv);
+    }
+
+    #[test]
+    fn test_csv_errors_name_the_record() {
+        let header = CSV_HEADER.join(",");
+        let err = from_csv(&format!("{}\n1,Title,,maybe,2024-03-01T08:00:00Z,,,,medium,,\n", header)).unwrap_err();
+        assert_eq!(err, "CSV record 2: invalid completed value 'maybe', expected true or false");
+        let err = from_csv(&format!("{}\n1,Title,,false,2024-03-01T08:00:00Z,,,,medium,-2,\n", header)).unwrap_err();
+        assert_eq!(err, "CSV record 2: invalid estimated_hours '-2', expected a positive number");
+        assert!(from_csv("id,title\n").unwrap_err().contains("header"));
+        assert!(from_csv(&format!("{},extra\n", header)).unwrap_err().contains("header"));
+        let err = from_csv(&format!("{}\n1,Title,,false,2024-03-01T08:00:00Z,,,,medium,\n", header)).unwrap_err();
+        assert_eq!(err, "CSV record 2: expected 11 fields, found 10");
+        assert_eq!(parse_csv("\"open").unwrap_err(), "unterminated quoted field");
+    }
+
+    #[test]
+    fn test_csv_from_older_exports() {
+        let old = "id,title,description,completed,created_at,completed_at,due_date,recurrence\n\
+                   1,Water plants,,false,2024-03-01T08:00:00Z,,,weekly\n";
+        let tasks = from_csv(old).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Water plants");
+        assert_eq!(tasks[0].recurrence, Some(Recurrence::Weekly));
+        assert_eq!(tasks[0].priority, Priority::Medium);
+        assert_eq!(tasks[0].estimated_hours, None);
+        assert!(tasks[0].tags.is_empty());
+
+        let with_hours = "id,title,description,completed,created_at,completed_at,due_date,recurrence,priority,\
+                          estimated_hours\n2,Review,,true,2024-03-01T08:00:00Z,2024-03-02T08:00:00Z,,,high,1.5\n";
+        let tasks = from_csv(with_hours).unwrap();
+        assert_eq!(tasks[0].priority, Priority::High);
+        assert_eq!(tasks[0].estimated_hours, Some(1.5));
+        assert!(tasks[0].tags.is_empty());
+    }
+
+    #[test]
+    fn test_markdown_is_export_only() {
+        let tasks = sample_tasks();
+        let refs: Vec<&Task> = tasks.iter().collect();
+        let markdown = ExportFormat::Markdown.write(&refs).unwrap();
+        assert!(markdown.contains("- [x] #1 Write docs"));
+        assert!(markdown.contains("- [ ] #2 Water plants (due 2024-03-10)"));
+        assert!(ExportFormat::Markdown.read(&markdown).unwrap_err().contains("cannot be imported"));
+    }
+}
@@ -0,0 +1,167 @@
+//! Task storage in IndexedDB, for task sets that outgrow localStorage.
+//!
+//! Only compiled with the `indexeddb` feature. See [`IndexedDbStorage`].
+
+use crate::storage::{LocalStorage, StorageBackend};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+pub const DB_NAME: &str = "gh_actions";
+const DB_VERSION: u32 = 1;
+const STORE_NAME: &str = "kv";
+
+thread_local! {
+    // The open database. JS handles cannot cross threads, so it lives here
+    // rather than in the (`Send`) storage value.
+    static DATABASE: RefCell<Option<IdbDatabase>> = const { RefCell::new(None) };
+    // Shared by every write, so a write that succeeds leaks nothing
+    static ON_WRITE_ERROR: Closure<dyn FnMut()> = Closure::new(|| {
+        web_sys::console::warn_1(&"failed to write to IndexedDB, the next save retries".into());
+    });
+}
+
+/// A key-value store in IndexedDB, which is not limited to the few
+/// megabytes localStorage allows.
+///
+/// IndexedDB is asynchronous while [`StorageBackend`] is not, so
+/// [`open`](Self::open) reads the values into memory up front. `load` then
+/// answers from memory, and `save` updates memory and queues the write, which
+/// IndexedDB commits in order in the background. A failed write is reported
+/// on the console; the next save writes the whole value again.
+#[derive(Debug, Default)]
+pub struct IndexedDbStorage {
+    values: HashMap<String, String>,
+    /// Keys moved from localStorage by [`open`](Self::open)
+    migrated: HashSet<String>,
+}
+
+impl IndexedDbStorage {
+    /// Opens the database and reads `keys` from it.
+    ///
+    /// A key found only in localStorage is copied into IndexedDB and then
+    /// removed from localStorage, so the move happens once. Fails when the
+    /// browser has no usable IndexedDB, as in some private browsing modes.
+    pub async fn open(keys: &[&str]) -> Result<Self, String> {
+        let db = open_database().await.map_err(describe)?;
+        let mut values = HashMap::new();
+        let mut migrated = HashSet::new();
+        for &key in keys {
+            let value = match get(&db, key).await.map_err(describe)? {
+                Some(value) => Some(value),
+                None => {
+                    let value = migrate(&db, key).await?;
+                    if value.is_some() {
+                        migrated.insert(key.to_string());
+                    }
+                    value
+                }
+            };
+            if let Some(value) = value {
+                values.insert(key.to_string(), value);
+            }
+        }
+        DATABASE.with(|database| *database.borrow_mut() = Some(db));
+        Ok(IndexedDbStorage { values, migrated })
+    }
+
+    /// Whether [`open`](Self::open) moved `key` here from localStorage
+    pub fn was_migrated(&self, key: &str) -> bool {
+        self.migrated.contains(key)
+    }
+}
+
+impl StorageBackend for IndexedDbStorage {
+    fn kind(&self) -> &'static str {
+        "indexedDB"
+    }
+
+    fn load(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.values.get(key).cloned())
+    }
+
+    fn save(&mut self, key: &str, value: &str) -> Result<(), String> {
+        self.values.insert(key.to_string(), value.to_string());
+        DATABASE.with(|database| match database.borrow().as_ref() {
+            Some(db) => queue_put(db, key, value).map(|_| ()).map_err(describe),
+            None => Err("IndexedDB is not open".to_string()),
+        })
+    }
+}
+
+/// Moves `key` from localStorage into the database, returning its value.
+async fn migrate(db: &IdbDatabase, key: &str) -> Result<Option<String>, String> {
+    let Some(value) = LocalStorage.load(key)? else {
+        return Ok(None);
+    };
+    let transaction = queue_put(db, key, &value).map_err(describe)?;
+    completion(&transaction).await.map_err(describe)?;
+    LocalStorage::remove(key)?;
+    Ok(Some(value))
+}
+
+async fn open_database() -> Result<IdbDatabase, JsValue> {
+    let factory = web_sys::window()
+        .ok_or("no window object")?
+        .indexed_db()?
+        .ok_or("IndexedDB is not available")?;
+    let request = factory.open_with_u32(DB_NAME, DB_VERSION)?;
+
+    // Only runs when the database is created, as there is a single version
+    let upgrade_request = request.clone();
+    let on_upgrade = Closure::once(move || {
+        if let Ok(db) = upgrade_request.result().and_then(|db| db.dyn_into::<IdbDatabase>()) {
+            let _ = db.create_object_store(STORE_NAME);
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+    let db = result(&request).await?;
+    request.set_onupgradeneeded(None);
+    db.dyn_into()
+}
+
+async fn get(db: &IdbDatabase, key: &str) -> Result<Option<String>, JsValue> {
+    let store = db.transaction_with_str(STORE_NAME)?.object_store(STORE_NAME)?;
+    let value = result(&store.get(&JsValue::from_str(key))?).await?;
+    Ok(value.as_string())
+}
+
+/// Starts writing `value` under `key` and returns the transaction doing it.
+fn queue_put(db: &IdbDatabase, key: &str, value: &str) -> Result<IdbTransaction, JsValue> {
+    let transaction = db.transaction_with_str_and_mode(STORE_NAME, IdbTransactionMode::Readwrite)?;
+    ON_WRITE_ERROR.with(|on_error| transaction.set_onerror(Some(on_error.as_ref().unchecked_ref())));
+    transaction
+        .object_store(STORE_NAME)?
+        .put_with_key(&JsValue::from_str(value), &JsValue::from_str(key))?;
+    Ok(transaction)
+}
+
+/// Waits for `request` to succeed and returns its result.
+async fn result(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let done = js_sys::Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    JsFuture::from(done).await?;
+    request.result()
+}
+
+/// Waits for `transaction` to commit.
+async fn completion(transaction: &IdbTransaction) -> Result<(), JsValue> {
+    let done = js_sys::Promise::new(&mut |resolve, reject| {
+        transaction.set_oncomplete(Some(&resolve));
+        transaction.set_onabort(Some(&reject));
+    });
+    JsFuture::from(done).await.map(|_| ())
+}
+
+fn describe(error: JsValue) -> String {
+    error
+        .dyn_ref::<js_sys::Error>()
+        .map(|error| String::from(error.message()))
+        .or_else(|| error.as_string())
+        .unwrap_or_else(|| format!("{:?}", error))
+}
@@ -1,5 +1,12 @@
 use egui::*;
-use egui_plot::{Line, Plot, PlotPoints};
+use egui_plot::{Bar, BarChart, Line, Plot, PlotPoint, PlotPoints, PlotUi};
+use crate::task::WeeklySummary;
+use crate::utils::{lerp, normalize_to_range, percentage_change};
+
+const WEEKDAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Width, in hours, of each bar of the completion time histogram
+pub const COMPLETION_HISTOGRAM_BUCKET_HOURS: f64 = 4.0;
 
 #[derive(Default)]
 pub struct KpiApp {
@@ -72,6 +79,12 @@ impl KpiApp {
         let total_tasks = 45;
         let completed_tasks = 32;
         let avg_completion_time = 2.3;
+        // The weekly trend compares the tasks completed in each of the last
+        // two weeks
+        let now = chrono::Utc::now();
+        let (this_week, last_week) = crate::app::store::with_tasks(|manager| {
+            (manager.weekly_summary_at(now), manager.weekly_summary_at(now - chrono::Duration::weeks(1)))
+        });
         let completion_rate = (completed_tasks as f32 / total_tasks as f32 * 100.0) as u32;
         
         ui.columns(4, |columns| {
@@ -99,7 +112,10 @@ impl KpiApp {
         ui.add_space(20.0);
         ui.label("📈 Quick insights:");
         ui.label("• Task completion is trending upward");
-        ui.label("• Average completion time has improved by 15% this week");
+        if let Some(change) = completion_time_change(&last_week, &this_week) {
+            let trend = if change <= 0.0 { "improved" } else { "worsened" };
+            ui.label(format!("• Average completion time has {} by {:.0}% this week", trend, change.abs()));
+        }
         ui.label("• Most productive hours are between 10 AM - 2 PM");
     }
     
@@ -118,6 +134,7 @@ impl KpiApp {
         Plot::new("task_creation_plot")
             .height(300.0)
             .show(ui, |plot_ui| {
+                show_point_tooltip(plot_ui, &points, "tasks");
                 plot_ui.line(
                     Line::new(PlotPoints::from(points))
                         .color(Color32::from_rgb(100, 200, 100))
@@ -144,6 +161,7 @@ impl KpiApp {
         Plot::new("completion_time_plot")
             .height(300.0)
             .show(ui, |plot_ui| {
+                show_point_tooltip(plot_ui, &points, "hours");
                 plot_ui.line(
                     Line::new(PlotPoints::from(points))
                         .color(Color32::from_rgb(200, 100, 100))
@@ -153,6 +171,15 @@ impl KpiApp {
             
         ui.add_space(10.0);
         ui.label("⏱️ Average time to complete tasks over the last 20 completed tasks");
+        
+        ui.add_space(20.0);
+        ui.heading("Completion Time Distribution");
+        ui.add_space(10.0);
+        
+        let histogram = crate::app::store::with_tasks(|manager| {
+            manager.get_completion_histogram(COMPLETION_HISTOGRAM_BUCKET_HOURS)
+        });
+        show_completion_histogram_chart(ui, &histogram, COMPLETION_HISTOGRAM_BUCKET_HOURS);
     }
     
     fn show_productivity_chart(&mut self, ui: &mut egui::Ui) {
@@ -175,6 +202,8 @@ impl KpiApp {
         Plot::new("productivity_plot")
             .height(300.0)
             .show(ui, |plot_ui| {
+                let all_points = [completed_points.as_slice(), created_points.as_slice()].concat();
+                show_point_tooltip(plot_ui, &all_points, "tasks");
                 plot_ui.line(
                     Line::new(PlotPoints::from(completed_points))
                         .color(Color32::from_rgb(100, 200, 100))
@@ -190,5 +219,161 @@ impl KpiApp {
             
         ui.add_space(10.0);
         ui.label("🚀 Daily productivity: tasks created vs completed over the last 2 weeks");
+        
+        ui.add_space(20.0);
+        ui.heading("Task Creation by Weekday");
+        ui.add_space(10.0);
+        
+        let counts = crate::app::store::with_tasks(|manager| manager.get_tasks_created_by_day_of_week());
+        show_day_of_week_heatmap(ui, &counts);
+    }
+}
+
+/// The point in `points` nearest to `cursor` by Euclidean distance, or `None`
+/// if there are no points.
+pub fn closest_point(cursor: PlotPoint, points: &[[f64; 2]]) -> Option<[f64; 2]> {
+    let distance = |[x, y]: [f64; 2]| (x - cursor.x).hypot(y - cursor.y);
+    points
+        .iter()
+        .copied()
+        .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+}
+
+/// While the plot is hovered, shows `Day N: X <unit>` next to the cursor for
+/// the data point closest to it. Days are numbered from 1 at `x = 0`.
+pub fn show_point_tooltip(plot_ui: &PlotUi, points: &[[f64; 2]], unit: &str) {
+    if !plot_ui.response().hovered() {
+        return;
+    }
+    let Some([x, y]) = plot_ui.pointer_coordinate().and_then(|cursor| closest_point(cursor, points)) else {
+        return;
+    };
+    
+    let value = if y.fract() == 0.0 { format!("{:.0}", y) } else { format!("{:.1}", y) };
+    let response = plot_ui.response();
+    egui::show_tooltip(plot_ui.ctx(), response.layer_id, response.id.with("point_tooltip"), |ui| {
+        ui.label(format!("Day {}: {} {}", x as i64 + 1, value, unit));
+    });
+}
+
+/// Reads the `[{"day": "Mon", "count": N}, ...]` payload produced by
+/// `get_creation_heatmap_json` back into Monday-first counts.
+pub fn parse_heatmap_counts(json: &str) -> [usize; 7] {
+    let mut counts = [0; 7];
+    if let Ok(days) = serde_json::from_str::<Vec<serde_json::Value>>(json) {
+        for (slot, day) in counts.iter_mut().zip(days.iter()) {
+            *slot = day["count"].as_u64().unwrap_or(0) as usize;
+        }
     }
-}
\ No newline at end of file
+    counts
+}
+
+/// Renders one square per weekday (Monday first) whose color intensity is
+/// proportional to that day's count relative to the busiest day.
+pub fn show_day_of_week_heatmap(ui: &mut egui::Ui, counts: &[usize; 7]) {
+    let max = counts.iter().copied().max().unwrap_or(0);
+    let cell_size = vec2(40.0, 40.0);
+    
+    ui.horizontal(|ui| {
+        for (label, count) in WEEKDAY_LABELS.iter().zip(counts.iter()) {
+            ui.vertical(|ui| {
+                let (rect, response) = ui.allocate_exact_size(cell_size, Sense::hover());
+                // No tasks at all is an empty range, shown as the lightest color
+                let intensity = normalize_to_range(*count as f64, 0.0, max as f64).unwrap_or(0.0);
+                let channel = |from: u8, to: u8| lerp(from as f64, to as f64, intensity).round() as u8;
+                let fill = Color32::from_rgb(channel(235, 40), channel(235, 160), channel(235, 70));
+                
+                ui.painter().rect(rect, 4.0, fill, Stroke::new(1.0, Color32::from_gray(160)));
+                ui.painter().text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    count.to_string(),
+                    FontId::proportional(14.0),
+                    if intensity > 0.5 { Color32::WHITE } else { Color32::BLACK },
+                );
+                response.on_hover_text(format!("{}: {} tasks", label, count));
+                ui.label(*label);
+            });
+        }
+    });
+}
+
+/// Percentage change of the average completion time from `last_week` to
+/// `this_week`, or `None` unless both weeks have completed tasks and last
+/// week's average is above zero
+fn completion_time_change(last_week: &WeeklySummary, this_week: &WeeklySummary) -> Option<f64> {
+    if last_week.completed == 0 || this_week.completed == 0 {
+        return None;
+    }
+    percentage_change(last_week.avg_completion_hours, this_week.avg_completion_hours).ok()
+}
+
+/// Renders `(bucket_start_hours, count)` pairs from
+/// `get_completion_histogram` as touching bars, one per bucket of
+/// `bucket_hours`. Hovering a bar shows its range and count; the last bar of
+/// a histogram cut off at `MAX_HISTOGRAM_BUCKETS` shows it has no upper end.
+pub fn show_completion_histogram_chart(ui: &mut egui::Ui, histogram: &[(f64, usize)], bucket_hours: f64) {
+    if histogram.is_empty() {
+        ui.label("No completed tasks yet.");
+        return;
+    }
+    let overflow = histogram.len() == crate::task::MAX_HISTOGRAM_BUCKETS;
+    let bars = histogram
+        .iter()
+        .enumerate()
+        .map(|(i, &(start, count))| {
+            let range = if overflow && i == histogram.len() - 1 {
+                format!("{}+ h", start)
+            } else {
+                format!("{}–{} h", start, start + bucket_hours)
+            };
+            // Bars are placed by their center
+            Bar::new(start + bucket_hours / 2.0, count as f64)
+                .width(bucket_hours)
+                .name(range)
+        })
+        .collect();
+    
+    Plot::new("completion_histogram_plot")
+        .height(250.0)
+        .x_axis_label("Hours to complete")
+        .y_axis_label("Tasks")
+        .show(ui, |plot_ui| {
+            plot_ui.bar_chart(
+                BarChart::new(bars)
+                    .color(Color32::from_rgb(100, 150, 220))
+                    .name("Completed tasks")
+            );
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_closest_point() {
+        let points = [[0.0, 1.0], [1.0, 4.0], [2.0, 2.0], [3.0, 2.5]];
+        assert_eq!(closest_point(PlotPoint::new(1.9, 2.2), &points), Some([2.0, 2.0]));
+        assert_eq!(closest_point(PlotPoint::new(0.6, 3.5), &points), Some([1.0, 4.0]));
+        // Far outside the data, the nearest end wins
+        assert_eq!(closest_point(PlotPoint::new(-10.0, 0.0), &points), Some([0.0, 1.0]));
+        assert_eq!(closest_point(PlotPoint::new(0.0, 0.0), &[]), None);
+    }
+    
+    #[test]
+    fn test_completion_time_change() {
+        let week = |completed, avg_completion_hours| WeeklySummary {
+            created: 0,
+            completed,
+            removed: 0,
+            completion_rate: 0.0,
+            avg_completion_hours,
+        };
+        assert_eq!(completion_time_change(&week(4, 2.0), &week(2, 1.5)), Some(-25.0));
+        assert_eq!(completion_time_change(&week(1, 2.0), &week(3, 3.0)), Some(50.0));
+        assert_eq!(completion_time_change(&week(0, 0.0), &week(2, 1.5)), None);
+        assert_eq!(completion_time_change(&week(2, 1.5), &week(0, 0.0)), None);
+        assert_eq!(completion_time_change(&week(1, 0.0), &week(1, 1.0)), None);
+    }
+}
@@ -1,22 +1,45 @@
 pub mod task;
+pub mod build_info;
+pub mod config;
+pub mod export;
+#[cfg(feature = "indexeddb")]
+pub mod indexed_db;
+pub mod storage;
+#[cfg(feature = "web")]
 pub mod wasm;
+#[cfg(feature = "web")]
+pub mod wasm_utils;
 pub mod user_manager;
 pub mod utils;
+#[cfg(feature = "gui")]
 pub mod app;
+#[cfg(feature = "gui")]
 pub mod kpi_app;
 
-pub use task::{Task, TaskManager};
-pub use user_manager::{User, UserManager};
+pub use build_info::BuildInfo;
+pub use config::AppConfig;
+pub use export::ExportFormat;
+pub use task::{
+    DailyCount, ImportSummary, MergeStrategy, MergeSummary, OldestTask, Priority, Recurrence, TagCount, Task,
+    TaskFilter, TaskId, TaskManager, TaskMergeResult, TaskOrder, TaskReport, TaskSpec, TaskSummary, ToggleResult,
+    WeeklySummary,
+};
+pub use user_manager::{CsvExportOptions, CsvImport, User, UserFilter, UserManager};
+#[cfg(feature = "concurrent")]
+pub use user_manager::SharedUserManager;
 pub use utils::{
     calculate_fibonacci, 
     calculate_fibonacci_recursive, 
     validate_email, 
+    validate_email_detailed, 
     is_prime, 
     factorial, 
     gcd, 
     lcm, 
     reverse_string, 
+    reverse_string_grapheme_aware, 
     is_palindrome, 
+    is_palindrome_unicode, 
     count_words
 };
 
@@ -48,7 +71,7 @@ mod tests {
     #[test]
     fn test_task_manager_integration() {
         let mut task_manager = TaskManager::new();
-        let id = task_manager.add_task("Test Task".to_string(), "Test Description".to_string());
+        let id = task_manager.add_task("Test Task".to_string(), "Test Description".to_string()).unwrap();
         
         assert_eq!(task_manager.get_total_count(), 1);
         assert_eq!(task_manager.get_completed_count(), 0);
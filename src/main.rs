@@ -1,56 +1,593 @@
-use clap::{Arg, Command};
-use gh_actions::{User, UserManager, calculate_fibonacci, validate_email};
+mod cli;
+
+use chrono::{DateTime, Duration, Utc};
+use clap::{value_parser, Arg, ArgAction, ArgGroup, ArgMatches, Command};
+use cli::batch;
+use cli::config::{self, Config, EffectiveConfig, OutputFormat};
+use cli::confirm::Confirmation;
+use cli::due::{self, DueTask};
+use cli::error::CliError;
+use cli::logging;
+use cli::listing::{self, Column, DEFAULT_TASK_COLUMNS, DEFAULT_USER_COLUMNS, TASK_COLUMNS, USER_COLUMNS};
+use cli::search;
+use cli::stats;
+use cli::style::{self, Style};
+use gh_actions::utils::{
+    calculate_fibonacci_u128, count_words, is_palindrome, is_strict_palindrome, reverse_string, slugify,
+    FibonacciIter, MAX_FIBONACCI_INDEX,
+};
+use gh_actions::{BuildInfo, ExportFormat, MergeStrategy, TaskManager, User, UserFilter, UserManager, calculate_fibonacci, validate_email, validate_email_detailed};
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// The crate version followed by the commit it was built from, e.g. `0.1.0 (abc1234)`.
+const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), " (", env!("GH_ACTIONS_GIT_HASH"), ")");
 
 fn main() {
     let matches = Command::new("gh_actions")
-        .version("0.1.0")
+        .version(VERSION)
         .author("DScudeler")
         .about("A sample Rust application for testing GitHub Actions")
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .global(true)
+                .value_name("PATH")
+                .value_parser(value_parser!(PathBuf))
+                .help("Config file to use instead of ~/.config/gh_actions/config.toml"),
+        )
+        .arg(
+            Arg::new("data-dir")
+                .long("data-dir")
+                .global(true)
+                .value_name("DIR")
+                .value_parser(value_parser!(PathBuf))
+                .help("Directory holding the data files"),
+        )
+        .arg(
+            Arg::new("file")
+                .long("file")
+                .global(true)
+                .value_name("PATH")
+                .value_parser(value_parser!(PathBuf))
+                .help("Tasks file to use instead of tasks.json in the data directory"),
+        )
+        .arg(
+            Arg::new("users-file")
+                .long("users-file")
+                .global(true)
+                .value_name("PATH")
+                .value_parser(value_parser!(PathBuf))
+                .help("Users file to use instead of users.json in the data directory"),
+        )
+        .arg(
+            Arg::new("output-format")
+                .long("output-format")
+                .global(true)
+                .value_name("FORMAT")
+                .value_parser(OutputFormat::parse)
+                .help("Output format: text or json"),
+        )
+        .arg(
+            Arg::new("tz-offset")
+                .long("tz-offset")
+                .global(true)
+                .value_name("OFFSET")
+                .value_parser(|s: &str| config::parse_utc_offset(s).map(|_| s.to_string()))
+                .help("UTC offset for task statistics, e.g. +02:00"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .global(true)
+                .action(ArgAction::Count)
+                .help("Log what the command does; repeat (-vv) for more detail"),
+        )
+        .arg(
+            Arg::new("quiet")
+                .short('q')
+                .long("quiet")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Only print errors and the command's output"),
+        )
+        .arg(
+            Arg::new("no-color")
+                .long("no-color")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Disable colored output (also honors NO_COLOR)"),
+        )
         .subcommand(
             Command::new("user")
                 .about("User management operations")
                 .subcommand(
                     Command::new("add")
                         .about("Add a new user")
+                        .arg(Arg::new("id").required_unless_present("batch").help("User ID"))
+                        .arg(Arg::new("name").required_unless_present("batch").help("User name"))
+                        .arg(Arg::new("email").required_unless_present("batch").help("User email"))
+                        .arg(
+                            Arg::new("batch")
+                                .long("batch")
+                                .action(ArgAction::SetTrue)
+                                .conflicts_with_all(["id", "name", "email"])
+                                .help("Read `name,email` lines from stdin, assigning IDs automatically"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("rm")
+                        .about("Delete a user")
                         .arg(Arg::new("id").required(true).help("User ID"))
-                        .arg(Arg::new("name").required(true).help("User name"))
-                        .arg(Arg::new("email").required(true).help("User email")),
+                        .arg(yes_arg()),
                 )
-                .subcommand(Command::new("list").about("List all users")),
+                .subcommand(
+                    Command::new("list")
+                        .about("List users, optionally filtered")
+                        .arg(
+                            Arg::new("active")
+                                .long("active")
+                                .action(ArgAction::SetTrue)
+                                .conflicts_with("inactive")
+                                .help("Only show active users"),
+                        )
+                        .arg(
+                            Arg::new("inactive")
+                                .long("inactive")
+                                .action(ArgAction::SetTrue)
+                                .help("Only show inactive users"),
+                        )
+                        .arg(
+                            Arg::new("domain")
+                                .long("domain")
+                                .value_name("DOMAIN")
+                                .help("Only show users with an email at this domain, e.g. example.com"),
+                        )
+                        .arg(
+                            Arg::new("limit")
+                                .long("limit")
+                                .value_name("N")
+                                .value_parser(value_parser!(usize))
+                                .help("Show at most N users, after sorting"),
+                        )
+                        .arg(
+                            Arg::new("columns")
+                                .long("columns")
+                                .value_name("LIST")
+                                .help("Comma-separated columns to show (id, name, email, active)"),
+                        )
+                        .arg(
+                            Arg::new("sort")
+                                .long("sort")
+                                .value_name("COLUMN")
+                                .help("Column to order rows by"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("task")
+                .about("Task management operations")
+                .subcommand(
+                    Command::new("add")
+                        .about("Add a new task")
+                        .arg(Arg::new("title").required_unless_present("batch").help("Task title"))
+                        .arg(Arg::new("description").default_value("").help("Task description"))
+                        .arg(
+                            Arg::new("batch")
+                                .long("batch")
+                                .action(ArgAction::SetTrue)
+                                .conflicts_with("title")
+                                .help("Read `title|description` lines from stdin"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("list")
+                        .about("List all tasks")
+                        .arg(
+                            Arg::new("columns")
+                                .long("columns")
+                                .value_name("LIST")
+                                .help("Comma-separated columns to show (id, done, title, description, created, completed, due)"),
+                        )
+                        .arg(
+                            Arg::new("sort")
+                                .long("sort")
+                                .value_name("COLUMN")
+                                .help("Column to order rows by"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("rm")
+                        .about("Delete a task")
+                        .arg(Arg::new("id").required(true).help("Task ID"))
+                        .arg(yes_arg()),
+                )
+                .subcommand(
+                    Command::new("clear-completed")
+                        .about("Delete all completed tasks")
+                        .arg(yes_arg())
+                        .arg(dry_run_arg()),
+                )
+                .subcommand(
+                    Command::new("toggle")
+                        .about("Toggle a task between completed and not completed")
+                        .arg(Arg::new("id").required(true).help("Task ID")),
+                )
+                .subcommand(
+                    Command::new("stats")
+                        .about("Show task completion statistics")
+                        .arg(
+                            Arg::new("days")
+                                .long("days")
+                                .value_name("N")
+                                .default_value("30")
+                                .value_parser(value_parser!(u32).range(1..))
+                                .help("Number of days, ending today, for velocity and the daily chart"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("due")
+                        .about("List incomplete tasks with a due date, soonest first")
+                        .arg(
+                            Arg::new("within")
+                                .long("within")
+                                .value_name("DURATION")
+                                .value_parser(due::parse_window)
+                                .conflicts_with("overdue")
+                                .help("Only tasks due before now + DURATION (e.g. 30m, 48h, 3d, 1w), including overdue ones"),
+                        )
+                        .arg(
+                            Arg::new("overdue")
+                                .long("overdue")
+                                .action(ArgAction::SetTrue)
+                                .help("Only tasks past their due date"),
+                        )
+                        .arg(
+                            Arg::new("check")
+                                .long("check")
+                                .action(ArgAction::SetTrue)
+                                .help("Exit with status 1 if any task is overdue"),
+                        )
+                        .arg(
+                            Arg::new("now")
+                                .long("now")
+                                .value_name("RFC3339")
+                                .hide(true)
+                                .value_parser(due::parse_timestamp),
+                        ),
+                )
+                .subcommand(
+                    Command::new("export")
+                        .about("Write all tasks to a JSON, CSV or Markdown file")
+                        .arg(
+                            Arg::new("path")
+                                .required(true)
+                                .value_parser(value_parser!(PathBuf))
+                                .help("File to write"),
+                        )
+                        .arg(file_format_arg()),
+                )
+                .subcommand(
+                    Command::new("import")
+                        .about("Add tasks from a JSON or CSV export")
+                        .arg(
+                            Arg::new("path")
+                                .required(true)
+                                .value_parser(value_parser!(PathBuf))
+                                .help("File to read"),
+                        )
+                        .arg(file_format_arg())
+                        .arg(
+                            Arg::new("merge")
+                                .long("merge")
+                                .value_name("STRATEGY")
+                                .value_parser(MergeStrategy::parse)
+                                .help("How to resolve clashing IDs: keep-newest or keep-both (default: refuse)"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("search")
+                .about("Search tasks and users")
+                .arg(Arg::new("query").required(true).help("Text to look for, ignoring case"))
+                .arg(
+                    Arg::new("tasks")
+                        .long("tasks")
+                        .action(ArgAction::SetTrue)
+                        .help("Search tasks (default: tasks and users)"),
+                )
+                .arg(
+                    Arg::new("users")
+                        .long("users")
+                        .action(ArgAction::SetTrue)
+                        .help("Search users (default: tasks and users)"),
+                ),
         )
         .subcommand(
             Command::new("fib")
                 .about("Calculate Fibonacci number")
-                .arg(Arg::new("number").required(true).help("Number to calculate")),
+                .arg(Arg::new("number").help("Number to calculate"))
+                .arg(
+                    Arg::new("upto")
+                        .long("upto")
+                        .value_name("N")
+                        .help("Print F(0) through F(N)"),
+                )
+                .arg(
+                    Arg::new("between")
+                        .long("between")
+                        .num_args(2)
+                        .value_names(["A", "B"])
+                        .help("Print F(A) through F(B)"),
+                )
+                .group(
+                    ArgGroup::new("mode")
+                        .args(["number", "upto", "between"])
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("inline")
+                        .long("inline")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("number")
+                        .help("Print a sequence on one line, comma-separated"),
+                ),
+        )
+        .subcommand(
+            Command::new("email")
+                .about("Email address tools")
+                .subcommand(
+                    Command::new("validate")
+                        .about("Check addresses and explain why invalid ones are rejected")
+                        .arg(Arg::new("address").required_unless_present("batch").help("Address to check"))
+                        .arg(
+                            Arg::new("batch")
+                                .long("batch")
+                                .action(ArgAction::SetTrue)
+                                .conflicts_with("address")
+                                .help("Read one address per line from stdin"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("string")
+                .about("Transform or inspect text")
+                .subcommand(
+                    Command::new("reverse")
+                        .about("Reverse the text")
+                        .arg(text_arg()),
+                )
+                .subcommand(
+                    Command::new("palindrome")
+                        .about("Print yes and exit 0 if the text is a palindrome, otherwise no and exit 1")
+                        .arg(text_arg())
+                        .arg(
+                            Arg::new("strict")
+                                .long("strict")
+                                .action(ArgAction::SetTrue)
+                                .help("Compare every character, including case, spaces and punctuation"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("words")
+                        .about("Count the words in the text")
+                        .arg(text_arg()),
+                )
+                .subcommand(
+                    Command::new("slug")
+                        .about("Turn the text into a lowercase, hyphenated slug")
+                        .arg(text_arg()),
+                ),
+        )
+        .subcommand(
+            Command::new("purge")
+                .about("Delete all tasks and users")
+                .arg(yes_arg())
+                .arg(dry_run_arg()),
+        )
+        .subcommand(Command::new("version").about("Show the version, commit, build time, target and enabled features"))
+        .subcommand(
+            Command::new("config")
+                .about("Inspect or create the config file")
+                .subcommand(Command::new("show").about("Print the effective config and where each value came from"))
+                .subcommand(
+                    Command::new("init")
+                        .about("Write a commented config template")
+                        .arg(
+                            Arg::new("force")
+                                .long("force")
+                                .action(ArgAction::SetTrue)
+                                .help("Overwrite an existing config file"),
+                        ),
+                ),
         )
         .get_matches();
 
+    style::init(matches.get_flag("no-color"));
+    logging::init(logging::level_for(matches.get_count("verbose"), matches.get_flag("quiet")));
+
+    // The single place where errors are reported and turned into exit codes.
+    if let Err(e) = run(&matches) {
+        if e != CliError::CheckFailed {
+            eprintln!("{} {}", style::stderr().error("Error:"), e);
+        }
+        std::process::exit(e.exit_code());
+    }
+}
+
+fn run(matches: &ArgMatches) -> Result<(), CliError> {
+    // `config init` must work even when the existing file is broken.
+    if let Some(("config", config_matches)) = matches.subcommand() {
+        if let Some(("init", init_matches)) = config_matches.subcommand() {
+            return handle_config_init(matches, init_matches);
+        }
+    }
+
+    let config = load_config(matches)?;
+
     match matches.subcommand() {
-        Some(("user", user_matches)) => handle_user_command(user_matches),
-        Some(("fib", fib_matches)) => handle_fib_command(fib_matches),
+        Some(("user", user_matches)) => handle_user_command(user_matches, &config),
+        Some(("task", task_matches)) => handle_task_command(task_matches, &config),
+        Some(("search", search_matches)) => handle_search_command(search_matches, &config),
+        Some(("fib", fib_matches)) => handle_fib_command(fib_matches, &config),
+        Some(("purge", purge_matches)) => handle_purge_command(purge_matches, &config),
+        Some(("email", email_matches)) => handle_email_command(email_matches, &config),
+        Some(("string", string_matches)) => handle_string_command(string_matches),
+        Some(("config", config_matches)) => handle_config_command(config_matches, &config),
+        Some(("version", _)) => handle_version_command(&config),
         _ => {
             println!("Welcome to gh_actions!");
             println!("Use --help to see available commands.");
+            Ok(())
         }
     }
 }
 
-fn handle_user_command(matches: &clap::ArgMatches) {
-    let mut user_manager = UserManager::new();
-    
+/// Resolves the effective config: explicit flags win over the config file,
+/// which wins over the built-in defaults.
+fn load_config(matches: &ArgMatches) -> Result<EffectiveConfig, CliError> {
+    let explicit_path = matches.get_one::<PathBuf>("config").cloned();
+    let path = explicit_path.clone().unwrap_or_else(config::default_config_path);
+
+    let file = Config::load(&path).map_err(CliError::InvalidInput)?;
+    log::debug!(
+        "config file {} {}",
+        path.display(),
+        if file.is_some() { "loaded" } else { "not found, using defaults" }
+    );
+    if explicit_path.is_some() && file.is_none() {
+        return Err(CliError::NotFound(format!("config file {} not found", path.display())));
+    }
+
+    let flags = Config {
+        data_dir: matches.get_one::<PathBuf>("data-dir").cloned(),
+        output_format: matches.get_one::<OutputFormat>("output-format").copied(),
+        timezone_offset: matches.get_one::<String>("tz-offset").cloned(),
+    };
+
+    // Checked here rather than with clap's `conflicts_with`, which misses
+    // global flags given at different subcommand levels.
+    if flags.data_dir.is_some() {
+        for flag in ["file", "users-file"] {
+            if matches.contains_id(flag) {
+                return Err(CliError::InvalidInput(format!(
+                    "--{} cannot be used with --data-dir",
+                    flag
+                )));
+            }
+        }
+    }
+
+    let mut effective =
+        EffectiveConfig::resolve(path, file, flags).map_err(CliError::InvalidInput)?;
+    effective.tasks_path = matches.get_one::<PathBuf>("file").cloned();
+    effective.users_path = matches.get_one::<PathBuf>("users-file").cloned();
+    Ok(effective)
+}
+
+fn load_users(config: &EffectiveConfig) -> Result<UserManager, CliError> {
+    let path = config.users_file();
+    log::info!("loading users from {}", path.display());
+    let mut manager = UserManager::new();
+    if path.exists() {
+        manager
+            .load_from_file(&path.to_string_lossy())
+            .map_err(|e| CliError::Io(format!("Failed to load {}: {}", path.display(), e)))?;
+    } else {
+        log::debug!("{} does not exist, starting with no users", path.display());
+    }
+    Ok(manager)
+}
+
+fn save_users(config: &EffectiveConfig, manager: &UserManager) -> Result<(), CliError> {
+    let path = config.users_file();
+    log::info!("saving {} users to {}", manager.count(), path.display());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| CliError::Io(format!("Failed to create {}: {}", parent.display(), e)))?;
+    }
+    manager
+        .save_to_file(&path.to_string_lossy())
+        .map_err(|e| CliError::Io(format!("Failed to save {}: {}", path.display(), e)))
+}
+
+fn load_tasks(config: &EffectiveConfig) -> Result<TaskManager, CliError> {
+    let path = config.tasks_file();
+    log::info!("loading tasks from {}", path.display());
+    let mut manager = TaskManager::new();
+    if path.exists() {
+        manager
+            .load_from_file(&path.to_string_lossy())
+            .map_err(|e| CliError::Io(format!("Failed to load {}: {}", path.display(), e)))?;
+    } else {
+        log::debug!("{} does not exist, starting with no tasks", path.display());
+    }
+    Ok(manager)
+}
+
+fn save_tasks(config: &EffectiveConfig, manager: &TaskManager) -> Result<(), CliError> {
+    let path = config.tasks_file();
+    log::info!("saving {} tasks to {}", manager.get_total_count(), path.display());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| CliError::Io(format!("Failed to create {}: {}", parent.display(), e)))?;
+    }
+    manager
+        .save_to_file(&path.to_string_lossy())
+        .map_err(|e| CliError::Io(format!("Failed to save {}: {}", path.display(), e)))
+}
+
+/// Whether a user already has `email`, ignoring case
+fn email_taken(user_manager: &UserManager, email: &str) -> bool {
+    user_manager.get_users().iter().any(|user| user.email.eq_ignore_ascii_case(email))
+}
+
+fn handle_user_command(matches: &ArgMatches, config: &EffectiveConfig) -> Result<(), CliError> {
+    let mut user_manager = load_users(config)?;
+    let out = style::stdout();
+
     match matches.subcommand() {
-        Some(("add", add_matches)) => {
-            let id: u32 = add_matches.get_one::<String>("id").unwrap().parse().unwrap_or_else(|_| {
-                eprintln!("Error: Invalid user ID");
-                std::process::exit(1);
+        Some(("add", add_matches)) if add_matches.get_flag("batch") => {
+            let mut next_id = user_manager.get_users().iter().map(|u| u.id).max().unwrap_or(0) + 1;
+            let (added, failed) = run_batch(|line| {
+                let (name, email) = batch::parse_user_line(line)?;
+                if !validate_email(&email) {
+                    return Err("Invalid email format".to_string());
+                }
+                if email_taken(&user_manager, &email) {
+                    return Err(format!("User with email {} already exists", email));
+                }
+                user_manager.add_user(User { id: next_id, name, email, active: true })?;
+                next_id += 1;
+                Ok(())
             });
+            if added > 0 {
+                save_users(config, &user_manager)?;
+            }
+            println!("{}", out.success(&format!("Added {} users", added)));
+            batch_result(failed, added + failed)?;
+        }
+        Some(("add", add_matches)) => {
+            let id: u32 = add_matches
+                .get_one::<String>("id")
+                .unwrap()
+                .parse()
+                .map_err(|_| CliError::InvalidInput("Invalid user ID".to_string()))?;
             
             let name = add_matches.get_one::<String>("name").unwrap().clone();
             let email = add_matches.get_one::<String>("email").unwrap().clone();
             
             if !validate_email(&email) {
-                eprintln!("Error: Invalid email format");
-                std::process::exit(1);
+                return Err(CliError::Validation("Invalid email format".to_string()));
+            }
+            if user_manager.get_user(id).is_some() {
+                return Err(CliError::Conflict(format!("User with ID {} already exists", id)));
+            }
+            if email_taken(&user_manager, &email) {
+                return Err(CliError::Conflict(format!("User with email {} already exists", email)));
             }
             
             let user = User {
@@ -60,39 +597,691 @@ fn handle_user_command(matches: &clap::ArgMatches) {
                 active: true,
             };
             
-            match user_manager.add_user(user) {
-                Ok(_) => println!("User added successfully!"),
-                Err(e) => {
-                    eprintln!("Error: {}", e);
-                    std::process::exit(1);
+            user_manager.add_user(user).map_err(CliError::Validation)?;
+            save_users(config, &user_manager)?;
+            println!("{}", out.success("User added successfully!"));
+        }
+        Some(("list", list_matches)) => {
+            let active = match (list_matches.get_flag("active"), list_matches.get_flag("inactive")) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            let filter = UserFilter {
+                active,
+                domain: list_matches.get_one::<String>("domain").cloned(),
+            };
+            let mut users = user_manager.filter(&filter);
+            if let Some(column) = list_matches.get_one::<String>("sort") {
+                listing::sort_by_column(&mut users, column, USER_COLUMNS).map_err(CliError::InvalidInput)?;
+            }
+            if let Some(&limit) = list_matches.get_one::<usize>("limit") {
+                users.truncate(limit);
+            }
+
+            if config.output_format.value == OutputFormat::Json {
+                return print_json(&users);
+            }
+
+            let columns = listing::select_columns(
+                list_matches.get_one::<String>("columns").map(String::as_str),
+                USER_COLUMNS,
+                DEFAULT_USER_COLUMNS,
+            )
+            .map_err(CliError::InvalidInput)?;
+
+            println!("{}", out.heading("Users:"));
+            if users.is_empty() {
+                println!("No users found.");
+            } else {
+                print_table(&out, &users, &columns, |user, line| {
+                    if user.active { line.to_string() } else { out.dim(line) }
+                });
+            }
+            if user_manager.count() == 0 {
+                return Ok(());
+            }
+            println!("{}", out.dim(&format!("showing {} of {} users", users.len(), user_manager.count())));
+        }
+        Some(("rm", rm_matches)) => {
+            let id: u32 = rm_matches
+                .get_one::<String>("id")
+                .unwrap()
+                .parse()
+                .map_err(|_| CliError::InvalidInput("Invalid user ID".to_string()))?;
+            let user = user_manager
+                .get_user(id)
+                .ok_or_else(|| CliError::NotFound(format!("User with ID {} not found", id)))?;
+
+            Confirmation::from_terminal(rm_matches.get_flag("yes"))
+                .confirm(&format!("Delete user {} '{}'?", id, user.name))?;
+            user_manager.delete_user(id).map_err(CliError::NotFound)?;
+            save_users(config, &user_manager)?;
+            println!("{}", out.success(&format!("User {} deleted", id)));
+        }
+        _ => {
+            println!("Use 'user --help' to see available user commands.");
+        }
+    }
+    Ok(())
+}
+
+fn handle_task_command(matches: &ArgMatches, config: &EffectiveConfig) -> Result<(), CliError> {
+    let mut task_manager = load_tasks(config)?;
+    let out = style::stdout();
+
+    match matches.subcommand() {
+        Some(("add", add_matches)) if add_matches.get_flag("batch") => {
+            let (added, failed) = run_batch(|line| {
+                let (title, description) = batch::parse_task_line(line);
+                if title.is_empty() {
+                    return Err("Task title cannot be empty".to_string());
+                }
+                task_manager.add_task(title, description).map(|_| ())
+            });
+            if added > 0 {
+                save_tasks(config, &task_manager)?;
+            }
+            println!("{}", out.success(&format!("Added {} tasks", added)));
+            batch_result(failed, added + failed)?;
+        }
+        Some(("add", add_matches)) => {
+            let title = add_matches.get_one::<String>("title").unwrap().clone();
+            let description = add_matches.get_one::<String>("description").unwrap().clone();
+
+            if title.trim().is_empty() {
+                return Err(CliError::Validation("Task title cannot be empty".to_string()));
+            }
+
+            let id = task_manager.add_task(title, description).map_err(CliError::Conflict)?;
+            save_tasks(config, &task_manager)?;
+            println!("{}", out.success(&format!("Task {} added successfully!", id)));
+        }
+        Some(("list", list_matches)) => {
+            let mut tasks = task_manager.get_all_tasks();
+            tasks.sort_by_key(|task| task.id);
+            if let Some(column) = list_matches.get_one::<String>("sort") {
+                listing::sort_by_column(&mut tasks, column, TASK_COLUMNS).map_err(CliError::InvalidInput)?;
+            }
+
+            if config.output_format.value == OutputFormat::Json {
+                return print_json(&tasks);
+            }
+
+            let columns = listing::select_columns(
+                list_matches.get_one::<String>("columns").map(String::as_str),
+                TASK_COLUMNS,
+                DEFAULT_TASK_COLUMNS,
+            )
+            .map_err(CliError::InvalidInput)?;
+
+            println!("{}", out.heading("Tasks:"));
+            if tasks.is_empty() {
+                println!("No tasks found.");
+                return Ok(());
+            }
+            print_table(&out, &tasks, &columns, |task, line| {
+                if task.completed { out.done(line) } else { line.to_string() }
+            });
+        }
+        Some(("toggle", toggle_matches)) => {
+            let id: u32 = toggle_matches
+                .get_one::<String>("id")
+                .unwrap()
+                .parse()
+                .map_err(|_| CliError::InvalidInput("Invalid task ID".to_string()))?;
+
+            let result = task_manager.toggle_task(id);
+            if !result.toggled {
+                return Err(CliError::NotFound(format!("Task with ID {} not found", id)));
+            }
+            save_tasks(config, &task_manager)?;
+
+            let state = if task_manager.get_task(id).is_some_and(|task| task.completed) {
+                "completed"
+            } else {
+                "not completed"
+            };
+            println!("{}", out.success(&format!("Task {} marked as {}", id, state)));
+            if let Some(next_id) = result.new_task_id {
+                println!("Next occurrence created as task {}", next_id);
+            }
+        }
+        Some(("stats", stats_matches)) => {
+            let days = *stats_matches.get_one::<u32>("days").unwrap();
+            let report = task_manager.get_report(Utc::now(), days, config.timezone_offset.value);
+
+            if config.output_format.value == OutputFormat::Json {
+                return print_json(&report);
+            }
+
+            println!("{}", out.heading(&format!("Task statistics (last {} days):", days)));
+            let lines = stats::report_lines(&report);
+            let width = lines.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+            for (label, value) in lines {
+                println!("{:<width$}  {}", format!("{}:", label), value, width = width + 1);
+            }
+        }
+        Some(("export", export_matches)) => {
+            let path = export_matches.get_one::<PathBuf>("path").unwrap();
+            let format = file_format(export_matches, path)?;
+            let mut tasks = task_manager.get_all_tasks();
+            tasks.sort_by_key(|task| task.id);
+            let content = format.write(&tasks).map_err(CliError::Io)?;
+            fs::write(path, content)
+                .map_err(|e| CliError::Io(format!("Failed to write {}: {}", path.display(), e)))?;
+            println!("{}", out.success(&format!("Exported {} tasks to {}", tasks.len(), path.display())));
+        }
+        Some(("import", import_matches)) => {
+            let path = import_matches.get_one::<PathBuf>("path").unwrap();
+            let format = file_format(import_matches, path)?;
+            let content = fs::read_to_string(path)
+                .map_err(|e| CliError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+            let tasks = format
+                .read(&content)
+                .map_err(|e| CliError::InvalidInput(format!("{}: {}", path.display(), e)))?;
+
+            let strategy = match import_matches.get_one::<MergeStrategy>("merge") {
+                Some(strategy) => *strategy,
+                None => {
+                    let clashes = task_manager.conflicting_ids(&tasks);
+                    if !clashes.is_empty() {
+                        let ids: Vec<String> = clashes.iter().map(u32::to_string).collect();
+                        return Err(CliError::Conflict(format!(
+                            "Task IDs already in use: {}; pass --merge keep-newest or --merge keep-both",
+                            ids.join(", ")
+                        )));
+                    }
+                    // Without clashes every strategy just adds the tasks
+                    MergeStrategy::KeepBoth
+                }
+            };
+
+            let summary = task_manager.merge(tasks, strategy).map_err(CliError::Conflict)?;
+            save_tasks(config, &task_manager)?;
+            println!(
+                "{}",
+                out.success(&format!(
+                    "Imported {} tasks ({} replaced, {} skipped)",
+                    summary.added + summary.replaced,
+                    summary.replaced,
+                    summary.skipped
+                ))
+            );
+        }
+        Some(("rm", rm_matches)) => {
+            let id: u32 = rm_matches
+                .get_one::<String>("id")
+                .unwrap()
+                .parse()
+                .map_err(|_| CliError::InvalidInput("Invalid task ID".to_string()))?;
+            let task = task_manager
+                .get_task(id)
+                .ok_or_else(|| CliError::NotFound(format!("Task with ID {} not found", id)))?;
+
+            Confirmation::from_terminal(rm_matches.get_flag("yes"))
+                .confirm(&format!("Delete task {} '{}'?", id, task.title))?;
+            task_manager.remove_task(id);
+            save_tasks(config, &task_manager)?;
+            println!("{}", out.success(&format!("Task {} deleted", id)));
+        }
+        Some(("clear-completed", clear_matches)) => {
+            let mut completed: Vec<_> = task_manager.get_all_tasks().into_iter().filter(|t| t.completed).collect();
+            completed.sort_by_key(|task| task.id);
+
+            if clear_matches.get_flag("dry-run") {
+                for task in &completed {
+                    println!("Would delete task {} '{}'", task.id, task.title);
                 }
+                println!("{} completed tasks would be deleted", completed.len());
+                return Ok(());
+            }
+            if completed.is_empty() {
+                println!("No completed tasks to delete.");
+                return Ok(());
             }
+
+            Confirmation::from_terminal(clear_matches.get_flag("yes"))
+                .confirm(&format!("Delete {} completed tasks?", completed.len()))?;
+            let removed = task_manager.clear_completed();
+            save_tasks(config, &task_manager)?;
+            println!("{}", out.success(&format!("Deleted {} completed tasks", removed)));
         }
-        Some(("list", _)) => {
-            println!("Users:");
-            for user in user_manager.get_users() {
-                println!("  ID: {}, Name: {}, Email: {}, Active: {}", 
-                         user.id, user.name, user.email, user.active);
+        Some(("due", due_matches)) => {
+            let now = due_matches.get_one::<DateTime<Utc>>("now").copied().unwrap_or_else(Utc::now);
+            let until = if due_matches.get_flag("overdue") {
+                Some(now)
+            } else {
+                due_matches.get_one::<Duration>("within").map(|window| now + *window)
+            };
+            let due_tasks: Vec<DueTask> = task_manager
+                .get_due_tasks(until)
+                .into_iter()
+                .filter_map(|task| DueTask::new(task, now))
+                // --overdue leaves out a task due exactly now
+                .filter(|task| !due_matches.get_flag("overdue") || task.overdue)
+                .collect();
+
+            if config.output_format.value == OutputFormat::Json {
+                print_json(&due_tasks)?;
+            } else if due_tasks.is_empty() {
+                println!("No tasks due.");
+            } else {
+                let rows: Vec<&DueTask> = due_tasks.iter().collect();
+                let columns: Vec<&Column<DueTask>> = due::DUE_COLUMNS.iter().collect();
+                print_table(&out, &rows, &columns, |task, line| {
+                    if task.overdue { out.overdue(line) } else { line.to_string() }
+                });
+            }
+
+            let any_overdue = task_manager.get_due_tasks(Some(now)).iter().any(|t| t.due_date < Some(now));
+            if due_matches.get_flag("check") && any_overdue {
+                return Err(CliError::CheckFailed);
             }
         }
         _ => {
-            println!("Use 'user --help' to see available user commands.");
+            println!("Use 'task --help' to see available task commands.");
+        }
+    }
+    Ok(())
+}
+
+fn handle_search_command(matches: &ArgMatches, config: &EffectiveConfig) -> Result<(), CliError> {
+    let query = matches.get_one::<String>("query").unwrap();
+    let (mut search_tasks, mut search_users) = (matches.get_flag("tasks"), matches.get_flag("users"));
+    if !search_tasks && !search_users {
+        (search_tasks, search_users) = (true, true);
+    }
+
+    let task_manager = if search_tasks { load_tasks(config)? } else { TaskManager::new() };
+    let user_manager = if search_users { load_users(config)? } else { UserManager::new() };
+    let tasks = task_manager.search(query);
+    let users = user_manager.search(query);
+
+    if config.output_format.value == OutputFormat::Json {
+        return print_json(&serde_json::json!({ "tasks": tasks, "users": users }));
+    }
+
+    if tasks.is_empty() && users.is_empty() {
+        println!("no matches");
+        return Ok(());
+    }
+
+    let out = style::stdout();
+    let mark = |line: &str| search::highlight(line, query, |m| out.highlight(m));
+    if !tasks.is_empty() {
+        let columns = listing::select_columns(None, TASK_COLUMNS, DEFAULT_TASK_COLUMNS)
+            .map_err(CliError::InvalidInput)?;
+        println!("{}", out.heading("Tasks:"));
+        print_table(&out, &tasks, &columns, |_, line| mark(line));
+    }
+    if !users.is_empty() {
+        let columns = listing::select_columns(None, USER_COLUMNS, DEFAULT_USER_COLUMNS)
+            .map_err(CliError::InvalidInput)?;
+        println!("{}", out.heading("Users:"));
+        print_table(&out, &users, &columns, |_, line| mark(line));
+    }
+    Ok(())
+}
+
+/// Applies `apply` to every batch line on stdin, reporting failures on
+/// stderr with their line numbers and carrying on with the next line.
+/// Returns how many lines succeeded and how many failed.
+fn run_batch(mut apply: impl FnMut(&str) -> Result<(), String>) -> (usize, usize) {
+    let err = style::stderr();
+    let (mut succeeded, mut failed) = (0, 0);
+    for (number, line) in batch::lines(io::stdin().lock()) {
+        match line.and_then(|line| apply(line.trim())) {
+            Ok(()) => succeeded += 1,
+            Err(e) => {
+                eprintln!("{} {}", err.error(&format!("line {}:", number)), e);
+                failed += 1;
+            }
+        }
+    }
+    (succeeded, failed)
+}
+
+/// Fails with exit code 2 if any batch line failed.
+fn batch_result(failed: usize, total: usize) -> Result<(), CliError> {
+    if failed == 0 {
+        Ok(())
+    } else {
+        Err(CliError::InvalidInput(format!("{} of {} lines failed", failed, total)))
+    }
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), CliError> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| CliError::Io(e.to_string()))?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Prints `items` as an aligned table, passing each data row through
+/// `row_style` so it can be colored without disturbing the column widths.
+fn print_table<T>(
+    out: &Style,
+    items: &[&T],
+    columns: &[&Column<T>],
+    row_style: impl Fn(&T, &str) -> String,
+) {
+    let table = listing::render(items, columns);
+    let mut lines = table.lines();
+    if let Some(header) = lines.next() {
+        println!("{}", out.heading(header));
+    }
+    if let Some(rule) = lines.next() {
+        println!("{}", out.dim(rule));
+    }
+    for (item, line) in items.iter().zip(lines) {
+        println!("{}", row_style(item, line));
+    }
+}
+
+fn handle_version_command(config: &EffectiveConfig) -> Result<(), CliError> {
+    let info = BuildInfo::current();
+    if config.output_format.value == OutputFormat::Json {
+        return print_json(&info);
+    }
+    println!("{}", info);
+    Ok(())
+}
+
+fn handle_fib_command(matches: &ArgMatches, config: &EffectiveConfig) -> Result<(), CliError> {
+    let parse = |value: &String| -> Result<u32, CliError> {
+        value
+            .parse()
+            .map_err(|_| CliError::InvalidInput("Invalid number".to_string()))
+    };
+
+    let (start, end) = if let Some(upto) = matches.get_one::<String>("upto") {
+        (0, parse(upto)?)
+    } else if let Some(mut bounds) = matches.get_many::<String>("between") {
+        let start = parse(bounds.next().unwrap())?;
+        let end = parse(bounds.next().unwrap())?;
+        if start > end {
+            return Err(CliError::InvalidInput(format!(
+                "Invalid range: {} is greater than {}",
+                start, end
+            )));
+        }
+        (start, end)
+    } else {
+        let number = parse(matches.get_one::<String>("number").unwrap())?;
+        if number <= MAX_FIBONACCI_INDEX {
+            let result = calculate_fibonacci(number).map_err(CliError::InvalidInput)?;
+            println!("Fibonacci of {} is: {}", number, result);
+        } else {
+            let result = calculate_fibonacci_u128(number).map_err(CliError::InvalidInput)?;
+            log::warn!("result too large for u64, using 128-bit");
+            println!("Fibonacci of {} is: {}", number, result);
         }
+        return Ok(());
+    };
+
+    if end > MAX_FIBONACCI_INDEX {
+        return Err(CliError::InvalidInput(format!(
+            "Number too large for u64: {} (maximum is {})",
+            end, MAX_FIBONACCI_INDEX
+        )));
     }
+
+    let values = FibonacciIter::new()
+        .skip(start as usize)
+        .take((end - start) as usize + 1);
+    let (open, separator, close) = match config.output_format.value {
+        OutputFormat::Json => ("[", ",", "]\n"),
+        OutputFormat::Text if matches.get_flag("inline") => ("", ", ", "\n"),
+        OutputFormat::Text => ("", "\n", "\n"),
+    };
+
+    // Write values as they are produced instead of joining them first; a
+    // closed pipe (e.g. `| head`) just ends the output.
+    let mut out = io::stdout().lock();
+    let _ = write_sequence(&mut out, values, open, separator, close);
+    Ok(())
 }
 
-fn handle_fib_command(matches: &clap::ArgMatches) {
-    let number: u32 = matches.get_one::<String>("number").unwrap().parse().unwrap_or_else(|_| {
-        eprintln!("Error: Invalid number");
-        std::process::exit(1);
-    });
-    
-    match calculate_fibonacci(number) {
-        Ok(result) => println!("Fibonacci of {} is: {}", number, result),
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+fn write_sequence(
+    out: &mut impl Write,
+    values: impl Iterator<Item = u64>,
+    open: &str,
+    separator: &str,
+    close: &str,
+) -> io::Result<()> {
+    out.write_all(open.as_bytes())?;
+    for (i, value) in values.enumerate() {
+        if i > 0 {
+            out.write_all(separator.as_bytes())?;
         }
+        write!(out, "{}", value)?;
+    }
+    out.write_all(close.as_bytes())?;
+    out.flush()
+}
+
+fn file_format_arg() -> Arg {
+    Arg::new("format")
+        .long("format")
+        .value_name("FORMAT")
+        .value_parser(value_parser!(ExportFormat))
+        .help("File format: json, csv or markdown (default: picked by the file extension)")
+}
+
+/// The `--format` given, or else the format named by the extension of `path`.
+fn file_format(matches: &ArgMatches, path: &Path) -> Result<ExportFormat, CliError> {
+    match matches.get_one::<ExportFormat>("format") {
+        Some(format) => Ok(*format),
+        None => ExportFormat::from_path(path)
+            .map_err(|e| CliError::InvalidInput(format!("{}, or pass --format", e))),
+    }
+}
+
+fn yes_arg() -> Arg {
+    Arg::new("yes")
+        .short('y')
+        .long("yes")
+        .action(ArgAction::SetTrue)
+        .help("Delete without asking for confirmation")
+}
+
+fn dry_run_arg() -> Arg {
+    Arg::new("dry-run")
+        .long("dry-run")
+        .action(ArgAction::SetTrue)
+        .help("Print what would be deleted without deleting anything")
+}
+
+fn handle_purge_command(matches: &ArgMatches, config: &EffectiveConfig) -> Result<(), CliError> {
+    let task_manager = load_tasks(config)?;
+    let mut user_manager = load_users(config)?;
+    let (tasks, users) = (task_manager.get_total_count(), user_manager.count());
+
+    if matches.get_flag("dry-run") {
+        println!("Would delete {} tasks and {} users", tasks, users);
+        return Ok(());
+    }
+
+    Confirmation::from_terminal(matches.get_flag("yes"))
+        .confirm(&format!("Delete all {} tasks and {} users?", tasks, users))?;
+    user_manager.clear();
+    save_tasks(config, &TaskManager::new())?;
+    save_users(config, &user_manager)?;
+    println!("{}", style::stdout().success(&format!("Deleted {} tasks and {} users", tasks, users)));
+    Ok(())
+}
+
+fn handle_email_command(matches: &ArgMatches, config: &EffectiveConfig) -> Result<(), CliError> {
+    let Some(("validate", validate_matches)) = matches.subcommand() else {
+        println!("Use 'email --help' to see available email commands.");
+        return Ok(());
+    };
+
+    let batch = validate_matches.get_flag("batch");
+    let addresses: Vec<String> = if batch {
+        batch::lines(io::stdin().lock())
+            .map(|(_, line)| line.map(|line| line.trim().to_string()))
+            .collect::<Result<_, _>>()
+            .map_err(|e| CliError::Io(format!("Failed to read stdin: {}", e)))?
+    } else {
+        vec![validate_matches.get_one::<String>("address").unwrap().clone()]
+    };
+    let results: Vec<(&str, Result<(), String>)> = addresses
+        .iter()
+        .map(|address| (address.as_str(), validate_email_detailed(address)))
+        .collect();
+    let valid = results.iter().filter(|(_, result)| result.is_ok()).count();
+
+    if config.output_format.value == OutputFormat::Json {
+        let entries: Vec<serde_json::Value> = results
+            .iter()
+            .map(|(email, result)| {
+                serde_json::json!({ "email": email, "valid": result.is_ok(), "reason": result.as_ref().err() })
+            })
+            .collect();
+        print_json(&entries)?;
+    } else {
+        let out = style::stdout();
+        for (email, result) in &results {
+            let verdict = match result {
+                Ok(()) => out.success("valid"),
+                Err(reason) => out.error(reason),
+            };
+            if batch {
+                println!("{}: {}", email, verdict);
+            } else {
+                println!("{}", verdict);
+            }
+        }
+        if batch {
+            println!("{} of {} addresses valid", valid, results.len());
+        }
+    }
+
+    if valid == results.len() {
+        Ok(())
+    } else {
+        Err(CliError::CheckFailed)
     }
 }
 
+fn text_arg() -> Arg {
+    Arg::new("text").help("Text to use; read from stdin when omitted")
+}
+
+/// The `text` argument, or all of stdin without its final line break.
+fn read_text(matches: &ArgMatches) -> Result<String, CliError> {
+    if let Some(text) = matches.get_one::<String>("text") {
+        return Ok(text.clone());
+    }
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .map_err(|e| CliError::Io(format!("Failed to read stdin: {}", e)))?;
+    if text.ends_with('\n') {
+        text.pop();
+        if text.ends_with('\r') {
+            text.pop();
+        }
+    }
+    Ok(text)
+}
+
+fn handle_string_command(matches: &ArgMatches) -> Result<(), CliError> {
+    match matches.subcommand() {
+        Some(("reverse", reverse_matches)) => {
+            println!("{}", reverse_string(&read_text(reverse_matches)?));
+        }
+        Some(("palindrome", palindrome_matches)) => {
+            let text = read_text(palindrome_matches)?;
+            let is_palindrome = if palindrome_matches.get_flag("strict") {
+                is_strict_palindrome(&text)
+            } else {
+                is_palindrome(&text)
+            };
+            if !is_palindrome {
+                println!("no");
+                return Err(CliError::CheckFailed);
+            }
+            println!("yes");
+        }
+        Some(("words", words_matches)) => {
+            println!("{}", count_words(&read_text(words_matches)?));
+        }
+        Some(("slug", slug_matches)) => {
+            println!("{}", slugify(&read_text(slug_matches)?));
+        }
+        _ => {
+            println!("Use 'string --help' to see available string commands.");
+        }
+    }
+    Ok(())
+}
+
+fn handle_config_command(matches: &ArgMatches, config: &EffectiveConfig) -> Result<(), CliError> {
+    match matches.subcommand() {
+        Some(("show", _)) if config.output_format.value == OutputFormat::Json => {
+            let json = serde_json::json!({
+                "config_file": config.path,
+                "file_loaded": config.file_loaded,
+                "settings": {
+                    "data_dir": {
+                        "value": config.data_dir.value,
+                        "source": config.data_dir.source.to_string(),
+                    },
+                    "output_format": {
+                        "value": config.output_format.value,
+                        "source": config.output_format.source.to_string(),
+                    },
+                    "timezone_offset": {
+                        "value": config.timezone_offset.value.to_string(),
+                        "source": config.timezone_offset.source.to_string(),
+                    },
+                },
+            });
+            print_json(&json)?;
+        }
+        Some(("show", _)) => {
+            let status = if config.file_loaded { "loaded" } else { "not found" };
+            println!("Config file: {} ({})", config.path.display(), status);
+            println!(
+                "data_dir        = {}  ({})",
+                config.data_dir.value.display(),
+                config.data_dir.source
+            );
+            println!(
+                "output_format   = {}  ({})",
+                config.output_format.value, config.output_format.source
+            );
+            println!(
+                "timezone_offset = {}  ({})",
+                config.timezone_offset.value, config.timezone_offset.source
+            );
+        }
+        _ => {
+            println!("Use 'config --help' to see available config commands.");
+        }
+    }
+    Ok(())
+}
+
+fn handle_config_init(global: &ArgMatches, matches: &ArgMatches) -> Result<(), CliError> {
+    let path = global
+        .get_one::<PathBuf>("config")
+        .cloned()
+        .unwrap_or_else(config::default_config_path);
+
+    if path.exists() && !matches.get_flag("force") {
+        return Err(CliError::Conflict(format!(
+            "config file {} already exists (use --force to overwrite)",
+            path.display()
+        )));
+    }
+
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, config::template()))
+        .map_err(|e| CliError::Io(format!("failed to write {}: {}", path.display(), e)))?;
+    println!("{}", style::stdout().success(&format!("Wrote config template to {}", path.display())));
+    Ok(())
+}
@@ -0,0 +1,315 @@
+use crate::task::{MergeStrategy, Task, TaskManager};
+use crate::utils::hex_encode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Key the web app keeps its tasks under when no namespace is given. It
+/// predates namespaces, so earlier saves are still found.
+pub const TASKS_KEY: &str = "wasm-tasks";
+
+/// Key for the tasks of `namespace`, `gh_actions:{namespace}:tasks`, so
+/// several apps on one origin keep separate lists. `None` gives [`TASKS_KEY`].
+pub fn tasks_key(namespace: Option<&str>) -> Result<String, String> {
+    match namespace {
+        None => Ok(TASKS_KEY.to_string()),
+        Some(namespace) if namespace.trim().is_empty() => Err("namespace cannot be empty".to_string()),
+        Some(namespace) => Ok(format!("gh_actions:{}:tasks", namespace)),
+    }
+}
+
+/// A string key-value store for persisting tasks in the browser.
+///
+/// Errors are plain messages: callers log them and carry on with the
+/// in-memory state, since a browser may refuse storage at any time
+/// (private browsing, quota exceeded).
+pub trait StorageBackend {
+    /// What the values are kept in, as reported to the page: `localStorage`,
+    /// `indexedDB` or `memory`
+    fn kind(&self) -> &'static str;
+    /// Returns `None` when nothing is stored under `key`.
+    fn load(&self, key: &str) -> Result<Option<String>, String>;
+    fn save(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+/// `window.localStorage`, looked up on every call so the module never needs
+/// the host page to provide anything.
+#[cfg(feature = "web")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalStorage;
+
+#[cfg(feature = "web")]
+impl LocalStorage {
+    fn storage() -> Result<web_sys::Storage, String> {
+        web_sys::window()
+            .ok_or("no window object")?
+            .local_storage()
+            .map_err(|e| format!("localStorage is not accessible: {:?}", e))?
+            .ok_or_else(|| "localStorage is not available".to_string())
+    }
+
+    /// Deletes `key`, for data that has moved to another backend.
+    pub fn remove(key: &str) -> Result<(), String> {
+        Self::storage()?
+            .remove_item(key)
+            .map_err(|e| format!("failed to remove '{}': {:?}", key, e))
+    }
+}
+
+#[cfg(feature = "web")]
+impl StorageBackend for LocalStorage {
+    fn kind(&self) -> &'static str {
+        "localStorage"
+    }
+
+    fn load(&self, key: &str) -> Result<Option<String>, String> {
+        Self::storage()?
+            .get_item(key)
+            .map_err(|e| format!("failed to read '{}': {:?}", key, e))
+    }
+
+    fn save(&mut self, key: &str, value: &str) -> Result<(), String> {
+        Self::storage()?.set_item(key, value).map_err(|e| {
+            let name = js_sys::Reflect::get(&e, &"name".into()).ok().and_then(|name| name.as_string());
+            // Firefox used its own name before adopting the standard one
+            match name.as_deref() {
+                Some("QuotaExceededError" | "NS_ERROR_DOM_QUOTA_REACHED") => {
+                    format!("storage is full, '{}' was not written ({} bytes)", key, value.len())
+                }
+                _ => format!("failed to write '{}': {:?}", key, e),
+            }
+        })
+    }
+}
+
+/// Keeps values in a map. Used in tests and where no browser is available.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryStorage {
+    values: HashMap<String, String>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for MemoryStorage {
+    fn kind(&self) -> &'static str {
+        "memory"
+    }
+
+    fn load(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.values.get(key).cloned())
+    }
+
+    fn save(&mut self, key: &str, value: &str) -> Result<(), String> {
+        self.values.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+}
+
+/// Writes every task in `manager` to `storage` under `key`, as JSON.
+pub fn save_tasks(storage: &mut dyn StorageBackend, key: &str, manager: &TaskManager) -> Result<(), String> {
+    let json = serde_json::to_string(&manager.get_all_tasks())
+        .map_err(|e| format!("failed to serialize tasks: {}", e))?;
+    storage.save(key, &json)
+}
+
+/// Reads the tasks saved by [`save_tasks`] under `key`. Returns `None` when
+/// nothing has been saved yet.
+pub fn load_tasks(storage: &dyn StorageBackend, key: &str) -> Result<Option<TaskManager>, String> {
+    match storage.load(key)? {
+        Some(json) if !json.is_empty() && json != "null" => {
+            let tasks: Vec<Task> =
+                serde_json::from_str(&json).map_err(|e| format!("failed to parse stored tasks: {}", e))?;
+            Ok(Some(tasks.into_iter().collect()))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Layout version written by [`export_backup`]. Bump it when the layout
+/// changes so older builds refuse backups they cannot read.
+pub const BACKUP_VERSION: u32 = 1;
+
+/// A copy of the task store that users can download and restore later.
+#[derive(Debug, Serialize, Deserialize)]
+struct Backup<T> {
+    version: u32,
+    created_at: DateTime<Utc>,
+    /// Hex SHA-256 of `tasks` as compact JSON
+    checksum: String,
+    tasks: T,
+}
+
+/// Hashes `tasks` after a round trip through `serde_json::Value`, whose
+/// sorted keys make the JSON the same on export and import.
+fn checksum(tasks: &serde_json::Value) -> String {
+    hex_encode(&Sha256::digest(tasks.to_string().as_bytes()))
+}
+
+/// Serializes every task in `manager` as a versioned, checksummed backup.
+pub fn export_backup(manager: &TaskManager, created_at: DateTime<Utc>) -> Result<String, String> {
+    let mut tasks = manager.get_all_tasks();
+    tasks.sort_by_key(|task| task.id);
+    let tasks = serde_json::to_value(tasks).map_err(|e| e.to_string())?;
+    let backup = Backup {
+        version: BACKUP_VERSION,
+        created_at,
+        checksum: checksum(&tasks),
+        tasks,
+    };
+    serde_json::to_string_pretty(&backup).map_err(|e| e.to_string())
+}
+
+/// Reads a backup written by [`export_backup`], checking its version and
+/// checksum before returning any task.
+pub fn parse_backup(data: &str) -> Result<Vec<Task>, String> {
+    let backup: Backup<serde_json::Value> =
+        serde_json::from_str(data).map_err(|e| format!("not a task backup: {}", e))?;
+    if backup.version != BACKUP_VERSION {
+        return Err(format!(
+            "unsupported backup version {} (expected {})",
+            backup.version, BACKUP_VERSION
+        ));
+    }
+    if checksum(&backup.tasks) != backup.checksum {
+        return Err("checksum mismatch, the backup is corrupt or was edited".to_string());
+    }
+    serde_json::from_value(backup.tasks).map_err(|e| format!("invalid task in backup: {}", e))
+}
+
+/// Puts the tasks of a backup into `manager` and returns a summary for the
+/// user.
+///
+/// With `merge`, the backup is merged into the current tasks, keeping the
+/// newer copy of a task present in both. Otherwise it replaces them. Either
+/// way, the removals logged for the weekly summary are kept.
+pub fn restore_backup(manager: &mut TaskManager, tasks: Vec<Task>, merge: bool) -> Result<String, String> {
+    if merge {
+        let summary = manager.merge(tasks, MergeStrategy::KeepNewest)?;
+        Ok(format!(
+            "Imported {} tasks ({} replaced, {} skipped)",
+            summary.added + summary.replaced,
+            summary.replaced,
+            summary.skipped
+        ))
+    } else {
+        let deletion_log = std::mem::take(&mut manager.deletion_log);
+        *manager = tasks.into_iter().collect();
+        manager.deletion_log = deletion_log;
+        Ok(format!("Restored {} tasks", manager.get_total_count()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Refuses every operation, like storage in some private browsing modes.
+    struct Unavailable;
+
+    impl StorageBackend for Unavailable {
+        fn kind(&self) -> &'static str {
+            "localStorage"
+        }
+
+        fn load(&self, _key: &str) -> Result<Option<String>, String> {
+            Err("localStorage is not available".to_string())
+        }
+
+        fn save(&mut self, _key: &str, _value: &str) -> Result<(), String> {
+            Err("localStorage is not available".to_string())
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut manager = TaskManager::new();
+        manager.add_task("Water plants".to_string(), "Balcony".to_string()).unwrap();
+        let done = manager.add_task("Write docs".to_string(), String::new()).unwrap();
+        manager.toggle_task(done);
+
+        let mut storage = MemoryStorage::new();
+        assert!(load_tasks(&storage, TASKS_KEY).unwrap().is_none());
+        save_tasks(&mut storage, TASKS_KEY, &manager).unwrap();
+
+        let mut loaded = load_tasks(&storage, TASKS_KEY).unwrap().unwrap();
+        assert_eq!(loaded.get_total_count(), 2);
+        assert!(loaded.get_task(done).unwrap().completed);
+        assert_eq!(loaded.get_task(1).unwrap().description, "Balcony");
+        assert_eq!(loaded.add_task("Next".to_string(), String::new()).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_bad_or_missing_storage() {
+        let mut storage = MemoryStorage::new();
+        storage.save(TASKS_KEY, "null").unwrap();
+        assert!(load_tasks(&storage, TASKS_KEY).unwrap().is_none());
+        storage.save(TASKS_KEY, "{not json").unwrap();
+        assert!(load_tasks(&storage, TASKS_KEY).unwrap_err().contains("failed to parse"));
+
+        assert!(load_tasks(&Unavailable, TASKS_KEY).is_err());
+        assert!(save_tasks(&mut Unavailable, TASKS_KEY, &TaskManager::new()).is_err());
+    }
+
+    #[test]
+    fn test_namespaced_keys() {
+        assert_eq!(tasks_key(None).unwrap(), TASKS_KEY);
+        assert_eq!(tasks_key(Some("work")).unwrap(), "gh_actions:work:tasks");
+        assert!(tasks_key(Some(" ")).is_err());
+
+        let mut manager = TaskManager::new();
+        manager.add_task("Work only".to_string(), String::new()).unwrap();
+        let mut storage = MemoryStorage::new();
+        save_tasks(&mut storage, &tasks_key(Some("work")).unwrap(), &manager).unwrap();
+        assert!(load_tasks(&storage, TASKS_KEY).unwrap().is_none());
+        assert!(load_tasks(&storage, &tasks_key(Some("home")).unwrap()).unwrap().is_none());
+        assert_eq!(load_tasks(&storage, "gh_actions:work:tasks").unwrap().unwrap().get_total_count(), 1);
+    }
+
+    #[test]
+    fn test_backup_round_trip() {
+        let mut manager = TaskManager::new();
+        manager.add_task("Water plants".to_string(), "Balcony".to_string()).unwrap();
+        let done = manager.add_task("Write docs".to_string(), String::new()).unwrap();
+        manager.toggle_task(done);
+
+        let created_at = DateTime::parse_from_rfc3339("2024-03-10T12:00:00Z").unwrap().with_timezone(&Utc);
+        let backup = export_backup(&manager, created_at).unwrap();
+        assert!(backup.contains("\"version\": 1"), "{}", backup);
+
+        let tasks = parse_backup(&backup).unwrap();
+        assert_eq!(tasks.iter().map(|task| task.id).collect::<Vec<_>>(), vec![1, done]);
+        assert!(tasks[1].completed);
+        assert_eq!(tasks[0].description, "Balcony");
+    }
+
+    #[test]
+    fn test_restore_keeps_removals() {
+        let mut manager = TaskManager::new();
+        let removed = manager.add_task("Old draft".to_string(), String::new()).unwrap();
+        manager.remove_task(removed);
+        let backup = Task::new(5, "Water plants".to_string(), String::new());
+
+        restore_backup(&mut manager, vec![backup], false).unwrap();
+        assert_eq!(manager.get_total_count(), 1);
+        assert_eq!(manager.weekly_summary().removed, 1);
+    }
+
+    #[test]
+    fn test_corrupt_backup() {
+        let mut manager = TaskManager::new();
+        manager.add_task("Water plants".to_string(), String::new()).unwrap();
+        let backup = export_backup(&manager, Utc::now()).unwrap();
+
+        let edited = backup.replace("Water plants", "Water cactus");
+        assert!(parse_backup(&edited).unwrap_err().contains("checksum mismatch"));
+        let future = backup.replace("\"version\": 1", "\"version\": 2");
+        assert!(parse_backup(&future).unwrap_err().contains("unsupported backup version 2"));
+        assert!(parse_backup(&backup[..backup.len() / 2]).unwrap_err().starts_with("not a task backup"));
+        assert!(parse_backup("[]").is_err());
+    }
+}
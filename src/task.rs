@@ -1,6 +1,75 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use chrono::{DateTime, Utc};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use chrono::{DateTime, Datelike, FixedOffset, Months, NaiveDate, Utc};
+use crate::export::ExportFormat;
+
+/// How often a recurring task comes back after it is completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    Monthly,
+    /// Every N days
+    Custom(u32),
+}
+
+impl Recurrence {
+    /// The date one recurrence period after `from`
+    pub fn advance(&self, from: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Recurrence::Daily => from + chrono::Duration::days(1),
+            Recurrence::Weekly => from + chrono::Duration::weeks(1),
+            // Clamps to the last day of shorter months (Jan 31 -> Feb 29)
+            Recurrence::Monthly => from.checked_add_months(Months::new(1)).unwrap_or(from),
+            Recurrence::Custom(days) => from + chrono::Duration::days(*days as i64),
+        }
+    }
+}
+
+/// `daily`, `weekly`, `monthly` or `every N days`, as written in CSV exports
+impl std::fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Recurrence::Daily => f.write_str("daily"),
+            Recurrence::Weekly => f.write_str("weekly"),
+            Recurrence::Monthly => f.write_str("monthly"),
+            Recurrence::Custom(days) => write!(f, "every {} days", days),
+        }
+    }
+}
+
+/// How urgent a task is. Tasks saved before priorities existed load as `Medium`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl Priority {
+    /// Parses `low`, `medium` or `high`, ignoring case
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(Priority::Low),
+            "medium" => Ok(Priority::Medium),
+            "high" => Ok(Priority::High),
+            _ => Err(format!("unknown priority '{}', expected 'low', 'medium' or 'high'", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+        })
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
@@ -10,6 +79,121 @@ pub struct Task {
     pub completed: bool,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    #[serde(default)]
+    pub priority: Priority,
+    /// Expected effort in hours, compared with the time actually taken by
+    /// [`TaskManager::get_estimation_accuracy`]
+    #[serde(default)]
+    pub estimated_hours: Option<f64>,
+    /// Labels for grouping tasks, kept in the order they were added. See
+    /// [`Task::add_tag`] for what a tag may contain.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Whether completing this recurring task already added its next
+    /// occurrence, so completing it again after un-completing adds no other.
+    /// CSV exports do not keep it.
+    #[serde(default)]
+    pub next_occurrence_spawned: bool,
+}
+
+/// The fields a task list shows, without the description and other details
+/// that can make the full task heavy.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskSummary {
+    pub id: u32,
+    pub title: String,
+    pub completed: bool,
+    pub priority: Priority,
+    pub due_date: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+}
+
+impl From<&Task> for TaskSummary {
+    fn from(task: &Task) -> Self {
+        TaskSummary {
+            id: task.id,
+            title: task.title.clone(),
+            completed: task.completed,
+            priority: task.priority,
+            due_date: task.due_date,
+            tags: task.tags.clone(),
+        }
+    }
+}
+
+/// A new task as described in JSON by the web UI; only `title` is required.
+///
+/// ```json
+/// {"title": "File taxes", "priority": "high", "due_date": "2024-04-15T00:00:00Z", "tags": ["admin"]}
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskSpec {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// `low`, `medium` or `high`
+    pub priority: Option<String>,
+    /// RFC 3339 timestamp
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub estimated_hours: Option<f64>,
+}
+
+impl TaskSpec {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("invalid task: {}", e))
+    }
+    
+    /// Builds the task, checking every field first so a bad one leaves
+    /// nothing half-made. The ID is set when the task is added.
+    pub fn into_task(self) -> Result<Task, String> {
+        if self.title.trim().is_empty() {
+            return Err("task title cannot be empty".to_string());
+        }
+        let mut task = Task::new(0, self.title, self.description);
+        if let Some(priority) = self.priority {
+            task.priority = Priority::parse(&priority)?;
+        }
+        if let Some(due_date) = self.due_date {
+            task.due_date = parse_due_date(&due_date)?;
+        }
+        for tag in &self.tags {
+            task.add_tag(tag)?;
+        }
+        task.estimated_hours = match self.estimated_hours {
+            Some(hours) if !(hours.is_finite() && hours > 0.0) => {
+                return Err(format!("invalid estimated_hours {}, expected a positive number", hours))
+            }
+            hours => hours,
+        };
+        Ok(task)
+    }
+}
+
+/// Parses an RFC 3339 due date such as `2024-03-10T17:00:00+01:00`. An empty
+/// string means no due date.
+pub fn parse_due_date(s: &str) -> Result<Option<DateTime<Utc>>, String> {
+    if s.is_empty() {
+        return Ok(None);
+    }
+    DateTime::parse_from_rfc3339(s)
+        .map(|due| Some(due.with_timezone(&Utc)))
+        .map_err(|e| format!("invalid due date '{}': {}", s, e))
+}
+
+/// Outcome of [`TaskManager::toggle_task`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ToggleResult {
+    /// Whether a task with the given ID existed and was toggled
+    pub toggled: bool,
+    /// ID of the next occurrence created when a recurring task was completed
+    pub new_task_id: Option<u32>,
 }
 
 impl Task {
@@ -21,7 +205,84 @@ impl Task {
             completed: false,
             created_at: Utc::now(),
             completed_at: None,
+            due_date: None,
+            recurrence: None,
+            priority: Priority::Medium,
+            estimated_hours: None,
+            tags: Vec::new(),
+            next_occurrence_spawned: false,
+        }
+    }
+    
+    /// A fresh, incomplete copy of this task under a new ID
+    pub fn clone_as_new(&self, id: u32) -> Task {
+        Task {
+            id,
+            title: self.title.clone(),
+            description: self.description.clone(),
+            due_date: self.due_date,
+            recurrence: self.recurrence,
+            priority: self.priority,
+            estimated_hours: self.estimated_hours,
+            tags: self.tags.clone(),
+            ..Task::new(id, String::new(), String::new())
+        }
+    }
+    
+    /// Hours from creation to completion, for completed tasks
+    #[must_use = "this returns the duration without modifying the task"]
+    pub fn actual_hours(&self) -> Option<f64> {
+        self.completed_at
+            .map(|completed| completed.signed_duration_since(self.created_at).num_seconds() as f64 / 3600.0)
+    }
+    
+    /// Time since the task was created
+    #[must_use = "this returns the duration without modifying the task"]
+    pub fn elapsed_since_creation(&self) -> chrono::Duration {
+        self.elapsed_since_creation_at(Utc::now())
+    }
+    
+    /// Time from creation until `now`
+    #[must_use = "this returns the duration without modifying the task"]
+    pub fn elapsed_since_creation_at(&self, now: DateTime<Utc>) -> chrono::Duration {
+        now - self.created_at
+    }
+    
+    /// Time from creation to completion, for completed tasks
+    #[must_use = "this returns the duration without modifying the task"]
+    pub fn time_to_complete(&self) -> Option<chrono::Duration> {
+        self.completed_at.map(|completed| completed - self.created_at)
+    }
+    
+    /// Adds `tag` after trimming it, returning whether it was new.
+    ///
+    /// Tags are matched ignoring case, so `Work` and `work` are the same tag.
+    /// Blank tags and tags containing commas are rejected, as commas separate
+    /// tags in CSV and in the tag input.
+    pub fn add_tag(&mut self, tag: &str) -> Result<bool, String> {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Err("tag cannot be empty".to_string());
         }
+        if tag.contains(',') {
+            return Err(format!("tag '{}' cannot contain a comma", tag));
+        }
+        if self.has_tag(tag) {
+            return Ok(false);
+        }
+        self.tags.push(tag.to_string());
+        Ok(true)
+    }
+    
+    /// Removes `tag`, ignoring case. Returns whether the task had it.
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let before = self.tags.len();
+        self.tags.retain(|existing| !existing.eq_ignore_ascii_case(tag.trim()));
+        self.tags.len() != before
+    }
+    
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag.trim()))
     }
     
     pub fn toggle_completed(&mut self) {
@@ -34,10 +295,400 @@ impl Task {
     }
 }
 
+/// How [`TaskManager::merge`] resolves an imported task whose ID is taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep whichever task was created or completed most recently
+    KeepNewest,
+    /// Keep the existing task and add the imported one under a new ID
+    KeepBoth,
+}
+
+impl MergeStrategy {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "keep-newest" => Ok(MergeStrategy::KeepNewest),
+            "keep-both" => Ok(MergeStrategy::KeepBoth),
+            other => Err(format!(
+                "unknown merge strategy '{}', expected 'keep-newest' or 'keep-both'",
+                other
+            )),
+        }
+    }
+}
+
+/// Which tasks [`TaskManager::get_tasks_filtered`] returns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TaskFilter {
+    #[default]
+    All,
+    /// Not completed yet
+    Active,
+    Completed,
+    /// Not completed and due before now
+    Overdue,
+}
+
+impl TaskFilter {
+    /// Parses `all`, `active`, `completed` or `overdue`, ignoring case
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(TaskFilter::All),
+            "active" => Ok(TaskFilter::Active),
+            "completed" => Ok(TaskFilter::Completed),
+            "overdue" => Ok(TaskFilter::Overdue),
+            _ => Err(format!(
+                "unknown filter '{}', expected 'all', 'active', 'completed' or 'overdue'",
+                s
+            )),
+        }
+    }
+    
+    #[must_use = "checking a task has no effect unless the result is used"]
+    pub fn matches(self, task: &Task, now: DateTime<Utc>) -> bool {
+        self.matches_state(task.completed, task.due_date, now)
+    }
+    
+    /// Like [`TaskFilter::matches`], for a task list that only has summaries
+    #[must_use = "checking a task has no effect unless the result is used"]
+    pub fn matches_summary(self, task: &TaskSummary, now: DateTime<Utc>) -> bool {
+        self.matches_state(task.completed, task.due_date, now)
+    }
+    
+    fn matches_state(self, completed: bool, due_date: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+        match self {
+            TaskFilter::All => true,
+            TaskFilter::Active => !completed,
+            TaskFilter::Completed => completed,
+            TaskFilter::Overdue => !completed && due_date.is_some_and(|due| due < now),
+        }
+    }
+}
+
+/// How [`TaskManager::get_tasks_page`] orders tasks. Ties go to the lower ID.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TaskOrder {
+    #[default]
+    Id,
+    /// Most recently created first
+    Newest,
+    /// Soonest due first, then tasks without a due date
+    Due,
+    /// Highest priority first
+    Priority,
+    /// Earliest created first
+    Oldest,
+    /// By title, ignoring case
+    Title,
+    /// Tasks not completed yet before completed ones
+    IncompleteFirst,
+}
+
+impl TaskOrder {
+    /// Parses `id`, `newest`, `due`, `priority`, `oldest`, `title` or
+    /// `incomplete`, ignoring case
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "id" => Ok(TaskOrder::Id),
+            "newest" => Ok(TaskOrder::Newest),
+            "due" => Ok(TaskOrder::Due),
+            "priority" => Ok(TaskOrder::Priority),
+            "oldest" => Ok(TaskOrder::Oldest),
+            "title" => Ok(TaskOrder::Title),
+            "incomplete" => Ok(TaskOrder::IncompleteFirst),
+            _ => Err(format!(
+                "unknown order '{}', expected 'id', 'newest', 'due', 'priority', 'oldest', 'title' or 'incomplete'",
+                s
+            )),
+        }
+    }
+    
+    /// Every key ends with the ID, so tasks that tie keep the same order
+    fn sort(self, tasks: &mut [&Task]) {
+        match self {
+            TaskOrder::Id => tasks.sort_by_key(|task| task.id),
+            TaskOrder::Newest => tasks.sort_by_key(|task| (std::cmp::Reverse(task.created_at), task.id)),
+            TaskOrder::Due => tasks.sort_by_key(|task| (task.due_date.is_none(), task.due_date, task.id)),
+            TaskOrder::Priority => tasks.sort_by_key(|task| (std::cmp::Reverse(task.priority), task.id)),
+            TaskOrder::Oldest => tasks.sort_by_key(|task| (task.created_at, task.id)),
+            TaskOrder::Title => tasks.sort_by_cached_key(|task| (task.title.to_lowercase(), task.id)),
+            TaskOrder::IncompleteFirst => tasks.sort_by_key(|task| (task.completed, task.id)),
+        }
+    }
+}
+
+/// The name [`TaskOrder::parse`] reads back
+impl std::fmt::Display for TaskOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TaskOrder::Id => "id",
+            TaskOrder::Newest => "newest",
+            TaskOrder::Due => "due",
+            TaskOrder::Priority => "priority",
+            TaskOrder::Oldest => "oldest",
+            TaskOrder::Title => "title",
+            TaskOrder::IncompleteFirst => "incomplete",
+        })
+    }
+}
+
+/// What [`TaskManager::merge`] did with the imported tasks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeSummary {
+    /// Added under their own ID, or a new one with [`MergeStrategy::KeepBoth`]
+    pub added: usize,
+    /// Replaced an older task with the same ID
+    pub replaced: usize,
+    /// Dropped in favour of a newer task with the same ID
+    pub skipped: usize,
+}
+
+/// What [`TaskManager::merge_manager`] did with the other manager's tasks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TaskMergeResult {
+    /// Inserted under their own ID
+    pub added: usize,
+    /// IDs already taken here, whose tasks were left out, in ascending order
+    pub skipped_ids: Vec<u32>,
+}
+
+/// What [`TaskManager::import_todoist`] or [`TaskManager::import_ticktick`]
+/// did with the items of an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Added as new tasks
+    pub imported: usize,
+    /// Left out: deleted items, items without a title and entries that are
+    /// not objects
+    pub skipped: usize,
+}
+
+impl std::fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Imported {} tasks ({} skipped)", self.imported, self.skipped)
+    }
+}
+
+/// Where another app keeps each task field in its JSON export. Where several
+/// keys are listed, the first one present wins, which covers older and newer
+/// versions of the same export.
+struct ExternalFormat {
+    name: &'static str,
+    /// Key of the item list when the export is an object rather than a list
+    items: &'static str,
+    title: &'static str,
+    description: &'static [&'static str],
+    created_at: &'static [&'static str],
+    completed_at: &'static [&'static str],
+    /// Whether an item is done, for exports that mark it without a date
+    completed: fn(&serde_json::Value) -> bool,
+    deleted: &'static str,
+    tags: &'static str,
+}
+
+/// A Todoist sync or backup export: `{"items": [...]}`. Newer exports use
+/// `added_at`, `completed_at` and a boolean `checked`; older ones use
+/// `date_added`, `date_completed`, `checked: 1` and a list of `notes`.
+const TODOIST: ExternalFormat = ExternalFormat {
+    name: "Todoist",
+    items: "items",
+    title: "content",
+    description: &["description", "notes"],
+    created_at: &["added_at", "date_added", "created"],
+    completed_at: &["completed_at", "date_completed"],
+    completed: |item| item.get("checked").or_else(|| item.get("completed")).is_some_and(is_truthy),
+    deleted: "is_deleted",
+    tags: "labels",
+};
+
+/// A TickTick project export: `{"project": {...}, "tasks": [...]}`, where
+/// `status` 2 means done
+const TICKTICK: ExternalFormat = ExternalFormat {
+    name: "TickTick",
+    items: "tasks",
+    title: "title",
+    description: &["content", "desc"],
+    created_at: &["createdTime"],
+    completed_at: &["completedTime"],
+    completed: |item| item.get("status").and_then(serde_json::Value::as_i64) == Some(2),
+    deleted: "deleted",
+    tags: "tags",
+};
+
+/// `true`, or a non-zero number as in `"checked": 1`
+fn is_truthy(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Bool(value) => *value,
+        serde_json::Value::Number(number) => number.as_f64().is_some_and(|number| number != 0.0),
+        _ => false,
+    }
+}
+
+/// Reads the date formats seen in exports: RFC 3339, `2024-03-01T14:02:11.000+0000`
+/// (TickTick), `Fri 26 Sep 2014 08:25:05 +0000` (older Todoist) and a bare
+/// `2024-03-01`, taken as midnight UTC.
+fn parse_external_date(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    DateTime::parse_from_rfc3339(s)
+        .or_else(|_| DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .or_else(|_| DateTime::parse_from_str(s, "%a %d %b %Y %H:%M:%S %z"))
+        .map(|date| date.with_timezone(&Utc))
+        .ok()
+        .or_else(|| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0).map(|date| date.and_utc()))
+}
+
+/// The text of a description field: a string, or a list of notes whose
+/// `content` is joined with blank lines
+fn external_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.trim().to_string(),
+        serde_json::Value::Array(notes) => notes
+            .iter()
+            .filter_map(|note| note.as_str().or_else(|| note.get("content")?.as_str()))
+            .map(str::trim)
+            .filter(|note| !note.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        _ => String::new(),
+    }
+}
+
+impl ExternalFormat {
+    /// Builds a task from one exported item, or `None` if it should be
+    /// skipped. A missing creation date falls back to the completion date,
+    /// then to `now`; a task marked done without a date counts as done when
+    /// it was created.
+    fn to_task(&self, item: &serde_json::Value, now: DateTime<Utc>) -> Option<Task> {
+        if !item.is_object() || item.get(self.deleted).is_some_and(is_truthy) {
+            return None;
+        }
+        let title = item.get(self.title)?.as_str()?.trim();
+        if title.is_empty() {
+            return None;
+        }
+        let description = self.description.iter()
+            .filter_map(|key| item.get(*key))
+            .map(external_text)
+            .find(|text| !text.is_empty())
+            .unwrap_or_default();
+        let date = |keys: &[&str]| {
+            keys.iter().find_map(|key| parse_external_date(item.get(*key)?.as_str()?))
+        };
+        let completed_at = date(self.completed_at);
+        let created_at = date(self.created_at).or(completed_at).unwrap_or(now);
+
+        let mut task = Task::new(0, title.to_string(), description);
+        task.created_at = created_at;
+        task.completed = completed_at.is_some() || (self.completed)(item);
+        // Never before creation, which would make the completion time negative
+        task.completed_at = task.completed.then(|| completed_at.map_or(created_at, |done| done.max(created_at)));
+        // Tags this app cannot hold, and Todoist's numeric label IDs, are dropped
+        for tag in item.get(self.tags).and_then(serde_json::Value::as_array).into_iter().flatten() {
+            if let Some(tag) = tag.as_str() {
+                let _ = task.add_tag(tag);
+            }
+        }
+        Some(task)
+    }
+}
+
+/// A task ID, used to index a [`TaskManager`] directly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(pub u32);
+
+/// Activity over the seven days before now, from [`TaskManager::weekly_summary`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeeklySummary {
+    /// Tasks created this week and not removed since
+    pub created: usize,
+    pub completed: usize,
+    /// Tasks removed this week since the manager was created. Removals are
+    /// not saved with the tasks, so they are counted per session only.
+    pub removed: usize,
+    /// Share of the tasks created this week that are now completed, from 0.0
+    /// to 1.0
+    pub completion_rate: f64,
+    /// Creation to completion, averaged over the tasks completed this week;
+    /// 0.0 when there are none
+    pub avg_completion_hours: f64,
+}
+
+/// Task KPIs over a window of days, as shown by `gh_actions task stats`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskReport {
+    /// Length of the window used for velocity, streak and daily counts
+    pub days: u32,
+    pub total: usize,
+    pub completed: usize,
+    pub pending: usize,
+    /// Completed tasks as a fraction of all tasks, from 0.0 to 1.0
+    pub completion_rate: f64,
+    pub average_completion_hours: Option<f64>,
+    pub median_completion_hours: Option<f64>,
+    /// Tasks completed per day within the window
+    pub velocity: f64,
+    /// See [`TaskManager::get_completion_streak`]
+    pub current_streak: u32,
+    /// Completions for each day in the window, oldest first
+    pub completions_per_day: Vec<DailyCount>,
+    /// The [`TOP_TAGS`] tags on the most tasks, most used first, ties in
+    /// name order
+    pub top_tags: Vec<TagCount>,
+    /// The incomplete task created first, if any
+    pub oldest_incomplete: Option<OldestTask>,
+}
+
+/// Number of tags listed in [`TaskReport::top_tags`]
+pub const TOP_TAGS: usize = 5;
+
+/// Most buckets [`TaskManager::get_completion_histogram`] returns
+pub const MAX_HISTOGRAM_BUCKETS: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    /// Tasks carrying the tag, completed or not
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OldestTask {
+    pub id: u32,
+    pub title: String,
+    /// Time since the task was created, up to the report's `now`
+    pub age_hours: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DailyCount {
+    pub date: NaiveDate,
+    pub count: usize,
+}
+
+#[cfg(feature = "json")]
+impl Task {
+    /// Serializes the task as a JSON object
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+    
+    /// Parses a task from a JSON object
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct TaskManager {
     pub(crate) tasks: HashMap<u32, Task>,
-    pub(crate) next_id: u32,
+    /// One past the highest ID handed out or seen. Wider than an ID, so a
+    /// task with ID `u32::MAX` leaves no ID to hand out rather than
+    /// overflowing.
+    pub(crate) next_id: u64,
+    /// Tasks removed in the last week, for [`TaskManager::weekly_summary`].
+    /// Kept in memory only, so it starts empty when tasks are loaded, but
+    /// survives [`crate::storage::restore_backup`].
+    pub(crate) deletion_log: Vec<(TaskId, DateTime<Utc>)>,
 }
 
 impl TaskManager {
@@ -45,52 +696,371 @@ impl TaskManager {
         TaskManager {
             tasks: HashMap::new(),
             next_id: 1,
+            deletion_log: Vec::new(),
         }
     }
     
-    pub fn add_task(&mut self, title: String, description: String) -> u32 {
-        let id = self.next_id;
-        let task = Task::new(id, title, description);
-        self.tasks.insert(id, task);
+    /// Adds a task under the next free ID. Fails only once a task holds the
+    /// ID `u32::MAX`, as IDs are never reused.
+    pub fn add_task(&mut self, title: String, description: String) -> Result<u32, String> {
+        let id = self.take_id()?;
+        self.tasks.insert(id, Task::new(id, title, description));
+        Ok(id)
+    }
+    
+    /// Adds `task` under the next free ID, replacing the ID it had. Fails
+    /// like [`TaskManager::add_task`].
+    pub fn add_task_from(&mut self, task: Task) -> Result<u32, String> {
+        let id = self.take_id()?;
+        self.tasks.insert(id, Task { id, ..task });
+        Ok(id)
+    }
+    
+    fn take_id(&mut self) -> Result<u32, String> {
+        let id = u32::try_from(self.next_id).map_err(|_| "every task ID is in use".to_string())?;
         self.next_id += 1;
-        id
+        Ok(id)
     }
     
+    #[must_use = "looking up a task has no effect unless the result is used"]
     pub fn get_task(&self, id: u32) -> Option<&Task> {
         self.tasks.get(&id)
     }
     
+    pub fn get_task_mut(&mut self, id: u32) -> Option<&mut Task> {
+        self.tasks.get_mut(&id)
+    }
+    
+    #[must_use = "this collects the tasks into a new Vec without modifying the manager"]
     pub fn get_all_tasks(&self) -> Vec<&Task> {
         self.tasks.values().collect()
     }
     
-    pub fn toggle_task(&mut self, id: u32) -> bool {
-        if let Some(task) = self.tasks.get_mut(&id) {
-            task.toggle_completed();
-            true
-        } else {
-            false
+    /// Toggle a task's completion state.
+    ///
+    /// Completing a task that has a recurrence schedules its next occurrence:
+    /// a copy is added with the due date advanced by one period (counted from
+    /// the completion time when the task has no due date). Only the first
+    /// completion does this, so un-completing and completing again adds no
+    /// second copy. Once every ID is in use, the task is completed without one.
+    pub fn toggle_task(&mut self, id: u32) -> ToggleResult {
+        let Some(task) = self.tasks.get_mut(&id) else {
+            return ToggleResult { toggled: false, new_task_id: None };
+        };
+        task.toggle_completed();
+        
+        let next = match (task.recurrence, task.completed_at) {
+            (Some(recurrence), Some(completed_at)) if !task.next_occurrence_spawned => {
+                let mut next = task.clone_as_new(0);
+                next.due_date = Some(recurrence.advance(task.due_date.unwrap_or(completed_at)));
+                Some(next)
+            }
+            _ => None,
+        };
+        let new_task_id = next.and_then(|next| self.add_task_from(next).ok());
+        if new_task_id.is_some() {
+            self[TaskId(id)].next_occurrence_spawned = true;
         }
+        
+        ToggleResult { toggled: true, new_task_id }
+    }
+    
+    /// Tasks whose title or description contains `query`, ignoring case,
+    /// ordered by ID
+    #[must_use = "searching has no effect unless the result is used"]
+    pub fn search(&self, query: &str) -> Vec<&Task> {
+        let query = query.to_lowercase();
+        let mut matches: Vec<&Task> = self.tasks.values()
+            .filter(|task| {
+                task.title.to_lowercase().contains(&query)
+                    || task.description.to_lowercase().contains(&query)
+            })
+            .collect();
+        matches.sort_by_key(|task| task.id);
+        matches
+    }
+    
+    /// Tasks with `tag`, ignoring case, ordered by ID
+    #[must_use = "this collects the tasks into a new Vec without modifying the manager"]
+    pub fn get_tasks_with_tag(&self, tag: &str) -> Vec<&Task> {
+        let mut matches: Vec<&Task> = self.tasks.values()
+            .filter(|task| task.has_tag(tag))
+            .collect();
+        matches.sort_by_key(|task| task.id);
+        matches
+    }
+    
+    /// Tasks matching `filter`, ordered by ID. `now` decides what is overdue.
+    #[must_use = "this collects the tasks into a new Vec without modifying the manager"]
+    pub fn get_tasks_filtered(&self, filter: TaskFilter, now: DateTime<Utc>) -> Vec<&Task> {
+        let mut matches: Vec<&Task> = self.tasks.values()
+            .filter(|task| filter.matches(task, now))
+            .collect();
+        matches.sort_by_key(|task| task.id);
+        matches
+    }
+    
+    /// Up to `limit` tasks in `order`, skipping the first `offset`
+    #[must_use = "this collects the tasks into a new Vec without modifying the manager"]
+    pub fn get_tasks_page(&self, offset: usize, limit: usize, order: TaskOrder) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self.tasks.values().collect();
+        order.sort(&mut tasks);
+        tasks.into_iter().skip(offset).take(limit).collect()
+    }
+    
+    /// Incomplete tasks with a due date, soonest first
+    ///
+    /// With `until`, only tasks due at or before it are included, which
+    /// covers overdue tasks as well as upcoming ones.
+    #[must_use = "this collects the tasks into a new Vec without modifying the manager"]
+    pub fn get_due_tasks(&self, until: Option<DateTime<Utc>>) -> Vec<&Task> {
+        let mut due: Vec<&Task> = self.tasks.values()
+            .filter(|task| !task.completed)
+            .filter(|task| match (task.due_date, until) {
+                (Some(due), Some(until)) => due <= until,
+                (Some(_), None) => true,
+                (None, _) => false,
+            })
+            .collect();
+        due.sort_by_key(|task| (task.due_date, task.id));
+        due
     }
     
+    /// Incomplete high-priority tasks, oldest first
+    #[must_use = "this collects the tasks into a new Vec without modifying the manager"]
+    pub fn get_high_priority_incomplete_tasks(&self) -> Vec<&Task> {
+        let mut hot: Vec<&Task> = self.tasks.values()
+            .filter(|task| task.priority == Priority::High && !task.completed)
+            .collect();
+        hot.sort_by_key(|task| (task.created_at, task.id));
+        hot
+    }
+
+    /// Incomplete tasks created more than `n` days ago, oldest first
+    #[must_use = "this collects the tasks into a new Vec without modifying the manager"]
+    pub fn get_tasks_stale_for_n_days(&self, n: u32) -> Vec<&Task> {
+        self.get_tasks_stale_for_n_days_at(n, Utc::now())
+    }
+
+    /// Incomplete tasks created more than `n` days before `now`, oldest first
+    #[must_use = "this collects the tasks into a new Vec without modifying the manager"]
+    pub fn get_tasks_stale_for_n_days_at(&self, n: u32, now: DateTime<Utc>) -> Vec<&Task> {
+        // Nothing can be older than the earliest representable date
+        let Some(cutoff) = now.checked_sub_signed(chrono::Duration::days(i64::from(n))) else {
+            return Vec::new();
+        };
+        let mut stale: Vec<&Task> = self.tasks.values()
+            .filter(|task| !task.completed && task.created_at < cutoff)
+            .collect();
+        stale.sort_by_key(|task| (task.created_at, task.id));
+        stale
+    }
+
     pub fn remove_task(&mut self, id: u32) -> bool {
-        self.tasks.remove(&id).is_some()
+        let removed = self.tasks.remove(&id).is_some();
+        if removed {
+            self.log_deletions([id]);
+        }
+        removed
+    }
+    
+    /// Remove every completed task, returning how many were removed
+    pub fn clear_completed(&mut self) -> usize {
+        self.retain(|task| !task.completed)
+    }
+    
+    /// Keeps only the tasks for which `predicate` returns true, like
+    /// [`Vec::retain`], and returns how many were removed. Tasks are visited
+    /// in no particular order.
+    pub fn retain<F: FnMut(&Task) -> bool>(&mut self, mut predicate: F) -> usize {
+        let mut removed = Vec::new();
+        self.tasks.retain(|&id, task| {
+            let keep = predicate(task);
+            if !keep {
+                removed.push(id);
+            }
+            keep
+        });
+        let count = removed.len();
+        self.log_deletions(removed);
+        count
+    }
+    
+    /// Records removals for the weekly summary, forgetting those too old to
+    /// appear in it
+    fn log_deletions(&mut self, ids: impl IntoIterator<Item = u32>) {
+        let now = Utc::now();
+        let week_ago = now - chrono::Duration::weeks(1);
+        self.deletion_log.retain(|(_, removed_at)| *removed_at > week_ago);
+        self.deletion_log.extend(ids.into_iter().map(|id| (TaskId(id), now)));
+    }
+    
+    /// Save all tasks as a JSON array ordered by ID
+    pub fn save_to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut tasks = self.get_all_tasks();
+        tasks.sort_by_key(|task| task.id);
+        let json = serde_json::to_string_pretty(&tasks)?;
+        fs::write(path, json)?;
+        log::debug!("wrote {} tasks to {}", tasks.len(), path);
+        Ok(())
+    }
+    
+    /// Replace all tasks with the JSON array stored at `path`
+    pub fn load_from_file(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
+        let tasks: Vec<Task> = serde_json::from_str(&content)?;
+        
+        self.tasks.clear();
+        self.next_id = 1;
+        self.extend(tasks);
+        log::debug!("parsed {} tasks from {}", self.tasks.len(), path);
+        Ok(())
+    }
+    
+    /// IDs of `tasks` that are already in use, in ascending order
+    #[must_use = "this returns the clashing IDs without modifying the manager"]
+    pub fn conflicting_ids(&self, tasks: &[Task]) -> Vec<u32> {
+        let mut ids: Vec<u32> = tasks.iter()
+            .map(|task| task.id)
+            .filter(|id| self.tasks.contains_key(id))
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+    
+    /// Add imported tasks, resolving ID collisions with `strategy`
+    ///
+    /// With [`MergeStrategy::KeepBoth`], nothing is added when the IDs are so
+    /// close to `u32::MAX` that renumbering every task could run out of IDs.
+    pub fn merge(&mut self, tasks: Vec<Task>, strategy: MergeStrategy) -> Result<MergeSummary, String> {
+        if strategy == MergeStrategy::KeepBoth {
+            let next_id = tasks.iter().map(|task| u64::from(task.id) + 1).fold(self.next_id, u64::max);
+            if next_id + tasks.len() as u64 > 1 << 32 {
+                return Err(format!("task IDs up to {} leave too few IDs to renumber clashing tasks", next_id - 1));
+            }
+        }
+        let last_change = |task: &Task| task.completed_at.unwrap_or(task.created_at);
+        let mut summary = MergeSummary::default();
+        for mut task in tasks {
+            match self.tasks.get(&task.id) {
+                None => {
+                    self.extend([task]);
+                    summary.added += 1;
+                }
+                Some(existing) => match strategy {
+                    MergeStrategy::KeepNewest if last_change(&task) > last_change(existing) => {
+                        self.tasks.insert(task.id, task);
+                        summary.replaced += 1;
+                    }
+                    MergeStrategy::KeepNewest => summary.skipped += 1,
+                    MergeStrategy::KeepBoth => {
+                        task.id = self.take_id()?;
+                        self.extend([task]);
+                        summary.added += 1;
+                    }
+                },
+            }
+        }
+        Ok(summary)
+    }
+    
+    /// Add the tasks of `other` whose IDs are free here, keeping the task
+    /// already here when both have an ID
+    ///
+    /// New IDs continue after the highest either manager has handed out, so
+    /// an ID `other` used for a task since removed is not reused.
+    pub fn merge_manager(&mut self, other: TaskManager) -> TaskMergeResult {
+        let mut result = TaskMergeResult::default();
+        let next_id = self.next_id.max(other.next_id);
+        let mut tasks: Vec<Task> = other.tasks.into_values().collect();
+        tasks.sort_by_key(|task| task.id);
+        for task in tasks {
+            match self.tasks.entry(task.id) {
+                Entry::Occupied(_) => result.skipped_ids.push(task.id),
+                Entry::Vacant(slot) => {
+                    slot.insert(task);
+                    result.added += 1;
+                }
+            }
+        }
+        self.next_id = next_id;
+        result
+    }
+    
+    /// Add the tasks in a CSV export (see [`ExportFormat::Csv`]), giving
+    /// tasks whose ID is already taken a fresh one
+    ///
+    /// Nothing is added unless the whole input parses.
+    pub fn import_from_csv(&mut self, csv: &str) -> Result<MergeSummary, String> {
+        let tasks = ExportFormat::Csv.read(csv)?;
+        self.merge(tasks, MergeStrategy::KeepBoth)
+    }
+    
+    /// Add the tasks in a Todoist JSON export under new IDs. Unknown fields
+    /// are ignored, and deleted or untitled items are skipped.
+    ///
+    /// Nothing is added unless the export as a whole can be read.
+    pub fn import_todoist(&mut self, data: &str) -> Result<ImportSummary, String> {
+        self.import_external(&TODOIST, data, Utc::now())
+    }
+    
+    /// Add the tasks in a TickTick JSON export under new IDs, like
+    /// [`import_todoist`](Self::import_todoist)
+    pub fn import_ticktick(&mut self, data: &str) -> Result<ImportSummary, String> {
+        self.import_external(&TICKTICK, data, Utc::now())
+    }
+    
+    fn import_external(
+        &mut self,
+        format: &ExternalFormat,
+        data: &str,
+        now: DateTime<Utc>,
+    ) -> Result<ImportSummary, String> {
+        let export: serde_json::Value = serde_json::from_str(data)
+            .map_err(|e| format!("not a {} export: {}", format.name, e))?;
+        let items = match export.get(format.items).unwrap_or(&export) {
+            serde_json::Value::Array(items) => items,
+            _ => {
+                return Err(format!(
+                    "not a {} export: expected a list of tasks or an object with `{}`",
+                    format.name, format.items
+                ))
+            }
+        };
+        let tasks: Vec<Task> = items.iter().filter_map(|item| format.to_task(item, now)).collect();
+        if self.next_id + tasks.len() as u64 > 1 << 32 {
+            return Err("too few task IDs are left for the imported tasks".to_string());
+        }
+        let summary = ImportSummary { imported: tasks.len(), skipped: items.len() - tasks.len() };
+        for task in tasks {
+            self.add_task_from(task)?;
+        }
+        Ok(summary)
     }
     
+    /// Number of tasks currently marked as completed.
+    ///
+    /// This walks every task, so the `#[must_use]` guards against paying for
+    /// a full scan whose result is then thrown away.
+    #[must_use = "counting tasks has no effect unless the result is used"]
     pub fn get_completed_count(&self) -> usize {
         self.tasks.values().filter(|t| t.completed).count()
     }
     
+    #[must_use = "counting tasks has no effect unless the result is used"]
     pub fn get_total_count(&self) -> usize {
         self.tasks.len()
     }
     
+    #[must_use = "filtering allocates a new Vec and does not change the manager"]
     pub fn get_tasks_created_since(&self, since: DateTime<Utc>) -> Vec<&Task> {
         self.tasks.values()
             .filter(|task| task.created_at >= since)
             .collect()
     }
     
+    #[must_use = "filtering allocates a new Vec and does not change the manager"]
     pub fn get_tasks_completed_since(&self, since: DateTime<Utc>) -> Vec<&Task> {
         self.tasks.values()
             .filter(|task| {
@@ -103,15 +1073,9 @@ impl TaskManager {
             .collect()
     }
     
+    #[must_use = "computing the average has no effect unless the result is used"]
     pub fn get_average_completion_time_hours(&self) -> Option<f64> {
-        let completed_tasks: Vec<_> = self.tasks.values()
-            .filter_map(|task| {
-                task.completed_at.map(|completed| {
-                    let duration = completed.signed_duration_since(task.created_at);
-                    duration.num_seconds() as f64 / 3600.0 // Convert to hours
-                })
-            })
-            .collect();
+        let completed_tasks = self.completion_times_hours();
             
         if completed_tasks.is_empty() {
             None
@@ -120,25 +1084,219 @@ impl TaskManager {
         }
     }
     
-    /// Get time series data for completed tasks over the last N days
-    pub fn get_completed_tasks_time_series(&self, days: u32) -> Vec<(DateTime<Utc>, usize)> {
-        let now = Utc::now();
-        let mut series = Vec::new();
-        
-        for day in 0..days {
-            let date = now - chrono::Duration::days(day as i64);
-            let start_of_day = date.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
-            let end_of_day = date.date_naive().and_hms_opt(23, 59, 59).unwrap().and_utc();
-            
-            let completed_count = self.tasks.values()
-                .filter(|task| {
-                    if let Some(completed_at) = task.completed_at {
-                        completed_at >= start_of_day && completed_at <= end_of_day
-                    } else {
-                        false
-                    }
-                })
-                .count();
+    #[must_use = "computing the median has no effect unless the result is used"]
+    pub fn get_median_completion_time_hours(&self) -> Option<f64> {
+        let mut hours = self.completion_times_hours();
+        if hours.is_empty() {
+            return None;
+        }
+        hours.sort_by(|a, b| a.total_cmp(b));
+        let mid = hours.len() / 2;
+        if hours.len() % 2 == 1 {
+            Some(hours[mid])
+        } else {
+            Some((hours[mid - 1] + hours[mid]) / 2.0)
+        }
+    }
+    
+    /// Completed tasks by how long they took, as `(bucket_start_hours, count)`
+    /// for buckets of `bucket_hours` from 0 up to the one holding the slowest
+    /// task. Empty buckets are included, so the starts are evenly spaced.
+    ///
+    /// At most [`MAX_HISTOGRAM_BUCKETS`] buckets are returned; when more would
+    /// be needed, the last one also counts every slower task. Empty when no
+    /// task is completed or when `bucket_hours` is not a positive number.
+    #[must_use = "building a histogram has no effect unless the result is used"]
+    pub fn get_completion_histogram(&self, bucket_hours: f64) -> Vec<(f64, usize)> {
+        if !(bucket_hours > 0.0 && bucket_hours.is_finite()) {
+            return Vec::new();
+        }
+        let hours = self.completion_times_hours();
+        let Some(slowest) = hours.iter().copied().reduce(f64::max) else {
+            return Vec::new();
+        };
+        // A task completed "before" it was created, after a clock change,
+        // counts as completed at once
+        let bucket = |hours: f64| (hours.max(0.0) / bucket_hours).floor();
+        let len = (bucket(slowest) + 1.0).min(MAX_HISTOGRAM_BUCKETS as f64) as usize;
+        let mut counts = vec![0; len];
+        for hours in hours {
+            counts[(bucket(hours) as usize).min(len - 1)] += 1;
+        }
+        counts.into_iter().enumerate().map(|(i, count)| (i as f64 * bucket_hours, count)).collect()
+    }
+    
+    fn completion_times_hours(&self) -> Vec<f64> {
+        self.tasks.values()
+            .filter_map(Task::actual_hours)
+            .collect()
+    }
+    
+    /// Tasks with an effort estimate, ordered by ID
+    #[must_use = "this collects the tasks into a new Vec without modifying the manager"]
+    pub fn get_tasks_with_estimates(&self) -> Vec<&Task> {
+        let mut estimated: Vec<&Task> = self.tasks.values()
+            .filter(|task| task.estimated_hours.is_some())
+            .collect();
+        estimated.sort_by_key(|task| task.id);
+        estimated
+    }
+    
+    /// Completed tasks that took more than 110% of their estimate, ordered by ID
+    #[must_use = "this collects the tasks into a new Vec without modifying the manager"]
+    pub fn get_overestimated_tasks(&self) -> Vec<&Task> {
+        let mut over: Vec<&Task> = self.tasks.values()
+            .filter(|task| match (task.actual_hours(), task.estimated_hours) {
+                (Some(actual), Some(estimate)) => actual > estimate * 1.1,
+                _ => false,
+            })
+            .collect();
+        over.sort_by_key(|task| task.id);
+        over
+    }
+    
+    /// Mean absolute percentage error of the estimates, as a fraction
+    ///
+    /// Averages `|actual - estimate| / estimate` over completed tasks with a
+    /// positive estimate, so 0.25 means estimates were off by 25% on average.
+    /// `None` when no completed task has one.
+    #[must_use = "computing the accuracy has no effect unless the result is used"]
+    pub fn get_estimation_accuracy(&self) -> Option<f64> {
+        let errors: Vec<f64> = self.tasks.values()
+            .filter_map(|task| {
+                let estimate = task.estimated_hours.filter(|hours| *hours > 0.0)?;
+                Some((task.actual_hours()? - estimate).abs() / estimate)
+            })
+            .collect();
+        if errors.is_empty() {
+            None
+        } else {
+            Some(errors.iter().sum::<f64>() / errors.len() as f64)
+        }
+    }
+    
+    /// Activity over the last seven days
+    #[must_use = "building a summary has no effect unless the result is used"]
+    pub fn weekly_summary(&self) -> WeeklySummary {
+        self.weekly_summary_at(Utc::now())
+    }
+    
+    /// Activity over the seven days before `now`
+    #[must_use = "building a summary has no effect unless the result is used"]
+    pub fn weekly_summary_at(&self, now: DateTime<Utc>) -> WeeklySummary {
+        let week_ago = now - chrono::Duration::weeks(1);
+        let this_week = |at: DateTime<Utc>| at > week_ago && at <= now;
+        
+        let created: Vec<&Task> = self.tasks.values().filter(|task| this_week(task.created_at)).collect();
+        let hours: Vec<f64> = self.tasks.values()
+            .filter(|task| task.completed_at.is_some_and(this_week))
+            .filter_map(Task::actual_hours)
+            .collect();
+        let removed = self.deletion_log.iter().filter(|(_, removed_at)| this_week(*removed_at)).count();
+        let created_and_completed = created.iter().filter(|task| task.completed).count();
+        
+        WeeklySummary {
+            created: created.len(),
+            completed: hours.len(),
+            removed,
+            completion_rate: if created.is_empty() {
+                0.0
+            } else {
+                created_and_completed as f64 / created.len() as f64
+            },
+            avg_completion_hours: if hours.is_empty() {
+                0.0
+            } else {
+                hours.iter().sum::<f64>() / hours.len() as f64
+            },
+        }
+    }
+    
+    /// Summarizes the task list over the `days` days ending on the day of `now`.
+    ///
+    /// Days are calendar days in `offset`, so a task completed late in the
+    /// evening counts towards the local date rather than the UTC one. The
+    /// streak is [`TaskManager::get_completion_streak_at`] `now`, in UTC days
+    /// like everywhere else it is shown.
+    #[must_use = "building a report has no effect unless the result is used"]
+    pub fn get_report(&self, now: DateTime<Utc>, days: u32, offset: FixedOffset) -> TaskReport {
+        let total = self.get_total_count();
+        let completed = self.get_completed_count();
+        
+        let today = now.with_timezone(&offset).date_naive();
+        let first_day = today - chrono::Duration::days(days.saturating_sub(1) as i64);
+        let mut completions_per_day: Vec<DailyCount> = first_day
+            .iter_days()
+            .take(days as usize)
+            .map(|date| DailyCount { date, count: 0 })
+            .collect();
+        for completed_at in self.tasks.values().filter_map(|task| task.completed_at) {
+            let date = completed_at.with_timezone(&offset).date_naive();
+            if date >= first_day && date <= today {
+                completions_per_day[(date - first_day).num_days() as usize].count += 1;
+            }
+        }
+        
+        let completed_in_window: usize = completions_per_day.iter().map(|day| day.count).sum();
+        
+        let mut tag_counts: HashMap<&str, usize> = HashMap::new();
+        for tag in self.tasks.values().flat_map(|task| &task.tags) {
+            *tag_counts.entry(tag).or_default() += 1;
+        }
+        let mut top_tags: Vec<TagCount> = tag_counts
+            .into_iter()
+            .map(|(tag, count)| TagCount { tag: tag.to_string(), count })
+            .collect();
+        top_tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        top_tags.truncate(TOP_TAGS);
+        
+        let oldest_incomplete = self
+            .tasks
+            .values()
+            .filter(|task| !task.completed)
+            .min_by_key(|task| (task.created_at, task.id))
+            .map(|task| OldestTask {
+                id: task.id,
+                title: task.title.clone(),
+                age_hours: (now - task.created_at).num_seconds() as f64 / 3600.0,
+            });
+        
+        TaskReport {
+            days,
+            total,
+            completed,
+            pending: total - completed,
+            completion_rate: if total == 0 { 0.0 } else { completed as f64 / total as f64 },
+            average_completion_hours: self.get_average_completion_time_hours(),
+            median_completion_hours: self.get_median_completion_time_hours(),
+            velocity: if days == 0 { 0.0 } else { completed_in_window as f64 / days as f64 },
+            current_streak: self.get_completion_streak_at(now),
+            completions_per_day,
+            top_tags,
+            oldest_incomplete,
+        }
+    }
+    
+    /// Get time series data for completed tasks over the last N days
+    #[must_use = "building a time series has no effect unless the result is used"]
+    pub fn get_completed_tasks_time_series(&self, days: u32) -> Vec<(DateTime<Utc>, usize)> {
+        let now = Utc::now();
+        let mut series = Vec::new();
+        
+        for day in 0..days {
+            let date = now - chrono::Duration::days(day as i64);
+            let start_of_day = date.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+            let end_of_day = date.date_naive().and_hms_opt(23, 59, 59).unwrap().and_utc();
+            
+            let completed_count = self.tasks.values()
+                .filter(|task| {
+                    if let Some(completed_at) = task.completed_at {
+                        completed_at >= start_of_day && completed_at <= end_of_day
+                    } else {
+                        false
+                    }
+                })
+                .count();
                 
             series.push((start_of_day, completed_count));
         }
@@ -148,6 +1306,7 @@ impl TaskManager {
     }
     
     /// Get time series data for incomplete tasks over the last N days
+    #[must_use = "building a time series has no effect unless the result is used"]
     pub fn get_incomplete_tasks_time_series(&self, days: u32) -> Vec<(DateTime<Utc>, usize)> {
         let now = Utc::now();
         let mut series = Vec::new();
@@ -175,6 +1334,7 @@ impl TaskManager {
     }
     
     /// Get cumulative completed tasks over time
+    #[must_use = "building a time series has no effect unless the result is used"]
     pub fn get_cumulative_completed_time_series(&self, days: u32) -> Vec<(DateTime<Utc>, usize)> {
         let now = Utc::now();
         let mut series = Vec::new();
@@ -200,7 +1360,72 @@ impl TaskManager {
         series
     }
     
+    /// Number of consecutive days, ending today, with at least one completion
+    ///
+    /// This is 0 until a task has been completed today. The streak in
+    /// [`TaskReport`] uses the same definition.
+    #[must_use = "computing the streak has no effect unless the result is used"]
+    pub fn get_completion_streak(&self) -> u32 {
+        self.get_completion_streak_at(Utc::now())
+    }
+    
+    /// [`TaskManager::get_completion_streak`] as of `now`, with days as UTC
+    /// dates
+    #[must_use = "computing the streak has no effect unless the result is used"]
+    pub fn get_completion_streak_at(&self, now: DateTime<Utc>) -> u32 {
+        let days: HashSet<NaiveDate> =
+            self.tasks.values().filter_map(|task| Some(task.completed_at?.date_naive())).collect();
+        let mut streak = 0;
+        let mut day = Some(now.date_naive());
+        while let Some(date) = day.filter(|date| days.contains(date)) {
+            streak += 1;
+            day = date.pred_opt();
+        }
+        streak
+    }
+    
+    /// Number of tasks created on each weekday, indexed Monday (0) to Sunday (6)
+    #[must_use = "building a histogram has no effect unless the result is used"]
+    pub fn get_tasks_created_by_day_of_week(&self) -> [usize; 7] {
+        let mut counts = [0; 7];
+        for task in self.tasks.values() {
+            counts[task.created_at.weekday().num_days_from_monday() as usize] += 1;
+        }
+        counts
+    }
+    
+    /// Number of tasks completed on each weekday, indexed Monday (0) to Sunday (6)
+    #[must_use = "building a histogram has no effect unless the result is used"]
+    pub fn get_tasks_completed_by_day_of_week(&self) -> [usize; 7] {
+        let mut counts = [0; 7];
+        for completed_at in self.tasks.values().filter_map(|task| task.completed_at) {
+            counts[completed_at.weekday().num_days_from_monday() as usize] += 1;
+        }
+        counts
+    }
+    
+    /// Tasks by the UTC date they were created on, each day ordered by
+    /// creation time
+    #[must_use = "grouping tasks has no effect unless the result is used"]
+    pub fn group_by_day_created(&self) -> BTreeMap<NaiveDate, Vec<&Task>> {
+        group_by_day(self.tasks.values().map(|task| (task.created_at, task)))
+    }
+    
+    /// Completed tasks by the UTC date they were completed on, each day
+    /// ordered by completion time
+    #[must_use = "grouping tasks has no effect unless the result is used"]
+    pub fn group_by_day_completed(&self) -> BTreeMap<NaiveDate, Vec<&Task>> {
+        group_by_day(self.tasks.values().filter_map(|task| Some((task.completed_at?, task))))
+    }
+    
+    /// Number of distinct UTC dates on which at least one task was created
+    #[must_use = "counting days has no effect unless the result is used"]
+    pub fn get_active_days_count(&self) -> usize {
+        self.tasks.values().map(|task| task.created_at.date_naive()).collect::<HashSet<_>>().len()
+    }
+    
     /// Predict completion time for incomplete tasks based on historical data
+    #[must_use = "predictions are computed on demand and not stored"]
     pub fn predict_task_completion_times(&self) -> Vec<(u32, f64)> {
         let avg_completion_time = self.get_average_completion_time_hours().unwrap_or(24.0);
         
@@ -237,6 +1462,58 @@ impl TaskManager {
     }
 }
 
+/// Buckets tasks by the UTC date of their timestamp, ordered by time and
+/// then ID within a day
+fn group_by_day<'a>(entries: impl Iterator<Item = (DateTime<Utc>, &'a Task)>) -> BTreeMap<NaiveDate, Vec<&'a Task>> {
+    let mut entries: Vec<_> = entries.collect();
+    entries.sort_by_key(|(at, task)| (*at, task.id));
+    let mut days: BTreeMap<NaiveDate, Vec<&Task>> = BTreeMap::new();
+    for (at, task) in entries {
+        days.entry(at.date_naive()).or_default().push(task);
+    }
+    days
+}
+
+/// Builds a manager from existing tasks, continuing IDs after the highest one.
+impl FromIterator<Task> for TaskManager {
+    fn from_iter<I: IntoIterator<Item = Task>>(iter: I) -> Self {
+        let mut manager = TaskManager::new();
+        manager.extend(iter);
+        manager
+    }
+}
+
+/// Adds tasks under their own IDs. A task whose ID is already taken replaces
+/// the stored one, and new IDs continue after the highest ID seen.
+impl Extend<Task> for TaskManager {
+    fn extend<I: IntoIterator<Item = Task>>(&mut self, iter: I) {
+        for task in iter {
+            self.next_id = self.next_id.max(u64::from(task.id) + 1);
+            self.tasks.insert(task.id, task);
+        }
+    }
+}
+
+/// `manager[TaskId(id)]` is shorthand for looking a task up by ID.
+///
+/// Indexing panics if no task has that ID; use [`TaskManager::get_task`]
+/// when the task may be missing.
+impl std::ops::Index<TaskId> for TaskManager {
+    type Output = Task;
+    
+    fn index(&self, id: TaskId) -> &Task {
+        self.get_task(id.0).expect("Task not found")
+    }
+}
+
+/// Mutable counterpart of the `Index<TaskId>` impl, with the same panic on
+/// missing IDs.
+impl std::ops::IndexMut<TaskId> for TaskManager {
+    fn index_mut(&mut self, id: TaskId) -> &mut Task {
+        self.tasks.get_mut(&id.0).expect("Task not found")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -252,7 +1529,7 @@ mod tests {
     #[test]
     fn test_task_manager() {
         let mut manager = TaskManager::new();
-        let id = manager.add_task("Test Task".to_string(), "Test Description".to_string());
+        let id = manager.add_task("Test Task".to_string(), "Test Description".to_string()).unwrap();
         
         assert_eq!(manager.get_total_count(), 1);
         assert_eq!(manager.get_completed_count(), 0);
@@ -260,4 +1537,867 @@ mod tests {
         manager.toggle_task(id);
         assert_eq!(manager.get_completed_count(), 1);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_get_due_tasks() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let mut manager = TaskManager::new();
+        for (title, due, completed) in [
+            ("Later", Some("2024-03-20T09:00:00Z"), false),
+            ("Overdue", Some("2024-03-08T09:00:00Z"), false),
+            ("Done", Some("2024-03-09T09:00:00Z"), true),
+            ("Whenever", None, false),
+            ("Soon", Some("2024-03-11T09:00:00Z"), false),
+        ] {
+            let id = manager.add_task(title.to_string(), String::new()).unwrap();
+            let task = manager.tasks.get_mut(&id).unwrap();
+            task.due_date = due.map(at);
+            task.completed = completed;
+        }
+        let titles = |tasks: Vec<&Task>| -> Vec<String> { tasks.iter().map(|t| t.title.clone()).collect() };
+        
+        assert_eq!(titles(manager.get_due_tasks(None)), vec!["Overdue", "Soon", "Later"]);
+        assert_eq!(titles(manager.get_due_tasks(Some(at("2024-03-12T00:00:00Z")))), vec!["Overdue", "Soon"]);
+    }
+    
+    #[test]
+    fn test_get_high_priority_incomplete_tasks() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let task = |id: u32, priority: Priority, created_at: &str| Task {
+            priority,
+            created_at: at(created_at),
+            ..Task::new(id, format!("Task {}", id), String::new())
+        };
+        let mut done = task(5, Priority::High, "2024-03-01T00:00:00Z");
+        done.completed = true;
+        let manager: TaskManager = [
+            task(1, Priority::High, "2024-03-04T00:00:00Z"),
+            task(2, Priority::Medium, "2024-03-01T00:00:00Z"),
+            task(3, Priority::High, "2024-03-02T00:00:00Z"),
+            task(4, Priority::Low, "2024-03-03T00:00:00Z"),
+            done,
+        ]
+        .into_iter()
+        .collect();
+        
+        let ids: Vec<u32> = manager.get_high_priority_incomplete_tasks().iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![3, 1]);
+    }
+
+    #[test]
+    fn test_get_tasks_stale_for_n_days() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let task = |id: u32, created_at: &str| Task {
+            created_at: at(created_at),
+            ..Task::new(id, format!("Task {}", id), String::new())
+        };
+        let mut done = task(4, "2024-01-01T00:00:00Z");
+        done.completed = true;
+        let manager: TaskManager = [
+            task(1, "2024-02-20T00:00:00Z"),
+            task(2, "2024-01-15T00:00:00Z"),
+            // Exactly 30 days old is not more than 30 days
+            task(3, "2024-02-09T12:00:00Z"),
+            done,
+            task(5, "2024-03-10T11:00:00Z"),
+        ]
+        .into_iter()
+        .collect();
+
+        let now = at("2024-03-10T12:00:00Z");
+        let ids = |n| -> Vec<u32> { manager.get_tasks_stale_for_n_days_at(n, now).iter().map(|t| t.id).collect() };
+        assert_eq!(ids(30), vec![2]);
+        assert_eq!(ids(7), vec![2, 3, 1]);
+        assert_eq!(ids(0), vec![2, 3, 1, 5]);
+        assert!(ids(u32::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_priority_parse_and_default() {
+        assert_eq!(Priority::parse("HIGH"), Ok(Priority::High));
+        assert_eq!(Priority::parse("low"), Ok(Priority::Low));
+        assert!(Priority::parse("urgent").is_err());
+        assert_eq!(Priority::High.to_string(), "high");
+    }
+    
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_priority_defaults_in_legacy_json() {
+        let legacy = r#"{"id":1,"title":"Old","description":"","completed":false,"created_at":"2024-03-01T00:00:00Z","completed_at":null}"#;
+        assert_eq!(Task::from_json_str(legacy).unwrap().priority, Priority::Medium);
+    }
+    
+    #[test]
+    fn test_clear_completed() {
+        let mut manager = TaskManager::new();
+        let done = manager.add_task("Done".to_string(), String::new()).unwrap();
+        let open = manager.add_task("Open".to_string(), String::new()).unwrap();
+        manager.toggle_task(done);
+        
+        assert_eq!(manager.clear_completed(), 1);
+        assert!(manager.get_task(done).is_none());
+        assert!(manager.get_task(open).is_some());
+        assert_eq!(manager.clear_completed(), 0);
+    }
+    
+    #[test]
+    fn test_retain() {
+        let mut manager = TaskManager::new();
+        for title in ["Keep", "Drop", "Keep too", "Drop too"] {
+            manager.add_task(title.to_string(), String::new()).unwrap();
+        }
+        
+        assert_eq!(manager.retain(|_| true), 0);
+        assert_eq!(manager.retain(|task| !task.title.starts_with("Drop")), 2);
+        let mut ids: Vec<u32> = manager.get_all_tasks().iter().map(|task| task.id).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(manager.weekly_summary().removed, 2);
+        
+        assert_eq!(manager.retain(|_| false), 2);
+        assert_eq!(manager.get_total_count(), 0);
+    }
+    
+    #[test]
+    fn test_toggle_missing_task() {
+        let mut manager = TaskManager::new();
+        assert_eq!(manager.toggle_task(7), ToggleResult { toggled: false, new_task_id: None });
+    }
+    
+    #[test]
+    fn test_recurring_task_creates_next_occurrence() {
+        let mut manager = TaskManager::new();
+        let id = manager.add_task("Water plants".to_string(), "Balcony".to_string()).unwrap();
+        let due = DateTime::parse_from_rfc3339("2024-01-31T08:00:00Z").unwrap().with_timezone(&Utc);
+        {
+            let task = manager.tasks.get_mut(&id).unwrap();
+            task.due_date = Some(due);
+            task.recurrence = Some(Recurrence::Monthly);
+        }
+        
+        let result = manager.toggle_task(id);
+        assert!(result.toggled);
+        let next_id = result.new_task_id.expect("next occurrence");
+        
+        let next = manager.get_task(next_id).unwrap();
+        assert_eq!(next.title, "Water plants");
+        assert_eq!(next.description, "Balcony");
+        assert!(!next.completed);
+        assert_eq!(next.recurrence, Some(Recurrence::Monthly));
+        assert_eq!(next.due_date.unwrap().to_rfc3339(), "2024-02-29T08:00:00+00:00");
+        assert_eq!(manager.get_total_count(), 2);
+        
+        // Un-completing the original does not spawn another copy
+        assert_eq!(manager.toggle_task(id).new_task_id, None);
+        assert_eq!(manager.get_total_count(), 2);
+        
+        // Nor does completing it again
+        assert!(manager.toggle_task(id).toggled);
+        assert!(manager.get_task(id).unwrap().completed);
+        assert_eq!(manager.get_total_count(), 2);
+        
+        // The next occurrence spawns its own when completed
+        assert!(!manager.get_task(next_id).unwrap().next_occurrence_spawned);
+        assert!(manager.toggle_task(next_id).new_task_id.is_some());
+        assert_eq!(manager.get_total_count(), 3);
+    }
+    
+    #[test]
+    fn test_recurrence_advance() {
+        let from = DateTime::parse_from_rfc3339("2024-03-10T12:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(Recurrence::Daily.advance(from), from + chrono::Duration::days(1));
+        assert_eq!(Recurrence::Weekly.advance(from), from + chrono::Duration::days(7));
+        assert_eq!(Recurrence::Custom(3).advance(from), from + chrono::Duration::days(3));
+        assert_eq!(Recurrence::Monthly.advance(from).to_rfc3339(), "2024-04-10T12:00:00+00:00");
+    }
+    
+    #[test]
+    fn test_recurrence_display() {
+        assert_eq!(Recurrence::Daily.to_string(), "daily");
+        assert_eq!(Recurrence::Monthly.to_string(), "monthly");
+        assert_eq!(Recurrence::Custom(3).to_string(), "every 3 days");
+    }
+    
+    #[test]
+    fn test_tasks_by_day_of_week() {
+        let mut manager = TaskManager::new();
+        // 2024-01-01 was a Monday
+        let monday = DateTime::parse_from_rfc3339("2024-01-01T09:00:00Z").unwrap().with_timezone(&Utc);
+        let sunday = DateTime::parse_from_rfc3339("2024-01-07T18:00:00Z").unwrap().with_timezone(&Utc);
+        
+        for (created_at, completed_at) in [(monday, Some(sunday)), (monday, None), (sunday, None)] {
+            let id = manager.add_task("Task".to_string(), String::new()).unwrap();
+            let task = manager.tasks.get_mut(&id).unwrap();
+            task.created_at = created_at;
+            task.completed = completed_at.is_some();
+            task.completed_at = completed_at;
+        }
+        
+        assert_eq!(manager.get_tasks_created_by_day_of_week(), [2, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(manager.get_tasks_completed_by_day_of_week(), [0, 0, 0, 0, 0, 0, 1]);
+    }
+    
+    #[test]
+    fn test_completion_histogram() {
+        let mut manager = TaskManager::new();
+        assert!(manager.get_completion_histogram(4.0).is_empty());
+        
+        let created_at = DateTime::parse_from_rfc3339("2024-03-10T08:00:00Z").unwrap().with_timezone(&Utc);
+        for minutes in [Some(30), Some(4 * 60), Some(11 * 60 + 59), Some(-5), None] {
+            let id = manager.add_task("Task".to_string(), String::new()).unwrap();
+            let task = manager.tasks.get_mut(&id).unwrap();
+            task.created_at = created_at;
+            task.completed = minutes.is_some();
+            task.completed_at = minutes.map(|minutes| created_at + chrono::Duration::minutes(minutes));
+        }
+        
+        // 4 hours starts the second bucket, and the third is the slowest task's
+        assert_eq!(manager.get_completion_histogram(4.0), vec![(0.0, 2), (4.0, 1), (8.0, 1)]);
+        assert_eq!(manager.get_completion_histogram(24.0), vec![(0.0, 4)]);
+        assert_eq!(manager.get_completion_histogram(1.5)[1], (1.5, 0));
+        for bucket_hours in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(manager.get_completion_histogram(bucket_hours).is_empty(), "{}", bucket_hours);
+        }
+        
+        // Too narrow for the slowest task: the last bucket takes the overflow
+        let narrow = manager.get_completion_histogram(1e-6);
+        assert_eq!(narrow.len(), MAX_HISTOGRAM_BUCKETS);
+        assert_eq!(narrow[0], (0.0, 1));
+        assert_eq!(narrow[MAX_HISTOGRAM_BUCKETS - 1].1, 3);
+        assert_eq!(narrow.iter().map(|(_, count)| count).sum::<usize>(), 4);
+    }
+    
+    #[test]
+    fn test_group_by_day() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let mut manager = TaskManager::new();
+        assert!(manager.group_by_day_created().is_empty());
+        assert_eq!(manager.get_active_days_count(), 0);
+        
+        for (created_at, completed_at) in [
+            ("2024-03-09T18:00:00Z", Some("2024-03-10T08:00:00Z")),
+            ("2024-03-01T08:00:00Z", None),
+            ("2024-03-09T07:00:00Z", Some("2024-03-10T07:00:00Z")),
+            ("2024-03-09T23:59:59Z", None),
+        ] {
+            let id = manager.add_task("Task".to_string(), String::new()).unwrap();
+            let task = manager.tasks.get_mut(&id).unwrap();
+            task.created_at = at(created_at);
+            task.completed = completed_at.is_some();
+            task.completed_at = completed_at.map(at);
+        }
+        
+        let ids = |tasks: &Vec<&Task>| tasks.iter().map(|task| task.id).collect::<Vec<_>>();
+        let created = manager.group_by_day_created();
+        assert_eq!(created.keys().copied().collect::<Vec<_>>(), vec![date("2024-03-01"), date("2024-03-09")]);
+        assert_eq!(ids(&created[&date("2024-03-01")]), vec![2]);
+        assert_eq!(ids(&created[&date("2024-03-09")]), vec![3, 1, 4]);
+        
+        let completed = manager.group_by_day_completed();
+        assert_eq!(completed.len(), 1);
+        assert_eq!(ids(&completed[&date("2024-03-10")]), vec![3, 1]);
+        assert_eq!(manager.get_active_days_count(), 2);
+    }
+    
+    #[test]
+    fn test_report() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let mut manager = TaskManager::new();
+        for (created_at, completed_at) in [
+            ("2024-03-01T08:00:00Z", Some("2024-03-08T10:00:00Z")),
+            ("2024-03-07T08:00:00Z", Some("2024-03-09T20:00:00Z")),
+            ("2024-03-09T08:00:00Z", Some("2024-03-09T14:00:00Z")),
+            ("2024-03-09T09:00:00Z", None),
+        ] {
+            let id = manager.add_task("Task".to_string(), String::new()).unwrap();
+            let task = manager.tasks.get_mut(&id).unwrap();
+            task.created_at = at(created_at);
+            task.completed = completed_at.is_some();
+            task.completed_at = completed_at.map(at);
+        }
+        
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let report = manager.get_report(at("2024-03-10T12:00:00Z"), 5, utc);
+        assert_eq!((report.total, report.completed, report.pending), (4, 3, 1));
+        assert_eq!(report.completion_rate, 0.75);
+        assert_eq!(report.median_completion_hours, Some(60.0));
+        assert_eq!(report.velocity, 0.6);
+        // Nothing completed on the 10th yet, so there is no streak
+        assert_eq!(report.current_streak, 0);
+        let counts: Vec<usize> = report.completions_per_day.iter().map(|d| d.count).collect();
+        assert_eq!(counts, vec![0, 0, 1, 2, 0]);
+        assert_eq!(report.completions_per_day[0].date.to_string(), "2024-03-06");
+        let evening_before = manager.get_report(at("2024-03-09T22:00:00Z"), 5, utc);
+        assert_eq!(evening_before.current_streak, 2);
+        
+        // Four hours ahead, the 20:00 UTC completion lands on the 10th
+        let plus_four = FixedOffset::east_opt(4 * 3600).unwrap();
+        let report = manager.get_report(at("2024-03-10T12:00:00Z"), 5, plus_four);
+        let counts: Vec<usize> = report.completions_per_day.iter().map(|d| d.count).collect();
+        assert_eq!(counts, vec![0, 0, 1, 1, 1]);
+        // The streak counts UTC days whatever the offset
+        assert_eq!(report.current_streak, 0);
+        
+        let oldest = report.oldest_incomplete.unwrap();
+        assert_eq!((oldest.id, oldest.age_hours), (4, 27.0));
+        assert!(report.top_tags.is_empty());
+    }
+    
+    #[test]
+    fn test_report_top_tags() {
+        let mut manager = TaskManager::new();
+        for (tags, completed) in [
+            (&["work", "urgent"][..], true),
+            (&["work", "home"][..], false),
+            (&["work", "b", "a"][..], false),
+            (&["home", "c", "d"][..], false),
+        ] {
+            let id = manager.add_task("Task".to_string(), String::new()).unwrap();
+            let task = manager.get_task_mut(id).unwrap();
+            for tag in tags {
+                task.add_tag(tag).unwrap();
+            }
+            task.completed = completed;
+        }
+        
+        let report = manager.get_report(Utc::now(), 1, FixedOffset::east_opt(0).unwrap());
+        let top: Vec<(&str, usize)> = report.top_tags.iter().map(|t| (t.tag.as_str(), t.count)).collect();
+        assert_eq!(top, vec![("work", 3), ("home", 2), ("a", 1), ("b", 1), ("c", 1)]);
+        assert_eq!(report.oldest_incomplete.unwrap().id, 2);
+    }
+    
+    #[test]
+    fn test_completion_streak() {
+        fn complete_days_ago(manager: &mut TaskManager, days: i64) {
+            let noon_today = Utc::now().date_naive().and_hms_opt(12, 0, 0).unwrap().and_utc();
+            let id = manager.add_task("Task".to_string(), String::new()).unwrap();
+            let task = manager.tasks.get_mut(&id).unwrap();
+            task.completed = true;
+            task.completed_at = Some(noon_today - chrono::Duration::days(days));
+        }
+        
+        let mut manager = TaskManager::new();
+        // Yesterday and the day before, then a gap on day 3
+        for days in [1, 1, 2, 4] {
+            complete_days_ago(&mut manager, days);
+        }
+        assert_eq!(manager.get_completion_streak(), 0);
+        
+        complete_days_ago(&mut manager, 0);
+        assert_eq!(manager.get_completion_streak(), 3);
+        
+        // Days are UTC dates: 23:30 on the 9th is still the 9th
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let mut manager = TaskManager::new();
+        for completed_at in ["2024-03-08T01:00:00Z", "2024-03-09T23:30:00Z"] {
+            let id = manager.add_task("Task".to_string(), String::new()).unwrap();
+            manager.tasks.get_mut(&id).unwrap().completed_at = Some(at(completed_at));
+        }
+        assert_eq!(manager.get_completion_streak_at(at("2024-03-09T23:59:00Z")), 2);
+        assert_eq!(manager.get_completion_streak_at(at("2024-03-10T00:00:00Z")), 0);
+    }
+    
+    #[test]
+    fn test_merge_manager() {
+        let manager_of = |ids: &[u32]| -> TaskManager {
+            ids.iter().map(|&id| Task::new(id, format!("Task {}", id), String::new())).collect()
+        };
+        let mut mine = manager_of(&[1, 2, 3, 4, 5]);
+        let mut theirs = manager_of(&[5, 6, 7, 8, 9]);
+        theirs.get_task_mut(5).unwrap().title = "Theirs".to_string();
+        // An ID handed out and then removed is not reused after the merge
+        let removed = theirs.add_task("Removed".to_string(), String::new()).unwrap();
+        theirs.remove_task(removed);
+
+        let result = mine.merge_manager(theirs);
+        assert_eq!(result, TaskMergeResult { added: 4, skipped_ids: vec![5] });
+        assert_eq!(mine.get_total_count(), 9);
+        assert_eq!(mine.get_task(5).unwrap().title, "Task 5");
+        assert_eq!(mine.get_task(9).unwrap().title, "Task 9");
+        assert_eq!(mine.add_task("Next".to_string(), String::new()).unwrap(), removed + 1);
+    }
+    
+    #[test]
+    fn test_merge_strategies() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let task = |id: u32, title: &str, created_at: &str| Task {
+            created_at: at(created_at),
+            ..Task::new(id, title.to_string(), String::new())
+        };
+        let existing = || -> TaskManager {
+            [task(1, "Old", "2024-03-05T00:00:00Z"), task(2, "Current", "2024-03-05T00:00:00Z")]
+                .into_iter()
+                .collect()
+        };
+        let incoming = || vec![
+            task(1, "Newer", "2024-03-06T00:00:00Z"),
+            task(2, "Stale", "2024-03-01T00:00:00Z"),
+            task(7, "Fresh", "2024-03-01T00:00:00Z"),
+        ];
+        
+        let mut manager = existing();
+        assert_eq!(manager.conflicting_ids(&incoming()), vec![1, 2]);
+        let summary = manager.merge(incoming(), MergeStrategy::KeepNewest).unwrap();
+        assert_eq!(summary, MergeSummary { added: 1, replaced: 1, skipped: 1 });
+        assert_eq!(manager[TaskId(1)].title, "Newer");
+        assert_eq!(manager[TaskId(2)].title, "Current");
+        assert_eq!(manager.add_task("Next".to_string(), String::new()).unwrap(), 8);
+        
+        let mut manager = existing();
+        let summary = manager.merge(incoming(), MergeStrategy::KeepBoth).unwrap();
+        assert_eq!(summary, MergeSummary { added: 3, replaced: 0, skipped: 0 });
+        let mut titles: Vec<(u32, &str)> = manager.get_all_tasks().iter()
+            .map(|t| (t.id, t.title.as_str()))
+            .collect();
+        titles.sort();
+        assert_eq!(titles, vec![(1, "Old"), (2, "Current"), (3, "Newer"), (4, "Stale"), (7, "Fresh")]);
+    }
+    
+    #[test]
+    fn test_ids_near_u32_max() {
+        let task = |id: u32| Task::new(id, format!("Task {}", id), String::new());
+        
+        // Renumbering the second task 1 would need an ID past u32::MAX
+        let mut manager = TaskManager::new();
+        let result = manager.merge(vec![task(u32::MAX - 1), task(1), task(1)], MergeStrategy::KeepBoth);
+        assert!(result.unwrap_err().contains("too few IDs"));
+        assert_eq!(manager.get_total_count(), 0);
+        
+        let mut manager: TaskManager = [task(u32::MAX - 1)].into_iter().collect();
+        let id = manager.add_task("Last".to_string(), String::new()).unwrap();
+        assert_eq!(id, u32::MAX);
+        assert_eq!(manager.add_task("Past the end".to_string(), String::new()).unwrap_err(), "every task ID is in use");
+        assert!(manager.add_task_from(task(0)).is_err());
+        assert!(manager.merge(vec![task(1)], MergeStrategy::KeepBoth).is_err());
+        let summary = manager.merge(vec![task(1)], MergeStrategy::KeepNewest).unwrap();
+        assert_eq!(summary, MergeSummary { added: 1, replaced: 0, skipped: 0 });
+        
+        // A recurring task is still completed, without a next occurrence
+        manager.get_task_mut(id).unwrap().recurrence = Some(Recurrence::Daily);
+        assert_eq!(manager.toggle_task(id), ToggleResult { toggled: true, new_task_id: None });
+        
+        let mut mine: TaskManager = [task(1)].into_iter().collect();
+        mine.merge_manager([task(u32::MAX)].into_iter().collect());
+        assert_eq!(mine.get_total_count(), 2);
+        assert!(mine.add_task("Next".to_string(), String::new()).is_err());
+    }
+    
+    #[test]
+    fn test_tags() {
+        let mut task = Task::new(1, "Pack".to_string(), String::new());
+        assert_eq!(task.add_tag(" travel "), Ok(true));
+        assert_eq!(task.add_tag("Travel"), Ok(false));
+        assert_eq!(task.add_tag("urgent"), Ok(true));
+        assert!(task.add_tag("  ").is_err());
+        assert!(task.add_tag("a,b").unwrap_err().contains("comma"));
+        assert_eq!(task.tags, vec!["travel", "urgent"]);
+        assert!(task.has_tag("URGENT"));
+        assert_eq!(task.clone_as_new(2).tags, task.tags);
+        
+        assert!(task.remove_tag("TRAVEL"));
+        assert!(!task.remove_tag("travel"));
+        assert_eq!(task.tags, vec!["urgent"]);
+        
+        let old: Task = serde_json::from_str(
+            r#"{"id":3,"title":"Old","description":"","completed":false,"created_at":"2024-03-01T08:00:00Z","completed_at":null}"#,
+        ).unwrap();
+        assert!(old.tags.is_empty());
+    }
+    
+    #[test]
+    fn test_task_summary() {
+        let mut manager = TaskManager::new();
+        let id = manager.add_task("Write report".to_string(), "x".repeat(10_000)).unwrap();
+        manager.get_task_mut(id).unwrap().priority = Priority::High;
+        
+        let summary = TaskSummary::from(&manager[TaskId(id)]);
+        assert_eq!((summary.id, summary.title.as_str(), summary.completed), (id, "Write report", false));
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["priority"], "High");
+        assert!(json["due_date"].is_null());
+        assert!(json.get("description").is_none(), "{}", json);
+        assert!(json.to_string().len() < 200);
+    }
+    
+    #[test]
+    fn test_task_spec() {
+        let spec = |json: &str| TaskSpec::from_json(json).and_then(TaskSpec::into_task);
+        let task = spec(
+            r#"{"title":"File taxes","priority":"High","due_date":"2024-04-15T09:00:00+02:00","tags":["admin"],"estimated_hours":2}"#,
+        )
+        .unwrap();
+        assert_eq!(task.priority, Priority::High);
+        assert_eq!(task.due_date.unwrap().to_rfc3339(), "2024-04-15T07:00:00+00:00");
+        assert_eq!(task.tags, vec!["admin"]);
+        assert_eq!(task.estimated_hours, Some(2.0));
+        assert_eq!(spec(r#"{"title":"Minimal"}"#).unwrap().priority, Priority::Medium);
+        
+        assert!(spec(r#"{"title":" "}"#).unwrap_err().contains("empty"));
+        assert!(spec(r#"{"title":"T","priority":"urgent"}"#).unwrap_err().contains("unknown priority"));
+        assert!(spec(r#"{"title":"T","due_date":"tomorrow"}"#).unwrap_err().contains("invalid due date 'tomorrow'"));
+        assert!(spec(r#"{"title":"T","tags":[""]}"#).unwrap_err().contains("empty"));
+        assert!(spec(r#"{"title":"T","estimated_hours":0}"#).unwrap_err().contains("positive"));
+        assert!(spec(r#"{"title":"T","colour":"red"}"#).unwrap_err().starts_with("invalid task"));
+        
+        let mut manager = TaskManager::new();
+        manager.add_task("First".to_string(), String::new()).unwrap();
+        let id = manager.add_task_from(spec(r#"{"title":"Second"}"#).unwrap()).unwrap();
+        assert_eq!((id, manager[TaskId(id)].id), (2, 2));
+        assert_eq!(manager.add_task("Third".to_string(), String::new()).unwrap(), 3);
+    }
+    
+    #[test]
+    fn test_task_durations() {
+        let created_at = DateTime::parse_from_rfc3339("2024-03-01T08:00:00Z").unwrap().with_timezone(&Utc);
+        let mut task = Task {
+            created_at,
+            ..Task::new(1, "Plan trip".to_string(), String::new())
+        };
+        assert_eq!(task.time_to_complete(), None);
+        let now = DateTime::parse_from_rfc3339("2024-03-01T10:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(task.elapsed_since_creation_at(now), chrono::Duration::minutes(150));
+        assert_eq!(task.elapsed_since_creation_at(created_at), chrono::Duration::zero());
+        assert_eq!(crate::utils::format_duration_human(task.elapsed_since_creation_at(now)), "2 hours ago");
+        
+        task.completed = true;
+        task.completed_at = Some(created_at + chrono::Duration::minutes(90));
+        assert_eq!(task.time_to_complete(), Some(chrono::Duration::minutes(90)));
+    }
+    
+    #[test]
+    fn test_weekly_summary() {
+        let now = DateTime::parse_from_rfc3339("2024-03-15T12:00:00Z").unwrap().with_timezone(&Utc);
+        let task = |id: u32, days_ago: i64, hours_taken: Option<i64>| {
+            let created_at = now - chrono::Duration::days(days_ago);
+            Task {
+                created_at,
+                completed: hours_taken.is_some(),
+                completed_at: hours_taken.map(|hours| created_at + chrono::Duration::hours(hours)),
+                ..Task::new(id, format!("Task {}", id), String::new())
+            }
+        };
+        let mut manager: TaskManager = [
+            task(1, 2, Some(4)),
+            task(2, 3, Some(8)),
+            task(3, 1, None),
+            task(4, 1, None),
+            // Created before the week, completed during it
+            task(5, 10, Some(24 * 9)),
+            // Entirely before the week
+            task(6, 20, Some(1)),
+        ]
+        .into_iter()
+        .collect();
+        manager.deletion_log = vec![
+            (TaskId(7), now - chrono::Duration::days(1)),
+            (TaskId(8), now - chrono::Duration::days(8)),
+        ];
+        
+        let summary = manager.weekly_summary_at(now);
+        assert_eq!(summary.created, 4);
+        assert_eq!(summary.completed, 3);
+        assert_eq!(summary.removed, 1);
+        assert_eq!(summary.completion_rate, 0.5);
+        assert!((summary.avg_completion_hours - (4.0 + 8.0 + 216.0) / 3.0).abs() < 1e-9);
+        
+        let empty = TaskManager::new().weekly_summary_at(now);
+        assert_eq!((empty.created, empty.completion_rate, empty.avg_completion_hours), (0, 0.0, 0.0));
+    }
+    
+    #[test]
+    fn test_removals_are_logged() {
+        let mut manager = TaskManager::new();
+        let kept = manager.add_task("Kept".to_string(), String::new()).unwrap();
+        let removed = manager.add_task("Removed".to_string(), String::new()).unwrap();
+        let done = manager.add_task("Done".to_string(), String::new()).unwrap();
+        manager.toggle_task(done);
+        
+        assert!(manager.remove_task(removed));
+        assert!(!manager.remove_task(removed));
+        assert_eq!(manager.clear_completed(), 1);
+        let logged: Vec<TaskId> = manager.deletion_log.iter().map(|(id, _)| *id).collect();
+        assert_eq!(logged, vec![TaskId(removed), TaskId(done)]);
+        assert_eq!(manager.weekly_summary().removed, 2);
+        assert!(manager.get_task(kept).is_some());
+    }
+    
+    #[test]
+    fn test_estimation_accuracy() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let task = |id: u32, estimate: Option<f64>, hours_taken: Option<i64>| Task {
+            created_at: at("2024-03-01T08:00:00Z"),
+            completed: hours_taken.is_some(),
+            completed_at: hours_taken.map(|hours| at("2024-03-01T08:00:00Z") + chrono::Duration::hours(hours)),
+            estimated_hours: estimate,
+            ..Task::new(id, format!("Task {}", id), String::new())
+        };
+        let mut manager: TaskManager = [
+            task(1, Some(4.0), Some(5)),
+            task(2, Some(10.0), Some(8)),
+            task(3, Some(2.0), Some(2)),
+            task(4, Some(3.0), None),
+            task(5, None, Some(1)),
+            task(6, Some(0.0), Some(1)),
+        ]
+        .into_iter()
+        .collect();
+        
+        let ids = |tasks: Vec<&Task>| -> Vec<u32> { tasks.iter().map(|task| task.id).collect() };
+        assert_eq!(ids(manager.get_tasks_with_estimates()), vec![1, 2, 3, 4, 6]);
+        assert_eq!(ids(manager.get_overestimated_tasks()), vec![1, 6]);
+        // (0.25 + 0.2 + 0.0) / 3
+        let mape = manager.get_estimation_accuracy().unwrap();
+        assert!((mape - 0.15).abs() < 1e-9, "{}", mape);
+        
+        manager.remove_task(1);
+        manager.remove_task(2);
+        manager.remove_task(3);
+        assert_eq!(manager.get_estimation_accuracy(), None);
+    }
+    
+    #[test]
+    fn test_import_from_csv() {
+        let header = "id,title,description,completed,created_at,completed_at,due_date,recurrence,priority,estimated_hours,tags";
+        let mut manager = TaskManager::new();
+        manager.add_task("Existing".to_string(), String::new()).unwrap();
+        
+        let csv = format!("{}\n1,Imported,,false,2024-03-01T08:00:00Z,,,,high,,\n5,Other,,true,2024-03-01T08:00:00Z,2024-03-02T08:00:00Z,,,low,1.5,\"home,garden\"\n", header);
+        let summary = manager.import_from_csv(&csv).unwrap();
+        assert_eq!(summary, MergeSummary { added: 2, replaced: 0, skipped: 0 });
+        assert_eq!(manager[TaskId(1)].title, "Existing");
+        assert_eq!(manager[TaskId(2)].title, "Imported");
+        assert!(manager[TaskId(5)].completed);
+        assert_eq!(manager[TaskId(2)].priority, Priority::High);
+        assert_eq!(manager[TaskId(5)].estimated_hours, Some(1.5));
+        assert_eq!(manager[TaskId(5)].tags, vec!["home", "garden"]);
+        
+        for bad in ["", "id,title\n", "\"unterminated", &format!("{}\n4294967295,Max,,false,2024-03-01T08:00:00Z,,,,medium,\n", header)] {
+            assert!(manager.import_from_csv(bad).is_err(), "{:?}", bad);
+        }
+        assert_eq!(manager.get_total_count(), 3);
+    }
+    
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_import_todoist() {
+        let mut manager = TaskManager::new();
+        manager.add_task("Existing".to_string(), String::new()).unwrap();
+        let summary = manager.import_todoist(include_str!("../tests/fixtures/todoist.json")).unwrap();
+        assert_eq!(summary, ImportSummary { imported: 3, skipped: 2 });
+        assert_eq!(summary.to_string(), "Imported 3 tasks (2 skipped)");
+
+        let milk = &manager[TaskId(2)];
+        assert_eq!((milk.title.as_str(), milk.description.as_str()), ("Buy Milk", "Oat, not dairy"));
+        assert_eq!(milk.created_at, utc("2024-03-01T08:25:05Z"));
+        assert!(!milk.completed && milk.completed_at.is_none());
+        assert_eq!(milk.tags, vec!["Food", "Shopping"]);
+
+        let taxes = &manager[TaskId(3)];
+        assert!(taxes.completed);
+        assert_eq!(taxes.completed_at, Some(utc("2024-03-05T17:31:05Z")));
+        assert_eq!(taxes.tags, vec!["admin"]);
+
+        // The older export layout, with notes and numeric label IDs
+        let plumber = &manager[TaskId(4)];
+        assert_eq!(plumber.description, "Leak under the sink\n\nAsk about the boiler too");
+        assert_eq!(plumber.created_at, utc("2014-09-26T08:25:05Z"));
+        assert_eq!(plumber.completed_at, Some(utc("2014-09-27T11:02:44Z")));
+        assert!(plumber.tags.is_empty());
+    }
+
+    #[test]
+    fn test_import_ticktick() {
+        let mut manager = TaskManager::new();
+        let summary = manager.import_ticktick(include_str!("../tests/fixtures/ticktick.json")).unwrap();
+        assert_eq!(summary, ImportSummary { imported: 3, skipped: 1 });
+
+        let passport = &manager[TaskId(1)];
+        assert_eq!(passport.description, "Photos are in the desk drawer");
+        assert_eq!(passport.created_at, utc("2024-03-01T14:02:11Z"));
+        assert!(!passport.completed);
+        assert_eq!(passport.tags, vec!["errands", "admin"]);
+
+        let packing = &manager[TaskId(2)];
+        assert_eq!(packing.description, "Checklist below");
+        assert_eq!(packing.completed_at, Some(utc("2024-03-04T19:45:00Z")));
+
+        // "Won't do" is not done, and a tag with a comma cannot be kept
+        let ukulele = &manager[TaskId(3)];
+        assert!(!ukulele.completed);
+        assert_eq!(ukulele.tags, vec!["music"]);
+    }
+
+    #[test]
+    fn test_import_external_defaults() {
+        let now = utc("2024-03-10T12:00:00Z");
+        let mut manager = TaskManager::new();
+        let items = r#"[
+            {"content": "No dates"},
+            {"content": "Done, no dates", "checked": true},
+            {"content": "Done only", "completed_at": "2024-03-08"},
+            {"content": "Done before created", "added_at": "2024-03-05T00:00:00Z", "completed_at": "2024-03-04T00:00:00Z"},
+            {"content": "Unreadable date", "added_at": "yesterday"},
+            {"content": 42},
+            "not a task"
+        ]"#;
+        let summary = manager.import_external(&TODOIST, items, now).unwrap();
+        assert_eq!(summary, ImportSummary { imported: 5, skipped: 2 });
+        let dates = |id| (manager[TaskId(id)].created_at, manager[TaskId(id)].completed_at);
+        assert_eq!(dates(1), (now, None));
+        assert_eq!(dates(2), (now, Some(now)));
+        assert_eq!(dates(3), (utc("2024-03-08T00:00:00Z"), Some(utc("2024-03-08T00:00:00Z"))));
+        assert_eq!(dates(4), (utc("2024-03-05T00:00:00Z"), Some(utc("2024-03-05T00:00:00Z"))));
+        assert_eq!(dates(5), (now, None));
+
+        for bad in ["", "{\"items\": 3}", "{\"tasks\": []}", "\"text\""] {
+            assert!(manager.import_todoist(bad).unwrap_err().starts_with("not a Todoist export"), "{:?}", bad);
+        }
+        assert!(manager.import_ticktick("{\"items\": []}").unwrap_err().contains("`tasks`"));
+        assert_eq!(manager.get_total_count(), 5);
+    }
+
+    #[test]
+    fn test_index_by_task_id() {
+        let mut manager = TaskManager::new();
+        let id = TaskId(manager.add_task("Draft".to_string(), String::new()).unwrap());
+        assert_eq!(manager[id].title, "Draft");
+        
+        manager[id].title = "Final".to_string();
+        assert_eq!(manager.get_task(id.0).unwrap().title, "Final");
+    }
+    
+    #[test]
+    #[should_panic(expected = "Task not found")]
+    fn test_index_missing_task_panics() {
+        let manager = TaskManager::new();
+        let _ = &manager[TaskId(42)];
+    }
+    
+    #[test]
+    fn test_search() {
+        let mut manager = TaskManager::new();
+        manager.add_task("Write DOCS".to_string(), String::new()).unwrap();
+        manager.add_task("Release".to_string(), "update the docs site".to_string()).unwrap();
+        manager.add_task("Fix bug".to_string(), String::new()).unwrap();
+        
+        let ids: Vec<u32> = manager.search("docs").iter().map(|task| task.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert!(manager.search("missing").is_empty());
+    }
+    
+    #[test]
+    fn test_get_tasks_with_tag() {
+        let mut manager = TaskManager::new();
+        let work = manager.add_task("Report".to_string(), String::new()).unwrap();
+        manager[TaskId(work)].add_tag("Work").unwrap();
+        let home = manager.add_task("Garden".to_string(), String::new()).unwrap();
+        manager[TaskId(home)].add_tag("home").unwrap();
+        let both = manager.add_task("Commute".to_string(), String::new()).unwrap();
+        manager[TaskId(both)].add_tag("home").unwrap();
+        manager[TaskId(both)].add_tag("work").unwrap();
+        
+        let ids = |tag| -> Vec<u32> { manager.get_tasks_with_tag(tag).iter().map(|task| task.id).collect() };
+        assert_eq!(ids("work"), vec![work, both]);
+        assert_eq!(ids(" HOME "), vec![home, both]);
+        assert!(ids("errands").is_empty());
+    }
+    
+    #[test]
+    fn test_get_tasks_filtered() {
+        let now = Utc::now();
+        let mut manager = TaskManager::new();
+        let done = manager.add_task("Done".to_string(), String::new()).unwrap();
+        manager.toggle_task(done);
+        let late = manager.add_task("Late".to_string(), String::new()).unwrap();
+        manager[TaskId(late)].due_date = Some(now - chrono::Duration::hours(1));
+        let later = manager.add_task("Later".to_string(), String::new()).unwrap();
+        manager[TaskId(later)].due_date = Some(now + chrono::Duration::hours(1));
+        
+        let ids = |filter| -> Vec<u32> {
+            manager.get_tasks_filtered(filter, now).iter().map(|task| task.id).collect()
+        };
+        assert_eq!(ids(TaskFilter::All), vec![done, late, later]);
+        assert_eq!(ids(TaskFilter::Active), vec![late, later]);
+        assert_eq!(ids(TaskFilter::Completed), vec![done]);
+        assert_eq!(ids(TaskFilter::Overdue), vec![late]);
+        for filter in [TaskFilter::All, TaskFilter::Active, TaskFilter::Completed, TaskFilter::Overdue] {
+            for task in manager.get_all_tasks() {
+                assert_eq!(filter.matches_summary(&TaskSummary::from(task), now), filter.matches(task, now));
+            }
+        }
+        
+        assert_eq!(TaskFilter::parse("Overdue"), Ok(TaskFilter::Overdue));
+        assert!(TaskFilter::parse("pending").unwrap_err().contains("unknown filter 'pending'"));
+    }
+    
+    #[test]
+    fn test_get_tasks_page() {
+        let now = Utc::now();
+        let mut manager = TaskManager::new();
+        for (i, priority) in [Priority::Low, Priority::High, Priority::Medium, Priority::High].into_iter().enumerate() {
+            let id = manager.add_task(format!("Task {}", i + 1), String::new()).unwrap();
+            manager[TaskId(id)].priority = priority;
+            manager[TaskId(id)].created_at = now - chrono::Duration::hours(10 - i as i64);
+        }
+        manager[TaskId(2)].title = "another task".to_string();
+        manager[TaskId(4)].title = "Another task".to_string();
+        manager.toggle_task(1);
+        manager[TaskId(1)].due_date = Some(now + chrono::Duration::days(2));
+        manager[TaskId(3)].due_date = Some(now + chrono::Duration::days(1));
+        
+        let ids = |offset, limit, order| -> Vec<u32> {
+            manager.get_tasks_page(offset, limit, order).iter().map(|task| task.id).collect()
+        };
+        assert_eq!(ids(0, 10, TaskOrder::Id), vec![1, 2, 3, 4]);
+        assert_eq!(ids(1, 2, TaskOrder::Id), vec![2, 3]);
+        assert_eq!(ids(3, 2, TaskOrder::Id), vec![4]);
+        assert!(ids(4, 2, TaskOrder::Id).is_empty());
+        assert!(ids(0, 0, TaskOrder::Id).is_empty());
+        assert_eq!(ids(0, 10, TaskOrder::Newest), vec![4, 3, 2, 1]);
+        assert_eq!(ids(0, 10, TaskOrder::Due), vec![3, 1, 2, 4]);
+        assert_eq!(ids(0, 10, TaskOrder::Priority), vec![2, 4, 3, 1]);
+        assert_eq!(ids(0, 10, TaskOrder::Oldest), vec![1, 2, 3, 4]);
+        assert_eq!(ids(0, 10, TaskOrder::Title), vec![2, 4, 1, 3]);
+        assert_eq!(ids(0, 10, TaskOrder::IncompleteFirst), vec![2, 3, 4, 1]);
+        
+        assert_eq!(TaskOrder::parse("Priority"), Ok(TaskOrder::Priority));
+        for order in [TaskOrder::Id, TaskOrder::Title, TaskOrder::IncompleteFirst] {
+            assert_eq!(TaskOrder::parse(&order.to_string()), Ok(order));
+        }
+        assert!(TaskOrder::parse("alphabetical").unwrap_err().contains("unknown order 'alphabetical'"));
+    }
+    
+    #[test]
+    fn test_collect_and_extend() {
+        let tasks = vec![
+            Task::new(4, "Four".to_string(), String::new()),
+            Task::new(2, "Two".to_string(), String::new()),
+        ];
+        let mut manager: TaskManager = tasks.into_iter().collect();
+        assert_eq!(manager.get_total_count(), 2);
+        assert_eq!(manager.add_task("Five".to_string(), String::new()).unwrap(), 5);
+        
+        manager.extend([Task::new(9, "Nine".to_string(), String::new())]);
+        assert_eq!(manager[TaskId(9)].title, "Nine");
+        assert_eq!(manager.add_task("Ten".to_string(), String::new()).unwrap(), 10);
+    }
+    
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_round_trip() {
+        let mut task = Task::new(3, "Pay rent".to_string(), "Before the 5th".to_string());
+        task.recurrence = Some(Recurrence::Monthly);
+        
+        let json = task.to_json_string().unwrap();
+        let parsed = Task::from_json_str(&json).unwrap();
+        assert_eq!(parsed.id, 3);
+        assert_eq!(parsed.title, "Pay rent");
+        assert_eq!(parsed.created_at, task.created_at);
+        assert_eq!(parsed.recurrence, Some(Recurrence::Monthly));
+        assert!(Task::from_json_str("{\"id\": 1}").is_err());
+    }
+}
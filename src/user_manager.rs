@@ -1,5 +1,14 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "concurrent")]
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard};
+
+const CSV_HEADER: [&str; 4] = ["id", "name", "email", "active"];
+const BACKUP_PREFIX: &str = "users_";
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
 
 /// Represents a user in the system.
 /// 
@@ -26,6 +35,96 @@ pub struct User {
     pub active: bool,
 }
 
+#[cfg(feature = "json")]
+impl User {
+    /// Serializes the user as a JSON object.
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Parses a user from a JSON object.
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Criteria for [`UserManager::filter`]. Unset criteria match every user,
+/// and a user must match all of the set ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserFilter {
+    /// Only active (`true`) or only inactive (`false`) users
+    pub active: Option<bool>,
+    /// Email domain, such as `example.com`, compared ignoring case
+    pub domain: Option<String>,
+}
+
+impl UserFilter {
+    #[must_use = "checking a user has no effect unless the result is used"]
+    pub fn matches(&self, user: &User) -> bool {
+        let active_ok = self.active.is_none_or(|active| user.active == active);
+        let domain_ok = self.domain.as_ref().is_none_or(|domain| {
+            user.email
+                .rsplit_once('@')
+                .is_some_and(|(_, user_domain)| user_domain.eq_ignore_ascii_case(domain))
+        });
+        active_ok && domain_ok
+    }
+}
+
+/// Outcome of [`UserManager::import_from_csv`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CsvImport {
+    /// Number of users added
+    pub imported: usize,
+    /// One message per suspicious or rejected row, such as
+    /// `Row 3: name is whitespace-only, skipped`
+    pub warnings: Vec<String>,
+}
+
+/// How [`UserManager::export_to_csv_with_options`] writes its output.
+///
+/// The defaults give RFC 4180 CSV that [`UserManager::import_from_csv`]
+/// reads back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvExportOptions {
+    /// Start with an `id,name,email,active` row
+    pub include_header: bool,
+    pub delimiter: char,
+    /// Wraps fields that contain the delimiter, the quote itself or a line
+    /// break; a quote inside a field is doubled
+    pub quote_char: char,
+}
+
+impl Default for CsvExportOptions {
+    fn default() -> Self {
+        CsvExportOptions {
+            include_header: true,
+            delimiter: ',',
+            quote_char: '"',
+        }
+    }
+}
+
+impl CsvExportOptions {
+    fn record(&self, fields: &[&str]) -> String {
+        let quote = self.quote_char.to_string();
+        let escaped: Vec<String> = fields
+            .iter()
+            .map(|field| {
+                if field.contains([self.delimiter, self.quote_char, '\n', '\r']) {
+                    format!("{q}{}{q}", field.replace(&quote, &quote.repeat(2)), q = quote)
+                } else {
+                    field.to_string()
+                }
+            })
+            .collect();
+        escaped.join(&self.delimiter.to_string())
+    }
+}
+
+/// A hook registered with one of the `UserManager::on_user_*` methods.
+type UserCallback = Box<dyn Fn(&User)>;
+
 /// Manages a collection of users with CRUD operations.
 /// 
 /// The UserManager provides functionality to add, retrieve, update, and delete users,
@@ -49,49 +148,119 @@ pub struct User {
 /// assert_eq!(manager.get_user(1), Some(&user));
 /// assert_eq!(manager.count(), 1);
 /// ```
-#[derive(Debug)]
 pub struct UserManager {
     users: Vec<User>,
+    on_added: Vec<UserCallback>,
+    on_deleted: Vec<UserCallback>,
+    on_updated: Vec<UserCallback>,
 }
 
-impl UserManager {
-    pub fn new() -> Self {
-        Self { users: Vec::new() }
+impl fmt::Debug for UserManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserManager")
+            .field("users", &self.users)
+            .field("on_added", &self.on_added.len())
+            .field("on_deleted", &self.on_deleted.len())
+            .field("on_updated", &self.on_updated.len())
+            .finish()
     }
+}
 
-    pub fn add_user(&mut self, user: User) -> Result<(), String> {
-        if self.users.iter().any(|u| u.id == user.id) {
-            return Err(format!("User with ID {} already exists", user.id));
+/// Lists the users as a table, in insertion order, with
+/// [`render_table`](crate::utils::render_table):
+///
+/// ```text
+/// ID  Name   Email              Active
+/// --  -----  -----------------  ------
+/// 1   Alice  alice@example.com  yes
+/// ```
+///
+/// Columns grow to fit their longest value. An empty manager shows
+/// `No users.`
+impl fmt::Display for UserManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.users.is_empty() {
+            return write!(f, "No users.");
         }
-        if user.name.trim().is_empty() {
-            return Err("User name cannot be empty".to_string());
+
+        let rows: Vec<Vec<String>> = self.users.iter()
+            .map(|user| vec![
+                user.id.to_string(),
+                user.name.clone(),
+                user.email.clone(),
+                if user.active { "yes" } else { "no" }.to_string(),
+            ])
+            .collect();
+        let table = crate::utils::render_table(&["ID", "Name", "Email", "Active"], &rows, usize::MAX);
+        write!(f, "{}", table.trim_end_matches('\n'))
+    }
+}
+
+impl UserManager {
+    pub fn new() -> Self {
+        Self {
+            users: Vec::new(),
+            on_added: Vec::new(),
+            on_deleted: Vec::new(),
+            on_updated: Vec::new(),
         }
-        if !crate::utils::validate_email(&user.email) {
-            return Err("Invalid email format".to_string());
+    }
+
+    /// Registers a callback that runs after a user is added.
+    ///
+    /// Callbacks run synchronously, in registration order, and only when the
+    /// mutation succeeds.
+    pub fn on_user_added<F: Fn(&User) + 'static>(&mut self, callback: F) {
+        self.on_added.push(Box::new(callback));
+    }
+
+    /// Registers a callback that runs after a user is deleted, receiving the
+    /// removed user.
+    pub fn on_user_deleted<F: Fn(&User) + 'static>(&mut self, callback: F) {
+        self.on_deleted.push(Box::new(callback));
+    }
+
+    /// Registers a callback that runs after a user is updated, activated or
+    /// deactivated, receiving the user as stored after the change.
+    pub fn on_user_updated<F: Fn(&User) + 'static>(&mut self, callback: F) {
+        self.on_updated.push(Box::new(callback));
+    }
+
+    fn notify(callbacks: &[UserCallback], user: &User) {
+        for callback in callbacks {
+            callback(user);
         }
+    }
+
+    pub fn add_user(&mut self, user: User) -> Result<(), String> {
+        check_new_user(&self.users, &user)?;
         self.users.push(user);
+        Self::notify(&self.on_added, &self.users[self.users.len() - 1]);
         Ok(())
     }
 
+    /// Looks up a user by ID.
+    ///
+    /// Marked `#[must_use]` because a lookup has no side effects: dropping the
+    /// returned `Option` usually means the caller meant to check for existence
+    /// and forgot to act on the answer.
+    #[must_use = "looking up a user has no effect unless the result is used"]
     pub fn get_user(&self, id: u32) -> Option<&User> {
         self.users.iter().find(|u| u.id == id)
     }
 
+    #[must_use = "this returns the users without modifying the manager"]
     pub fn get_users(&self) -> &Vec<User> {
         &self.users
     }
 
     pub fn update_user(&mut self, id: u32, updated_user: User) -> Result<(), String> {
-        if updated_user.name.trim().is_empty() {
-            return Err("User name cannot be empty".to_string());
-        }
-        if !crate::utils::validate_email(&updated_user.email) {
-            return Err("Invalid email format".to_string());
-        }
+        check_fields(&updated_user)?;
 
         match self.users.iter_mut().find(|u| u.id == id) {
             Some(user) => {
                 *user = updated_user;
+                Self::notify(&self.on_updated, user);
                 Ok(())
             }
             None => Err(format!("User with ID {} not found", id)),
@@ -99,28 +268,47 @@ impl UserManager {
     }
 
     pub fn delete_user(&mut self, id: u32) -> Result<(), String> {
-        let initial_len = self.users.len();
-        self.users.retain(|u| u.id != id);
-        
-        if self.users.len() == initial_len {
-            Err(format!("User with ID {} not found", id))
-        } else {
-            Ok(())
+        match self.users.iter().position(|u| u.id == id) {
+            Some(index) => {
+                let user = self.users.remove(index);
+                Self::notify(&self.on_deleted, &user);
+                Ok(())
+            }
+            None => Err(format!("User with ID {} not found", id)),
         }
     }
 
+    #[must_use = "filtering allocates a new Vec and does not change the manager"]
     pub fn get_active_users(&self) -> Vec<&User> {
         self.users.iter().filter(|u| u.active).collect()
     }
 
+    #[must_use = "filtering allocates a new Vec and does not change the manager"]
     pub fn get_inactive_users(&self) -> Vec<&User> {
         self.users.iter().filter(|u| !u.active).collect()
     }
 
+    /// Users whose name or email contains `query`, ignoring case.
+    #[must_use = "searching has no effect unless the result is used"]
+    pub fn search(&self, query: &str) -> Vec<&User> {
+        let query = query.to_lowercase();
+        self.users
+            .iter()
+            .filter(|u| u.name.to_lowercase().contains(&query) || u.email.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// Users matching every criterion in `filter`, in insertion order.
+    #[must_use = "filtering has no effect unless the result is used"]
+    pub fn filter(&self, filter: &UserFilter) -> Vec<&User> {
+        self.users.iter().filter(|u| filter.matches(u)).collect()
+    }
+
     pub fn activate_user(&mut self, id: u32) -> Result<(), String> {
         match self.users.iter_mut().find(|u| u.id == id) {
             Some(user) => {
                 user.active = true;
+                Self::notify(&self.on_updated, user);
                 Ok(())
             }
             None => Err(format!("User with ID {} not found", id)),
@@ -131,6 +319,7 @@ impl UserManager {
         match self.users.iter_mut().find(|u| u.id == id) {
             Some(user) => {
                 user.active = false;
+                Self::notify(&self.on_updated, user);
                 Ok(())
             }
             None => Err(format!("User with ID {} not found", id)),
@@ -140,15 +329,147 @@ impl UserManager {
     pub fn save_to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
         let json = serde_json::to_string_pretty(&self.users)?;
         fs::write(path, json)?;
+        log::debug!("wrote {} users to {}", self.users.len(), path);
         Ok(())
     }
 
     pub fn load_from_file(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
         let content = fs::read_to_string(path)?;
         self.users = serde_json::from_str(&content)?;
+        log::debug!("parsed {} users from {}", self.users.len(), path);
         Ok(())
     }
 
+    /// Writes every user as CSV with the default [`CsvExportOptions`].
+    #[must_use = "this returns the CSV text without writing it anywhere"]
+    pub fn export_to_csv(&self) -> String {
+        self.export_to_csv_with_options(&CsvExportOptions::default())
+    }
+
+    /// Writes every user as CSV, one line per user in insertion order.
+    #[must_use = "this returns the CSV text without writing it anywhere"]
+    pub fn export_to_csv_with_options(&self, opts: &CsvExportOptions) -> String {
+        let mut out = String::new();
+        if opts.include_header {
+            out.push_str(&opts.record(&CSV_HEADER));
+            out.push('\n');
+        }
+        for user in &self.users {
+            let id = user.id.to_string();
+            let active = user.active.to_string();
+            out.push_str(&opts.record(&[&id, &user.name, &user.email, &active]));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Adds the users in CSV text with an `id,name,email,active` header.
+    ///
+    /// Rows are numbered as in a spreadsheet, with the header as row 1. Rows
+    /// with an empty name are skipped silently. Rows that look like mistakes
+    /// are reported in [`CsvImport::warnings`]. A whitespace-only name or a
+    /// user that [`add_user`](Self::add_user) rejects skips the row. An
+    /// `active` value other than `true`/`false`/`1`/`0`/`yes`/`no` imports the
+    /// user as active. Only a missing header or malformed CSV is an error.
+    pub fn import_from_csv(&mut self, csv: &str) -> Result<CsvImport, String> {
+        let mut records = crate::export::parse_csv(csv)?.into_iter();
+        match records.next() {
+            Some(header) if header == CSV_HEADER => {}
+            _ => return Err(format!("CSV must start with the header `{}`", CSV_HEADER.join(","))),
+        }
+
+        let mut report = CsvImport::default();
+        for (index, record) in records.enumerate() {
+            let row = index + 2;
+            let [id, name, email, active] = record.as_slice() else {
+                report.warnings.push(format!(
+                    "Row {}: expected {} fields, found {}, skipped",
+                    row,
+                    CSV_HEADER.len(),
+                    record.len()
+                ));
+                continue;
+            };
+            if name.is_empty() {
+                continue;
+            }
+            if name.trim().is_empty() {
+                report.warnings.push(format!("Row {}: name is whitespace-only, skipped", row));
+                continue;
+            }
+            let Ok(id) = id.trim().parse() else {
+                report.warnings.push(format!("Row {}: invalid id '{}', skipped", row, id));
+                continue;
+            };
+            let active = parse_active(active).unwrap_or_else(|| {
+                report.warnings.push(format!(
+                    "Row {}: active value '{}' is not true/false/1/0/yes/no, imported as active",
+                    row, active
+                ));
+                true
+            });
+
+            let user = User { id, name: name.trim().to_string(), email: email.trim().to_string(), active };
+            match self.add_user(user) {
+                Ok(()) => report.imported += 1,
+                Err(e) => report.warnings.push(format!("Row {}: {}, skipped", row, e)),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Saves the users to `<dir>/users_<timestamp>.json`, stamped with the
+    /// current UTC time, and returns the new file's path.
+    ///
+    /// `dir` is created if needed. A second backup within the same second
+    /// replaces the first.
+    pub fn backup(&self, dir: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        self.backup_at(dir, Utc::now())
+    }
+
+    /// Like [`backup`](Self::backup), stamped with `time` instead of the clock.
+    pub fn backup_at(&self, dir: &Path, time: DateTime<Utc>) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}{}.json", BACKUP_PREFIX, time.format(BACKUP_TIMESTAMP_FORMAT)));
+        let path_str = path.to_str().ok_or("backup path is not valid UTF-8")?;
+        self.save_to_file(path_str)?;
+        Ok(path)
+    }
+
+    /// Deletes all but the `keep` most recent backups in `dir` and returns how
+    /// many were deleted. Other files in `dir` are left alone.
+    pub fn rotate_backups(dir: &Path, keep: usize) -> Result<usize, Box<dyn std::error::Error>> {
+        let backups = Self::list_backups(dir)?;
+        let excess = backups.len().saturating_sub(keep);
+        for path in &backups[..excess] {
+            fs::remove_file(path)?;
+        }
+        log::debug!("removed {} old user backups from {}", excess, dir.display());
+        Ok(excess)
+    }
+
+    /// Loads the most recent backup in `dir`.
+    pub fn restore_from_latest(dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let latest = Self::list_backups(dir)?
+            .pop()
+            .ok_or_else(|| format!("no user backups in {}", dir.display()))?;
+        let mut manager = UserManager::new();
+        manager.load_from_file(latest.to_str().ok_or("backup path is not valid UTF-8")?)?;
+        Ok(manager)
+    }
+
+    /// Backup files in `dir`, oldest first. The timestamp format makes
+    /// filename order chronological.
+    fn list_backups(dir: &Path) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+        let mut backups: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.file_name().and_then(|n| n.to_str()).is_some_and(is_backup_name))
+            .collect();
+        backups.sort();
+        Ok(backups)
+    }
+
+    #[must_use = "counting users has no effect unless the result is used"]
     pub fn count(&self) -> usize {
         self.users.len()
     }
@@ -158,12 +479,202 @@ impl UserManager {
     }
 }
 
+/// What [`UserManager::add_user`] asks of a new user: a free ID, a name and
+/// a valid email.
+fn check_new_user(users: &[User], user: &User) -> Result<(), String> {
+    if users.iter().any(|u| u.id == user.id) {
+        return Err(format!("User with ID {} already exists", user.id));
+    }
+    check_fields(user)
+}
+
+fn check_fields(user: &User) -> Result<(), String> {
+    if user.name.trim().is_empty() {
+        return Err("User name cannot be empty".to_string());
+    }
+    if !crate::utils::validate_email(&user.email) {
+        return Err("Invalid email format".to_string());
+    }
+    Ok(())
+}
+
+fn parse_active(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" => Some(true),
+        "false" | "0" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+fn is_backup_name(name: &str) -> bool {
+    name.strip_prefix(BACKUP_PREFIX)
+        .and_then(|rest| rest.strip_suffix(".json"))
+        .is_some_and(|stamp| chrono::NaiveDateTime::parse_from_str(stamp, BACKUP_TIMESTAMP_FORMAT).is_ok())
+}
+
+/// Two managers are equal when they hold the same users, in any order.
+///
+/// Registered callbacks are not compared.
+impl PartialEq for UserManager {
+    fn eq(&self, other: &Self) -> bool {
+        self.users.len() == other.users.len()
+            && self.users.iter().all(|user| other.get_user(user.id) == Some(user))
+    }
+}
+
 impl Default for UserManager {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Adds each user with [`UserManager::add_user`], silently skipping users
+/// it rejects and users whose ID or email (ignoring case) is already taken.
+impl Extend<User> for UserManager {
+    fn extend<I: IntoIterator<Item = User>>(&mut self, iter: I) {
+        for user in iter {
+            if self.users.iter().any(|u| u.email.eq_ignore_ascii_case(&user.email)) {
+                continue;
+            }
+            let _ = self.add_user(user);
+        }
+    }
+}
+
+/// Builds a manager from users as [`Extend`] adds them, so the first of
+/// several users sharing an ID or email wins.
+impl FromIterator<User> for UserManager {
+    fn from_iter<I: IntoIterator<Item = User>>(iter: I) -> Self {
+        let mut manager = UserManager::new();
+        manager.extend(iter);
+        manager
+    }
+}
+
+/// Yields the users in the order they were added, consuming the manager and
+/// its callbacks.
+impl IntoIterator for UserManager {
+    type Item = User;
+    type IntoIter = std::vec::IntoIter<User>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.users.into_iter()
+    }
+}
+
+/// Yields the users in the order they were added.
+impl<'a> IntoIterator for &'a UserManager {
+    type Item = &'a User;
+    type IntoIter = std::slice::Iter<'a, User>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.users.iter()
+    }
+}
+
+/// Users that threads can share, with the `concurrent` feature. Clones are
+/// handles to the same users, and each call holds a read or write lock only
+/// while it runs, so lookups on several threads proceed together.
+///
+/// Lookups return copies, as a reference into the users could not outlive
+/// the lock; [`read`](Self::read) borrows them all under the lock instead.
+/// Users are checked as [`UserManager`] checks them. There are no
+/// callbacks, and converting a `UserManager` keeps only its users.
+///
+/// A panic while the lock is held does not lock out other threads: every
+/// change is made in one step, so the users are still consistent.
+#[cfg(feature = "concurrent")]
+#[derive(Debug, Clone, Default)]
+pub struct SharedUserManager {
+    users: Arc<RwLock<Vec<User>>>,
+}
+
+#[cfg(feature = "concurrent")]
+impl SharedUserManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn users(&self) -> RwLockReadGuard<'_, Vec<User>> {
+        self.users.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Runs `f` on the users, in the order they were added, while holding
+    /// the read lock.
+    pub fn read<R>(&self, f: impl FnOnce(&[User]) -> R) -> R {
+        f(&self.users())
+    }
+
+    pub fn add_user(&self, user: User) -> Result<(), String> {
+        let mut users = self.users.write().unwrap_or_else(PoisonError::into_inner);
+        check_new_user(&users, &user)?;
+        users.push(user);
+        Ok(())
+    }
+
+    #[must_use = "looking up a user has no effect unless the result is used"]
+    pub fn get_user(&self, id: u32) -> Option<User> {
+        self.users().iter().find(|u| u.id == id).cloned()
+    }
+
+    #[must_use = "this returns the users without modifying the manager"]
+    pub fn get_users(&self) -> Vec<User> {
+        self.users().clone()
+    }
+
+    #[must_use = "filtering allocates a new Vec and does not change the manager"]
+    pub fn get_active_users(&self) -> Vec<User> {
+        self.users().iter().filter(|u| u.active).cloned().collect()
+    }
+
+    #[must_use = "filtering allocates a new Vec and does not change the manager"]
+    pub fn get_inactive_users(&self) -> Vec<User> {
+        self.users().iter().filter(|u| !u.active).cloned().collect()
+    }
+
+    pub fn update_user(&self, id: u32, updated_user: User) -> Result<(), String> {
+        check_fields(&updated_user)?;
+        let mut users = self.users.write().unwrap_or_else(PoisonError::into_inner);
+        match users.iter_mut().find(|u| u.id == id) {
+            Some(user) => {
+                *user = updated_user;
+                Ok(())
+            }
+            None => Err(format!("User with ID {} not found", id)),
+        }
+    }
+
+    pub fn delete_user(&self, id: u32) -> Result<(), String> {
+        let mut users = self.users.write().unwrap_or_else(PoisonError::into_inner);
+        match users.iter().position(|u| u.id == id) {
+            Some(index) => {
+                users.remove(index);
+                Ok(())
+            }
+            None => Err(format!("User with ID {} not found", id)),
+        }
+    }
+
+    #[must_use = "counting users has no effect unless the result is used"]
+    pub fn count(&self) -> usize {
+        self.users().len()
+    }
+
+    /// A [`UserManager`] holding a copy of the users, for saving, exporting
+    /// and everything else the shared handle does not offer.
+    #[must_use = "this returns a copy without modifying the shared users"]
+    pub fn snapshot(&self) -> UserManager {
+        UserManager { users: self.get_users(), ..UserManager::new() }
+    }
+}
+
+#[cfg(feature = "concurrent")]
+impl From<UserManager> for SharedUserManager {
+    fn from(manager: UserManager) -> Self {
+        Self { users: Arc::new(RwLock::new(manager.users)) }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -383,4 +894,384 @@ mod tests {
         manager.clear();
         assert_eq!(manager.count(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_on_user_added_callback() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut manager = UserManager::new();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&seen);
+        manager.on_user_added(move |user| sink.borrow_mut().push(user.clone()));
+
+        let user = create_test_user(1);
+        manager.add_user(user.clone()).unwrap();
+        assert!(manager.add_user(user.clone()).is_err());
+
+        assert_eq!(*seen.borrow(), vec![user]);
+    }
+
+    #[test]
+    fn test_on_user_updated_and_deleted_callbacks() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut manager = UserManager::new();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let updated = Rc::clone(&events);
+        manager.on_user_updated(move |user| {
+            updated.borrow_mut().push(format!("updated {} {}", user.id, user.active))
+        });
+        let deleted = Rc::clone(&events);
+        manager.on_user_deleted(move |user| deleted.borrow_mut().push(format!("deleted {}", user.id)));
+
+        manager.add_user(create_test_user(1)).unwrap();
+        manager.deactivate_user(1).unwrap();
+        manager.delete_user(1).unwrap();
+        assert!(manager.delete_user(1).is_err());
+
+        assert_eq!(*events.borrow(), vec!["updated 1 false", "deleted 1"]);
+    }
+
+    #[test]
+    fn test_equality_ignores_insertion_order() {
+        let mut forward = UserManager::new();
+        let mut backward = UserManager::new();
+        for id in 1..=3 {
+            forward.add_user(create_test_user(id)).unwrap();
+            backward.add_user(create_test_user(4 - id)).unwrap();
+        }
+        assert_eq!(forward, backward);
+
+        backward.deactivate_user(2).unwrap();
+        assert_ne!(forward, backward);
+
+        backward.delete_user(2).unwrap();
+        assert_ne!(forward, backward);
+    }
+
+    #[test]
+    fn test_equality_after_file_round_trip() {
+        let mut original = UserManager::new();
+        original.add_user(create_test_user(2)).unwrap();
+        original.add_user(create_test_user(1)).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("users.json");
+        original.save_to_file(path.to_str().unwrap()).unwrap();
+
+        let mut round_tripped = UserManager::new();
+        round_tripped.load_from_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(original, round_tripped);
+    }
+
+    #[test]
+    fn test_backup_rotation_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = UserManager::new();
+        let start = DateTime::parse_from_rfc3339("2024-03-10T12:00:00Z").unwrap().with_timezone(&Utc);
+        for i in 1..=5 {
+            manager.add_user(create_test_user(i)).unwrap();
+            let path = manager.backup_at(dir.path(), start + chrono::Duration::minutes(i.into())).unwrap();
+            assert!(path.exists());
+        }
+        assert!(UserManager::list_backups(dir.path()).unwrap()[0].ends_with("users_20240310_120100.json"));
+        fs::write(dir.path().join("notes.txt"), "keep me").unwrap();
+
+        assert_eq!(UserManager::rotate_backups(dir.path(), 3).unwrap(), 2);
+        let remaining = UserManager::list_backups(dir.path()).unwrap();
+        assert_eq!(remaining.len(), 3);
+        assert!(remaining[0].ends_with("users_20240310_120300.json"));
+        assert!(dir.path().join("notes.txt").exists());
+        assert_eq!(UserManager::rotate_backups(dir.path(), 3).unwrap(), 0);
+
+        assert_eq!(UserManager::restore_from_latest(dir.path()).unwrap(), manager);
+    }
+
+    #[test]
+    fn test_backup_uses_timestamped_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = UserManager::new().backup(&dir.path().join("backups")).unwrap();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(is_backup_name(name), "{}", name);
+        assert!(UserManager::restore_from_latest(dir.path()).unwrap_err().to_string().contains("no user backups"));
+    }
+
+    #[test]
+    fn test_import_from_csv_warnings() {
+        let csv = "id,name,email,active\n\
+                   1,Ann,ann@example.com,true\n\
+                   2,\"   \",blank@example.com,true\n\
+                   3,,empty@example.com,true\n\
+                   4,Bob,bob@example.com,maybe\n\
+                   5,Cy,cy@example.com,NO\n\
+                   1,Dup,dup@example.com,1\n";
+        let mut manager = UserManager::new();
+        let report = manager.import_from_csv(csv).unwrap();
+
+        assert_eq!(report.imported, 3);
+        assert_eq!(
+            report.warnings,
+            vec![
+                "Row 3: name is whitespace-only, skipped",
+                "Row 5: active value 'maybe' is not true/false/1/0/yes/no, imported as active",
+                "Row 7: User with ID 1 already exists, skipped",
+            ]
+        );
+        assert!(manager.get_user(2).is_none());
+        assert!(manager.get_user(3).is_none());
+        assert!(manager.get_user(4).unwrap().active);
+        assert!(!manager.get_user(5).unwrap().active);
+        assert!(manager.import_from_csv("name,email\n").is_err());
+    }
+
+    #[test]
+    fn test_export_to_csv() {
+        let mut manager = UserManager::new();
+        manager.add_user(create_test_user(1)).unwrap();
+        manager
+            .add_user(User {
+                id: 2,
+                name: "Doe, Jane".to_string(),
+                email: "jane@example.com".to_string(),
+                active: false,
+            })
+            .unwrap();
+
+        let csv = manager.export_to_csv();
+        assert!(csv.starts_with("id,name,email,active\n"), "{}", csv);
+        assert!(csv.ends_with("2,\"Doe, Jane\",jane@example.com,false\n"), "{}", csv);
+
+        let mut imported = UserManager::new();
+        assert_eq!(imported.import_from_csv(&csv).unwrap().imported, 2);
+        assert_eq!(imported.get_user(2).unwrap().name, "Doe, Jane");
+    }
+
+    #[test]
+    fn test_export_to_csv_with_options() {
+        let mut manager = UserManager::new();
+        manager
+            .add_user(User {
+                id: 7,
+                name: "Jane 'JJ' Doe; Jr".to_string(),
+                email: "jane@example.com".to_string(),
+                active: true,
+            })
+            .unwrap();
+
+        let opts = CsvExportOptions { delimiter: ';', ..CsvExportOptions::default() };
+        assert_eq!(
+            manager.export_to_csv_with_options(&opts),
+            "id;name;email;active\n7;\"Jane 'JJ' Doe; Jr\";jane@example.com;true\n"
+        );
+
+        let opts = CsvExportOptions { include_header: false, delimiter: '\t', quote_char: '\'' };
+        assert_eq!(
+            manager.export_to_csv_with_options(&opts),
+            "7\t'Jane ''JJ'' Doe; Jr'\tjane@example.com\ttrue\n"
+        );
+    }
+
+    #[test]
+    fn test_display_table() {
+        let mut manager = UserManager::new();
+        assert_eq!(manager.to_string(), "No users.");
+
+        manager.add_user(create_test_user(1)).unwrap();
+        manager
+            .add_user(User {
+                id: 12,
+                name: "Bo".to_string(),
+                email: "bo@example.org".to_string(),
+                active: false,
+            })
+            .unwrap();
+        assert_eq!(
+            manager.to_string(),
+            "ID  Name         Email              Active\n\
+             --  -----------  -----------------  ------\n\
+             1   Test User 1  test1@example.com  yes\n\
+             12  Bo           bo@example.org     no"
+        );
+    }
+
+    #[test]
+    fn test_display_table_long_email() {
+        let mut manager = UserManager::new();
+        manager.add_user(create_test_user(1)).unwrap();
+        let email = format!("{}@{}example.com", "very.long.mailbox.name".repeat(2), "sub.".repeat(12));
+        manager
+            .add_user(User { id: 2, name: "Zoë".to_string(), email: email.clone(), active: true })
+            .unwrap();
+
+        let table = manager.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        // The long email is not truncated, and the column after it starts
+        // where the rule below the header does
+        assert!(lines[3].contains(&email));
+        let active_column = lines[1].rfind(' ').unwrap() + 1;
+        assert_eq!(lines[0].chars().nth(active_column), Some('A'), "{}", table);
+        assert_eq!(lines[3].chars().nth(active_column), Some('y'), "{}", table);
+    }
+
+    #[test]
+    fn test_search() {
+        let mut manager = UserManager::new();
+        manager.add_user(create_test_user(1)).unwrap();
+        manager
+            .add_user(User {
+                id: 2,
+                name: "Ann Lee".to_string(),
+                email: "ann@example.org".to_string(),
+                active: true,
+            })
+            .unwrap();
+
+        let ids: Vec<u32> = manager.search("ANN").iter().map(|u| u.id).collect();
+        assert_eq!(ids, vec![2]);
+        assert_eq!(manager.search("example").len(), 2);
+        assert!(manager.search("nobody").is_empty());
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut manager = UserManager::new();
+        for (id, email, active) in [
+            (1, "ann@example.com", true),
+            (2, "bob@EXAMPLE.com", false),
+            (3, "cid@other.org", true),
+            (4, "dee@sub.example.com", true),
+        ] {
+            let name = email.split('@').next().unwrap().to_string();
+            manager.add_user(User { id, name, email: email.to_string(), active }).unwrap();
+        }
+        let ids = |filter: UserFilter| -> Vec<u32> { manager.filter(&filter).iter().map(|u| u.id).collect() };
+
+        assert_eq!(ids(UserFilter::default()), vec![1, 2, 3, 4]);
+        assert_eq!(ids(UserFilter { active: Some(false), ..UserFilter::default() }), vec![2]);
+        assert_eq!(
+            ids(UserFilter { domain: Some("example.com".to_string()), ..UserFilter::default() }),
+            vec![1, 2]
+        );
+        assert_eq!(
+            ids(UserFilter { active: Some(true), domain: Some("Example.com".to_string()) }),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_into_iterator() {
+        let mut manager = UserManager::new();
+        for id in [3, 1, 2] {
+            manager.add_user(create_test_user(id)).unwrap();
+        }
+
+        let mut ids = Vec::new();
+        for user in &manager {
+            ids.push(user.id);
+        }
+        assert_eq!(ids, vec![3, 1, 2]);
+
+        let names: Vec<String> = manager.into_iter().map(|u| u.name).collect();
+        assert_eq!(names, vec!["Test User 3", "Test User 1", "Test User 2"]);
+        assert_eq!(UserManager::new().into_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_collect_and_extend() {
+        let mut same_email = create_test_user(4);
+        same_email.email = "TEST1@example.com".to_string();
+        let mut invalid = create_test_user(5);
+        invalid.email = "not an email".to_string();
+        let mut renamed = create_test_user(1);
+        renamed.name = "Second with ID 1".to_string();
+
+        let mut manager: UserManager =
+            vec![create_test_user(1), create_test_user(2), renamed, same_email, invalid].into_iter().collect();
+        assert_eq!(manager.count(), 2);
+        assert_eq!(manager.get_user(1).unwrap().name, "Test User 1");
+
+        manager.extend([create_test_user(2), create_test_user(3)]);
+        let ids: Vec<u32> = manager.into_iter().map(|u| u.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[cfg(feature = "concurrent")]
+    #[test]
+    fn test_shared_manager_across_threads() {
+        use std::thread;
+
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SharedUserManager>();
+
+        let mut manager = UserManager::new();
+        for id in 1..=10 {
+            let mut user = create_test_user(id);
+            user.active = id % 2 == 0;
+            manager.add_user(user).unwrap();
+        }
+        let shared = SharedUserManager::from(manager);
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    for _ in 0..500 {
+                        let active = shared.get_active_users();
+                        assert!(active.len() >= 5);
+                        assert!(active.iter().all(|user| user.active));
+                    }
+                })
+            })
+            .collect();
+        let writer = {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                for id in 100..200 {
+                    shared.add_user(create_test_user(id)).unwrap();
+                }
+            })
+        };
+        writer.join().unwrap();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        assert_eq!(shared.count(), 110);
+        assert_eq!(shared.get_active_users().len(), 105);
+        assert_eq!(shared.get_user(150), Some(create_test_user(150)));
+        assert!(shared.add_user(create_test_user(150)).is_err());
+        assert_eq!(shared.snapshot().get_user(150), Some(&create_test_user(150)));
+    }
+
+    #[cfg(feature = "concurrent")]
+    #[test]
+    fn test_shared_manager_changes() {
+        let shared = SharedUserManager::new();
+        shared.add_user(create_test_user(1)).unwrap();
+        shared.add_user(create_test_user(2)).unwrap();
+
+        let mut renamed = create_test_user(1);
+        renamed.name = "Renamed".to_string();
+        renamed.active = false;
+        shared.update_user(1, renamed.clone()).unwrap();
+        assert_eq!(shared.get_inactive_users(), vec![renamed]);
+        assert!(shared.update_user(1, User { name: " ".to_string(), ..create_test_user(1) }).is_err());
+        assert!(shared.update_user(9, create_test_user(9)).is_err());
+
+        shared.delete_user(2).unwrap();
+        assert!(shared.delete_user(2).is_err());
+        assert_eq!(shared.read(|users| users.iter().map(|u| u.id).collect::<Vec<_>>()), vec![1]);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_round_trip() {
+        let user = create_test_user(7);
+        let json = user.to_json_string().unwrap();
+        assert_eq!(User::from_json_str(&json).unwrap(), user);
+        assert!(User::from_json_str("not json").is_err());
+    }
+}
@@ -1,4 +1,13 @@
 use std::collections::HashMap;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// The largest `n` for which F(n) fits in a `u64`.
+pub const MAX_FIBONACCI_INDEX: u32 = 93;
+
+/// The largest `n` for which F(n) fits in a `u128`.
+pub const MAX_FIBONACCI_U128_INDEX: u32 = 186;
 
 /// Calculates the nth Fibonacci number iteratively.
 /// 
@@ -28,8 +37,9 @@ use std::collections::HashMap;
 /// // Numbers too large will return an error
 /// assert!(calculate_fibonacci(100).is_err());
 /// ```
+#[must_use = "this returns the result of the computation without side effects"]
 pub fn calculate_fibonacci(n: u32) -> Result<u64, String> {
-    if n > 93 {
+    if n > MAX_FIBONACCI_INDEX {
         return Err("Number too large for u64".to_string());
     }
     
@@ -53,8 +63,82 @@ pub fn calculate_fibonacci(n: u32) -> Result<u64, String> {
     }
 }
 
+/// Calculates the nth Fibonacci number as a `u128`.
+/// 
+/// Works like [`calculate_fibonacci`] but accepts `n` up to 186 instead of 93.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use gh_actions::utils::calculate_fibonacci_u128;
+/// 
+/// assert_eq!(calculate_fibonacci_u128(10).unwrap(), 55);
+/// assert_eq!(calculate_fibonacci_u128(100).unwrap(), 354224848179261915075);
+/// assert!(calculate_fibonacci_u128(187).is_err());
+/// ```
+#[must_use = "this returns the result of the computation without side effects"]
+pub fn calculate_fibonacci_u128(n: u32) -> Result<u128, String> {
+    if n > MAX_FIBONACCI_U128_INDEX {
+        return Err("Number too large for u128".to_string());
+    }
+    
+    if n == 0 {
+        return Ok(0);
+    }
+    
+    let (mut a, mut b) = (0u128, 1u128);
+    for _ in 2..=n {
+        let sum = a.checked_add(b).ok_or_else(|| "Overflow occurred".to_string())?;
+        a = b;
+        b = sum;
+    }
+    Ok(b)
+}
+
+/// Calculates F(n) mod `modulus` using fast doubling.
+/// 
+/// Unlike [`calculate_fibonacci`] and [`calculate_fibonacci_u128`] this has
+/// no upper bound on `n`, and runs in O(log n) steps using the identities
+/// F(2k) = F(k) * (2*F(k+1) - F(k)) and F(2k+1) = F(k)^2 + F(k+1)^2.
+/// 
+/// Returns an error if `modulus` is zero.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use gh_actions::utils::calculate_fibonacci_mod;
+/// 
+/// assert_eq!(calculate_fibonacci_mod(10, 1000).unwrap(), 55);
+/// assert_eq!(calculate_fibonacci_mod(1_000_000_000_000, 10).unwrap(), 5);
+/// assert!(calculate_fibonacci_mod(5, 0).is_err());
+/// ```
+#[must_use = "this returns the result of the computation without side effects"]
+pub fn calculate_fibonacci_mod(n: u64, modulus: u64) -> Result<u64, String> {
+    if modulus == 0 {
+        return Err("Modulus must be greater than zero".to_string());
+    }
+    
+    let m = modulus as u128;
+    // (a, b) = (F(k), F(k+1)) for k = the bits of n processed so far
+    let (mut a, mut b) = (0u128, 1 % m);
+    for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+        let even = a * ((2 * b + m - a) % m) % m;
+        // Reduce each square first: their sum could overflow a u128
+        let odd = (a * a % m + b * b % m) % m;
+        if (n >> bit) & 1 == 1 {
+            a = odd;
+            b = (even + odd) % m;
+        } else {
+            a = even;
+            b = odd;
+        }
+    }
+    Ok(a as u64)
+}
+
+#[must_use = "this returns the result of the computation without side effects"]
 pub fn calculate_fibonacci_recursive(n: u32) -> Result<u64, String> {
-    if n > 93 {
+    if n > MAX_FIBONACCI_INDEX {
         return Err("Number too large for u64".to_string());
     }
     
@@ -84,14 +168,59 @@ pub fn calculate_fibonacci_recursive(n: u32) -> Result<u64, String> {
     fib_helper(n, &mut memo)
 }
 
+/// An iterator over the Fibonacci sequence, starting at F(0).
+///
+/// The iterator ends after F(93), the last value that fits in a `u64`, so
+/// it yields exactly `MAX_FIBONACCI_INDEX + 1` items.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::FibonacciIter;
+///
+/// let first: Vec<u64> = FibonacciIter::new().take(8).collect();
+/// assert_eq!(first, vec![0, 1, 1, 2, 3, 5, 8, 13]);
+/// assert_eq!(FibonacciIter::new().count(), 94);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FibonacciIter {
+    current: Option<u64>,
+    next: Option<u64>,
+}
+
+impl FibonacciIter {
+    pub fn new() -> Self {
+        Self { current: Some(0), next: Some(1) }
+    }
+}
+
+impl Default for FibonacciIter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for FibonacciIter {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let value = self.current?;
+        let following = self.next.and_then(|next| value.checked_add(next));
+        self.current = self.next;
+        self.next = following;
+        Some(value)
+    }
+}
+
 /// Validates an email address format.
 /// 
 /// This function performs basic email validation checking for:
-/// - Presence of exactly one '@' symbol
+/// - Presence of exactly one '@' symbol outside a quoted local part
 /// - Non-empty local and domain parts
 /// - Presence of at least one '.' in the domain
 /// - Length constraints and character validation
 /// - Protection against common malformed patterns
+/// - A well-formed local part (see [`is_local_part_valid`])
 /// 
 /// # Arguments
 /// 
@@ -115,38 +244,135 @@ pub fn calculate_fibonacci_recursive(n: u32) -> Result<u64, String> {
 /// assert!(!validate_email("user@"));
 /// assert!(!validate_email("user@@domain.com"));
 /// ```
+///
+/// A bare `validate_email(&input);` statement validates nothing, which is an
+/// easy mistake to make when guarding user input; `#[must_use]` turns it into
+/// a compiler warning.
+#[must_use = "an email is only validated if the returned bool is checked"]
 pub fn validate_email(email: &str) -> bool {
-    if email.len() < 5 || email.len() > 320 {
-        return false;
+    validate_email_detailed(email).is_ok()
+}
+
+/// Validates an email address like [`validate_email`], but explains why an
+/// invalid address was rejected.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::validate_email_detailed;
+///
+/// assert!(validate_email_detailed("user@example.com").is_ok());
+/// assert_eq!(
+///     validate_email_detailed("user@localhost").unwrap_err(),
+///     "domain 'localhost' has no '.'"
+/// );
+/// ```
+pub fn validate_email_detailed(email: &str) -> Result<(), String> {
+    if email.len() < 5 {
+        return Err("too short (minimum 5 characters)".to_string());
+    }
+    if email.len() > 320 {
+        return Err("too long (maximum 320 characters)".to_string());
     }
     
-    let parts: Vec<&str> = email.split('@').collect();
-    if parts.len() != 2 {
-        return false;
+    // Split on the last `@`: a quoted local part may itself contain one.
+    let (local, domain) = email
+        .rsplit_once('@')
+        .ok_or_else(|| "missing '@'".to_string())?;
+    
+    if local.is_empty() {
+        return Err("missing local part before '@'".to_string());
+    }
+    if !is_local_part_valid(local) {
+        return Err(format!("invalid local part '{}'", local));
     }
     
-    let (local, domain) = (parts[0], parts[1]);
+    if domain.is_empty() {
+        return Err("missing domain after '@'".to_string());
+    }
+    if domain.len() > 253 {
+        return Err("domain too long (maximum 253 characters)".to_string());
+    }
+    if !domain.contains('.') {
+        return Err(format!("domain '{}' has no '.'", domain));
+    }
+    if domain.starts_with('.') || domain.ends_with('.') || domain.contains("..") {
+        return Err(format!("domain '{}' has an empty label", domain));
+    }
     
-    if local.is_empty() || local.len() > 64 || domain.is_empty() || domain.len() > 253 {
+    match domain.chars().find(|&c| !(c.is_alphanumeric() || c == '.' || c == '-')) {
+        Some(c) => Err(format!("domain contains invalid character '{}'", c)),
+        None => Ok(()),
+    }
+}
+
+/// Checks the part of an email address before the `@`.
+///
+/// The local part must be 1 to 64 characters of alphanumerics and the
+/// separators `.`, `_`, `-` and `+`. It may not start or end with `.`, and
+/// no two separators may be adjacent, so `user..name` and `user+-tag` are
+/// both rejected.
+///
+/// A local part wrapped in double quotes is instead checked as an RFC 5321
+/// quoted string: it must not be empty and may contain any printable ASCII
+/// character, with `"` and `\` only allowed when escaped by a backslash.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::is_local_part_valid;
+///
+/// assert!(is_local_part_valid("user-tag"));
+/// assert!(!is_local_part_valid("user+-tag"));
+/// assert!(is_local_part_valid("\"user name\""));
+/// assert!(!is_local_part_valid("\"\""));
+/// ```
+#[must_use = "a local part is only validated if the returned bool is checked"]
+pub fn is_local_part_valid(local: &str) -> bool {
+    if local.is_empty() || local.len() > 64 {
         return false;
     }
     
-    if !domain.contains('.') {
+    if local.len() >= 2 && local.starts_with('"') && local.ends_with('"') {
+        return is_quoted_string_valid(&local[1..local.len() - 1]);
+    }
+    
+    if local.starts_with('.') || local.ends_with('.') {
         return false;
     }
     
-    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+    let is_separator = |c: char| matches!(c, '.' | '_' | '-' | '+');
+    if !local.chars().all(|c| c.is_alphanumeric() || is_separator(c)) {
         return false;
     }
     
-    if domain.starts_with('.') || domain.ends_with('.') || domain.contains("..") {
+    let chars: Vec<char> = local.chars().collect();
+    !chars.windows(2).any(|pair| is_separator(pair[0]) && is_separator(pair[1]))
+}
+
+/// Checks the contents of a quoted local part, without the surrounding quotes.
+fn is_quoted_string_valid(content: &str) -> bool {
+    if content.is_empty() {
         return false;
     }
     
-    local.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '_' || c == '-' || c == '+') &&
-    domain.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-')
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if !matches!(chars.next(), Some('"' | '\\')) {
+                    return false;
+                }
+            }
+            '"' => return false,
+            ' '..='~' => {}
+            _ => return false,
+        }
+    }
+    true
 }
 
+#[must_use = "this returns the result of the check without side effects"]
 pub fn is_prime(n: u64) -> bool {
     if n < 2 {
         return false;
@@ -167,6 +393,7 @@ pub fn is_prime(n: u64) -> bool {
     true
 }
 
+#[must_use = "this returns the result of the computation without side effects"]
 pub fn factorial(n: u64) -> Result<u64, String> {
     if n > 20 {
         return Err("Number too large for u64 factorial".to_string());
@@ -182,6 +409,7 @@ pub fn factorial(n: u64) -> Result<u64, String> {
     Ok(result)
 }
 
+#[must_use = "this returns the result of the computation without side effects"]
 pub fn gcd(mut a: u64, mut b: u64) -> u64 {
     while b != 0 {
         let temp = b;
@@ -191,6 +419,7 @@ pub fn gcd(mut a: u64, mut b: u64) -> u64 {
     a
 }
 
+#[must_use = "this returns the result of the computation without side effects"]
 pub fn lcm(a: u64, b: u64) -> Option<u64> {
     if a == 0 || b == 0 {
         return Some(0);
@@ -200,10 +429,28 @@ pub fn lcm(a: u64, b: u64) -> Option<u64> {
     a.checked_div(gcd_val)?.checked_mul(b)
 }
 
+#[must_use = "this returns a new String and leaves the input untouched"]
 pub fn reverse_string(s: &str) -> String {
     s.chars().rev().collect()
 }
 
+/// Reverses `s` by grapheme cluster, so combining marks, emoji sequences and
+/// `\r\n` stay attached to the character they belong to.
+///
+/// [`reverse_string`] reverses code points, which is enough for ASCII but
+/// moves a combining accent onto the wrong letter.
+///
+/// ```
+/// use gh_actions::utils::reverse_string_grapheme_aware;
+///
+/// assert_eq!(reverse_string_grapheme_aware("he\u{301}llo"), "olle\u{301}h");
+/// ```
+#[must_use = "this returns a new String and leaves the input untouched"]
+pub fn reverse_string_grapheme_aware(s: &str) -> String {
+    s.graphemes(true).rev().collect()
+}
+
+#[must_use = "this returns the result of the check without side effects"]
 pub fn is_palindrome(s: &str) -> bool {
     let cleaned: String = s.chars()
         .filter(|c| c.is_alphanumeric())
@@ -213,10 +460,838 @@ pub fn is_palindrome(s: &str) -> bool {
     cleaned == reverse_string(&cleaned)
 }
 
+/// Like [`is_palindrome`], but compares user-perceived characters (grapheme
+/// clusters) instead of code points.
+///
+/// The text is first normalized to NFC, so `ñ` typed as one code point and
+/// as `n` plus a combining tilde count as the same character, and a base
+/// letter keeps its accents when the string is reversed.
+///
+/// ```
+/// use gh_actions::utils::is_palindrome_unicode;
+///
+/// assert!(is_palindrome_unicode("A\u{f1}a"));
+/// assert!(is_palindrome_unicode("An\u{303}a"));
+/// assert!(!is_palindrome_unicode("xn\u{308}nx"));
+/// ```
+#[must_use = "this returns the result of the check without side effects"]
+pub fn is_palindrome_unicode(s: &str) -> bool {
+    let normalized: String = s.nfc().collect();
+    let graphemes: Vec<String> = normalized
+        .graphemes(true)
+        .filter(|g| g.chars().next().is_some_and(char::is_alphanumeric))
+        .map(str::to_lowercase)
+        .collect();
+    graphemes.iter().eq(graphemes.iter().rev())
+}
+
+/// Like [`is_palindrome`], but compares every character as-is, so case,
+/// spaces and punctuation all count.
+#[must_use = "this returns the result of the check without side effects"]
+pub fn is_strict_palindrome(s: &str) -> bool {
+    s.chars().eq(s.chars().rev())
+}
+
+#[must_use = "counting words has no effect unless the result is used"]
 pub fn count_words(text: &str) -> usize {
     text.split_whitespace().count()
 }
 
+/// Turns `s` into a lowercase, URL-friendly slug.
+///
+/// Runs of anything other than letters and digits become a single `-`, with
+/// none left at either end.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::slugify;
+///
+/// assert_eq!(slugify("Hello, World!"), "hello-world");
+/// assert_eq!(slugify("  Rust -- 2024 "), "rust-2024");
+/// ```
+#[must_use = "this returns a new String and leaves the input untouched"]
+pub fn slugify(s: &str) -> String {
+    let mut slug = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+/// Whether `s` is a Rust-style identifier: an ASCII letter or `_`, then
+/// ASCII letters, digits or `_`, at most 255 characters in all.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::is_valid_identifier;
+///
+/// assert!(is_valid_identifier("_hello123"));
+/// assert!(!is_valid_identifier("123bad"));
+/// ```
+#[must_use = "an identifier is only validated if the returned bool is checked"]
+pub fn is_valid_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    s.len() <= 255
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Splits `s` into lowercase words at anything other than letters and digits,
+/// and where a capital starts a new word: `XMLHttpRequest` gives `xml`,
+/// `http` and `request`.
+fn identifier_words(s: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for part in s.split(|c: char| !c.is_alphanumeric()).filter(|part| !part.is_empty()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit());
+            let ends_acronym =
+                i > 0 && chars[i - 1].is_uppercase() && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if c.is_uppercase() && (after_lower || ends_acronym) && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.extend(c.to_lowercase());
+        }
+        words.push(word);
+    }
+    words
+}
+
+/// Converts CamelCase, or words separated by spaces, dashes or the like,
+/// to snake_case.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::to_snake_case;
+///
+/// assert_eq!(to_snake_case("HelloWorld"), "hello_world");
+/// assert_eq!(to_snake_case("parse HTTPResponse"), "parse_http_response");
+/// ```
+#[must_use = "this returns a new String and leaves the input untouched"]
+pub fn to_snake_case(s: &str) -> String {
+    identifier_words(s).join("_")
+}
+
+/// Converts snake_case, or words separated by spaces, dashes or the like,
+/// to CamelCase with a capital first letter, the reverse of
+/// [`to_snake_case`].
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::to_camel_case;
+///
+/// assert_eq!(to_camel_case("hello_world"), "HelloWorld");
+/// assert_eq!(to_camel_case("user id"), "UserId");
+/// ```
+#[must_use = "this returns a new String and leaves the input untouched"]
+pub fn to_camel_case(s: &str) -> String {
+    identifier_words(s)
+        .iter()
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars.next().into_iter().flat_map(char::to_uppercase).chain(chars)
+        })
+        .collect()
+}
+
+/// Formats a duration as a short, rounded-down human reading such as `45s`,
+/// `20m`, `3h` or `5 days`.
+///
+/// Hours are used up to two days, so "due in 30h" reads better than "1 days".
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::humanize_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(humanize_duration(Duration::from_secs(3 * 3600 + 59)), "3h");
+/// assert_eq!(humanize_duration(Duration::from_secs(50 * 3600)), "2 days");
+/// ```
+#[must_use = "this returns a new String and has no other effect"]
+pub fn humanize_duration(duration: std::time::Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m", seconds / 60),
+        3600..=172_799 => format!("{}h", seconds / 3600),
+        _ => format!("{} days", seconds / 86_400),
+    }
+}
+
+/// How long ago something happened, such as `2 hours ago` or `3 days ago`.
+///
+/// The largest whole unit is used, from minutes up to years. Anything under
+/// a minute, or in the future because of clock skew, is `just now`.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::format_duration_human;
+/// use chrono::Duration;
+///
+/// assert_eq!(format_duration_human(Duration::minutes(150)), "2 hours ago");
+/// assert_eq!(format_duration_human(Duration::days(1)), "1 day ago");
+/// ```
+#[must_use = "this returns a new String and has no other effect"]
+pub fn format_duration_human(d: chrono::Duration) -> String {
+    const UNITS: [(&str, i64); 6] = [
+        ("year", 365 * 86_400),
+        ("month", 30 * 86_400),
+        ("week", 7 * 86_400),
+        ("day", 86_400),
+        ("hour", 3600),
+        ("minute", 60),
+    ];
+    let seconds = d.num_seconds();
+    UNITS
+        .iter()
+        .find(|(_, unit)| seconds >= *unit)
+        .map(|(name, unit)| {
+            let count = seconds / unit;
+            format!("{} {}{} ago", count, name, if count == 1 { "" } else { "s" })
+        })
+        .unwrap_or_else(|| "just now".to_string())
+}
+
+/// Parses a duration written as numbers and units, such as `3 days`,
+/// `1 week` or `2 hours 30 minutes`, adding the parts up.
+///
+/// The units are `seconds`/`secs`/`s`, `minutes`/`mins`/`m`, `hours`/`h`,
+/// `days`/`d` and `weeks`/`w`, in the singular too and in any case. The
+/// space between a number and its unit is optional, so `90s` works.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::parse_duration_human;
+/// use chrono::Duration;
+///
+/// assert_eq!(parse_duration_human("1 day 12 hours"), Ok(Duration::hours(36)));
+/// assert_eq!(parse_duration_human("90 seconds"), Ok(Duration::seconds(90)));
+/// assert!(parse_duration_human("3 fortnights").is_err());
+/// ```
+pub fn parse_duration_human(s: &str) -> Result<chrono::Duration, String> {
+    use chrono::Duration;
+
+    let mut rest = s.trim_start();
+    if rest.is_empty() {
+        return Err("duration cannot be empty".to_string());
+    }
+    let mut total = Duration::zero();
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let (amount, after) = rest.split_at(digits);
+        if amount.is_empty() {
+            return Err(format!("invalid duration '{}': expected a number at '{}'", s, rest));
+        }
+        let amount: i64 = amount.parse().map_err(|_| format!("invalid duration '{}': '{}' is too large", s, amount))?;
+
+        let after = after.trim_start();
+        let letters = after.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(after.len());
+        let (unit, after) = after.split_at(letters);
+        let part = match unit.to_ascii_lowercase().as_str() {
+            "seconds" | "second" | "secs" | "sec" | "s" => Duration::try_seconds(amount),
+            "minutes" | "minute" | "mins" | "min" | "m" => Duration::try_minutes(amount),
+            "hours" | "hour" | "h" => Duration::try_hours(amount),
+            "days" | "day" | "d" => Duration::try_days(amount),
+            "weeks" | "week" | "w" => Duration::try_weeks(amount),
+            "" => return Err(format!("invalid duration '{}': missing unit after {}", s, amount)),
+            _ => {
+                return Err(format!(
+                    "invalid duration '{}': unknown unit '{}', expected seconds, minutes, hours, days or weeks",
+                    s, unit
+                ))
+            }
+        };
+        total = part
+            .and_then(|part| total.checked_add(&part))
+            .ok_or_else(|| format!("invalid duration '{}': too long", s))?;
+        rest = after.trim_start();
+    }
+    Ok(total)
+}
+
+/// Units of [`format_bytes`] and [`parse_bytes`], each 1024 times the last
+const BYTE_UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+/// A byte count for people: `512 B` below 1 KB, then the largest unit up
+/// to TB with two decimal places, such as `1.50 KB`. Units are powers of
+/// 1024.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::format_bytes;
+///
+/// assert_eq!(format_bytes(512), "512 B");
+/// assert_eq!(format_bytes(1536), "1.50 KB");
+/// assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.00 GB");
+/// ```
+#[must_use = "this returns a new String and has no other effect"]
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut exponent = (bytes.ilog2() / 10).min(BYTE_UNITS.len() as u32 - 1);
+    let mut value = bytes as f64 / 1024f64.powi(exponent as i32);
+    // Just under a unit boundary rounds up to 1024.00, which reads better as 1.00 of the next unit
+    if (value * 100.0).round() >= 1024.0 * 100.0 && (exponent as usize) < BYTE_UNITS.len() - 1 {
+        exponent += 1;
+        value /= 1024.0;
+    }
+    format!("{:.2} {}", value, BYTE_UNITS[exponent as usize])
+}
+
+/// Reads a size written by [`format_bytes`], such as `1.50 KB`, back as
+/// bytes, rounding to the nearest byte. Units ignore case, the space before
+/// them is optional and a bare number is a count of bytes.
+///
+/// Two decimal places lose precision, so only sizes that are a whole
+/// number of hundredths of their unit, such as exact multiples of it, come
+/// back unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::parse_bytes;
+///
+/// assert_eq!(parse_bytes("1.50 KB"), Ok(1536));
+/// assert_eq!(parse_bytes("2mb"), Ok(2 * 1024 * 1024));
+/// assert!(parse_bytes("3 PB").is_err());
+/// ```
+pub fn parse_bytes(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let unit_start = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(unit_start);
+    let number = number.trim_end();
+    let value: f64 = match number.parse() {
+        Ok(value) if number.bytes().all(|b| b.is_ascii_digit() || b == b'.') => value,
+        _ => return Err(format!("invalid size '{}': expected a number such as 1.5 KB", s)),
+    };
+    let exponent = if unit.is_empty() {
+        0
+    } else {
+        BYTE_UNITS
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(unit))
+            .ok_or_else(|| format!("invalid size '{}': unknown unit '{}', expected B, KB, MB, GB or TB", s, unit))?
+    };
+    let bytes = (value * 1024f64.powi(exponent as i32)).round();
+    if bytes >= u64::MAX as f64 {
+        return Err(format!("invalid size '{}': too large", s));
+    }
+    Ok(bytes as u64)
+}
+
+/// Shortens `s` to at most `max_width` terminal columns, replacing the cut
+/// tail with `…`.
+///
+/// Widths are measured in display columns, so wide characters such as CJK
+/// ideographs count as two.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::truncate_to_width;
+///
+/// assert_eq!(truncate_to_width("hello world", 8), "hello w…");
+/// assert_eq!(truncate_to_width("short", 8), "short");
+/// ```
+#[must_use = "this returns a new String and leaves the input untouched"]
+pub fn truncate_to_width(s: &str, max_width: usize) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let mut truncated = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if width + char_width > max_width - 1 {
+            break;
+        }
+        truncated.push(c);
+        width += char_width;
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Renders rows as a plain-text table with left-aligned, padded columns.
+///
+/// Each column is as wide as its widest cell, capped at `max_column_width`
+/// display columns; longer cells are truncated with `…`. The header is
+/// followed by a row of dashes and columns are separated by two spaces.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::render_table;
+///
+/// let rows = vec![
+///     vec!["1".to_string(), "Alice".to_string()],
+///     vec!["2".to_string(), "Bob".to_string()],
+/// ];
+/// let table = render_table(&["ID", "Name"], &rows, 40);
+/// assert_eq!(table, "ID  Name\n--  -----\n1   Alice\n2   Bob\n");
+/// ```
+#[must_use = "this returns the rendered table without printing it"]
+pub fn render_table(headers: &[&str], rows: &[Vec<String>], max_column_width: usize) -> String {
+    let cells: Vec<Vec<String>> = std::iter::once(headers.iter().map(|h| h.to_string()).collect())
+        .chain(rows.iter().cloned())
+        .map(|row: Vec<String>| {
+            row.iter()
+                .map(|cell| truncate_to_width(cell, max_column_width))
+                .collect()
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|col| {
+            cells.iter()
+                .filter_map(|row| row.get(col))
+                .map(|cell| cell.width())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut output = String::new();
+    let mut push_row = |row: &[String]| {
+        let line = row.iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - cell.width())))
+            .collect::<Vec<_>>()
+            .join("  ");
+        output.push_str(line.trim_end());
+        output.push('\n');
+    };
+
+    push_row(&cells[0]);
+    push_row(&widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<_>>());
+    for row in &cells[1..] {
+        push_row(row);
+    }
+    output
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes `data` as Base64 with the standard alphabet and `=` padding
+/// (RFC 4648).
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::base64_encode;
+///
+/// assert_eq!(base64_encode(b"Man"), "TWFu");
+/// assert_eq!(base64_encode(b"Ma"), "TWE=");
+/// ```
+#[must_use = "this returns a new String and has no other effect"]
+pub fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let byte = |i: usize| chunk.get(i).copied().unwrap_or(0);
+        let group = u32::from_be_bytes([0, byte(0), byte(1), byte(2)]);
+        // A chunk of n bytes fills n + 1 characters; the rest is padding
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decodes standard, padded Base64 as written by [`base64_encode`].
+///
+/// Fails on characters outside the alphabet, on a length that is not a
+/// multiple of four, on misplaced `=`, and on padding bits that are not zero,
+/// so each byte string has exactly one accepted encoding.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::base64_decode;
+///
+/// assert_eq!(base64_decode("TWFu").unwrap(), b"Man");
+/// assert!(base64_decode("TWF").is_err());
+/// ```
+pub fn base64_decode(s: &str) -> Result<Vec<u8>, String> {
+    let bytes = s.as_bytes();
+    if !bytes.len().is_multiple_of(4) {
+        return Err(format!("invalid Base64 length {}, expected a multiple of 4", bytes.len()));
+    }
+    let padding = bytes.iter().rev().take_while(|&&b| b == b'=').count();
+    if padding > 2 {
+        return Err("invalid Base64 padding".to_string());
+    }
+
+    let mut out = Vec::with_capacity(bytes.len() / 4 * 3);
+    let last = bytes.len().saturating_sub(4);
+    for (start, chunk) in bytes.chunks(4).enumerate().map(|(i, chunk)| (i * 4, chunk)) {
+        let chunk_padding = if start == last { padding } else { 0 };
+        let mut group = 0u32;
+        for (i, &b) in chunk[..4 - chunk_padding].iter().enumerate() {
+            let value = BASE64_ALPHABET
+                .iter()
+                .position(|&c| c == b)
+                .ok_or_else(|| match b {
+                    b'=' => "invalid Base64 padding".to_string(),
+                    _ => {
+                        let c = s[start + i..].chars().next().unwrap_or('?');
+                        format!("invalid Base64 character '{}' at position {}", c, start + i)
+                    }
+                })?;
+            group |= (value as u32) << (18 - 6 * i);
+        }
+        let [_, a, b, c] = group.to_be_bytes();
+        let decoded = &[a, b, c][..3 - chunk_padding];
+        // The bits below the last decoded byte must be zero
+        if group & (0xff_ffff >> (decoded.len() * 8)) != 0 {
+            return Err("invalid Base64 padding".to_string());
+        }
+        out.extend_from_slice(decoded);
+    }
+    Ok(out)
+}
+
+/// Encodes `data` as lowercase hexadecimal, two digits per byte.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::hex_encode;
+///
+/// assert_eq!(hex_encode(&[0xde, 0xad, 0xbe, 0xef]), "deadbeef");
+/// ```
+#[must_use = "this returns a new String and has no other effect"]
+pub fn hex_encode(data: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(data.len() * 2);
+    for &byte in data {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0xf) as usize] as char);
+    }
+    out
+}
+
+/// Decodes hexadecimal in either case. Fails on an odd number of digits or a
+/// character that is not a hex digit.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::hex_decode;
+///
+/// assert_eq!(hex_decode("DEADbeef").unwrap(), vec![0xde, 0xad, 0xbe, 0xef]);
+/// assert!(hex_decode("abc").is_err());
+/// ```
+pub fn hex_decode(s: &str) -> Result<Vec<u8>, String> {
+    let digits = s
+        .char_indices()
+        .map(|(i, c)| c.to_digit(16).ok_or_else(|| format!("invalid hex character '{}' at position {}", c, i)))
+        .collect::<Result<Vec<u32>, String>>()?;
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("invalid hex length {}, expected an even number of digits", digits.len()));
+    }
+    Ok(digits.chunks(2).map(|pair| (pair[0] << 4 | pair[1]) as u8).collect())
+}
+
+/// Sums every byte of `data`, modulo `u32::MAX`.
+///
+/// Catches most accidental changes but not reordered bytes; prefer
+/// [`adler32`] when that matters.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::simple_checksum;
+///
+/// assert_eq!(simple_checksum(b"abc"), 97 + 98 + 99);
+/// ```
+#[must_use = "computing a checksum has no effect unless the result is used"]
+pub fn simple_checksum(data: &[u8]) -> u32 {
+    let modulus = u64::from(u32::MAX);
+    let sum = data.iter().fold(0u64, |sum, &byte| (sum + u64::from(byte)) % modulus);
+    sum as u32
+}
+
+/// Largest prime below 2^16, the modulus of Adler-32
+const ADLER_MOD: u32 = 65521;
+
+/// Bytes that can be summed before `b` may overflow a `u32`, so the
+/// modulo only runs once per block (the same bound zlib uses)
+const ADLER_BLOCK: usize = 5552;
+
+/// The Adler-32 checksum from zlib (RFC 1950).
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::adler32;
+///
+/// assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+/// assert_eq!(adler32(b""), 1);
+/// ```
+#[must_use = "computing a checksum has no effect unless the result is used"]
+pub fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for block in data.chunks(ADLER_BLOCK) {
+        for &byte in block {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= ADLER_MOD;
+        b %= ADLER_MOD;
+    }
+    (b << 16) | a
+}
+
+/// Shifts each ASCII letter `shift` places along the alphabet, wrapping
+/// around and keeping its case. Everything else, including non-ASCII
+/// letters, is left alone. A negative `shift` moves letters backwards.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::caesar_cipher;
+///
+/// assert_eq!(caesar_cipher("Hello, World!", 3), "Khoor, Zruog!");
+/// assert_eq!(caesar_cipher("Khoor, Zruog!", -3), "Hello, World!");
+/// ```
+#[must_use = "this returns a new String and has no other effect"]
+pub fn caesar_cipher(text: &str, shift: i32) -> String {
+    let shift = shift.rem_euclid(26) as u8;
+    text.chars()
+        .map(|c| {
+            let base = match c {
+                'a'..='z' => b'a',
+                'A'..='Z' => b'A',
+                _ => return c,
+            };
+            (base + (c as u8 - base + shift) % 26) as char
+        })
+        .collect()
+}
+
+/// [`caesar_cipher`] with a shift of 13, which is its own inverse.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::rot13;
+///
+/// assert_eq!(rot13("Why did the chicken cross the road?"), "Jul qvq gur puvpxra pebff gur ebnq?");
+/// assert_eq!(rot13(&rot13("Gnat")), "Gnat");
+/// ```
+#[must_use = "this returns a new String and has no other effect"]
+pub fn rot13(text: &str) -> String {
+    caesar_cipher(text, 13)
+}
+
+/// Replaces each run of a repeated grapheme cluster with its length and the
+/// grapheme, so `"aaabbc"` becomes `"3a2b1c"`.
+///
+/// Digits in `s` make the output ambiguous, and [`run_length_decode`] reads
+/// them back as counts.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::run_length_encode;
+///
+/// assert_eq!(run_length_encode("aaabbc"), "3a2b1c");
+/// assert_eq!(run_length_encode("🇫🇷🇫🇷é"), "2🇫🇷1é");
+/// ```
+#[must_use = "this returns a new String and has no other effect"]
+pub fn run_length_encode(s: &str) -> String {
+    let mut out = String::new();
+    let mut graphemes = s.graphemes(true).peekable();
+    while let Some(grapheme) = graphemes.next() {
+        let mut count = 1;
+        while graphemes.next_if_eq(&grapheme).is_some() {
+            count += 1;
+        }
+        out.push_str(&count.to_string());
+        out.push_str(grapheme);
+    }
+    out
+}
+
+/// Expands the count-grapheme pairs written by [`run_length_encode`].
+///
+/// Fails on a grapheme without a count before it, a count without a
+/// grapheme after it, and a count of zero or one too large to build.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::run_length_decode;
+///
+/// assert_eq!(run_length_decode("3a2b1c").unwrap(), "aaabbc");
+/// assert!(run_length_decode("a3").is_err());
+/// ```
+pub fn run_length_decode(s: &str) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = s;
+    while !rest.is_empty() {
+        let position = s.len() - rest.len();
+        // Read the count a char at a time: a digit followed by a combining
+        // mark or a joiner is a single grapheme
+        let (count, after) = rest.split_at(rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len());
+        let Some(grapheme) = after.graphemes(true).next() else {
+            return Err(format!("count {} at the end has no character after it", count));
+        };
+        if count.is_empty() {
+            return Err(format!("missing count before '{}' at position {}", grapheme, position));
+        }
+        let repeat: usize = count
+            .parse()
+            .map_err(|_| format!("count {} at position {} is too large", count, position))?;
+        if repeat == 0 {
+            return Err(format!("count of zero at position {}", position));
+        }
+        let size = repeat
+            .checked_mul(grapheme.len())
+            .filter(|&size| size <= isize::MAX as usize)
+            .ok_or_else(|| format!("count {} at position {} is too large", count, position))?;
+        out.try_reserve(size).map_err(|e| format!("cannot expand {} x '{}': {}", repeat, grapheme, e))?;
+        out.extend(std::iter::repeat_n(grapheme, repeat));
+        rest = &after[grapheme.len()..];
+    }
+    Ok(out)
+}
+
+/// Maps `value` from `input_min..=input_max` onto `0.0..=1.0`, clamping
+/// values outside the range.
+///
+/// Fails unless `input_min < input_max`, or when `value` is NaN.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::normalize_to_range;
+///
+/// assert_eq!(normalize_to_range(15.0, 10.0, 20.0).unwrap(), 0.5);
+/// assert_eq!(normalize_to_range(25.0, 10.0, 20.0).unwrap(), 1.0);
+/// assert!(normalize_to_range(1.0, 5.0, 5.0).is_err());
+/// ```
+pub fn normalize_to_range(value: f64, input_min: f64, input_max: f64) -> Result<f64, String> {
+    if input_min.is_nan() || input_max.is_nan() || input_min >= input_max {
+        return Err(format!("invalid range {}..{}, the minimum must be below the maximum", input_min, input_max));
+    }
+    if value.is_nan() {
+        return Err("cannot normalize NaN".to_string());
+    }
+    Ok(clamp((value - input_min) / (input_max - input_min), 0.0, 1.0))
+}
+
+/// Linear interpolation: `a` at `t = 0.0`, `b` at `t = 1.0`, and in between
+/// for `t` in between. Other values of `t` extrapolate.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::lerp;
+///
+/// assert_eq!(lerp(0.0, 10.0, 0.5), 5.0);
+/// assert_eq!(lerp(235.0, 40.0, 1.0), 40.0);
+/// ```
+#[must_use = "this returns the interpolated value and has no other effect"]
+pub fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    // Exact at both ends, unlike `a + (b - a) * t`
+    (1.0 - t) * a + t * b
+}
+
+/// `value` limited to `min..=max`. Unlike [`f64::clamp`] it works for any
+/// `PartialOrd` type and does not panic when `min > max`; the result is then
+/// `min` or `max`, whichever `value` crosses first.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::clamp;
+///
+/// assert_eq!(clamp(15.0, 0.0, 10.0), 10.0);
+/// assert_eq!(clamp("m", "a", "f"), "f");
+/// ```
+#[must_use = "this returns the clamped value and has no other effect"]
+pub fn clamp<T: PartialOrd>(value: T, min: T, max: T) -> T {
+    if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+/// Change from `old` to `new` in percent of `old`, such as `10.0` for a rise
+/// from 100 to 110 and `-50.0` for a fall from 4 to 2.
+///
+/// Fails when `old` is zero, as there is no percentage of nothing.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::percentage_change;
+///
+/// assert_eq!(percentage_change(100.0, 110.0), Ok(10.0));
+/// assert_eq!(percentage_change(4.0, 2.0), Ok(-50.0));
+/// assert!(percentage_change(0.0, 5.0).is_err());
+/// ```
+pub fn percentage_change(old: f64, new: f64) -> Result<f64, String> {
+    if old == 0.0 {
+        return Err("cannot compute a percentage change from zero".to_string());
+    }
+    Ok((new - old) / old * 100.0)
+}
+
+/// Growth per period that takes `start` to `end` in `periods` steps, as a
+/// fraction: `0.1` means 10% per period, compounded.
+///
+/// Fails unless `start` and `periods` are positive. A fall to zero gives
+/// `-1.0`, a complete loss.
+///
+/// # Examples
+///
+/// ```
+/// use gh_actions::utils::compound_growth_rate;
+///
+/// let rate = compound_growth_rate(1000.0, 1210.0, 2.0).unwrap();
+/// assert!((rate - 0.1).abs() < 1e-12);
+/// assert!(compound_growth_rate(0.0, 10.0, 2.0).is_err());
+/// ```
+pub fn compound_growth_rate(start: f64, end: f64, periods: f64) -> Result<f64, String> {
+    if start.is_nan() || start <= 0.0 {
+        return Err(format!("invalid start value {}, expected a positive number", start));
+    }
+    if periods.is_nan() || periods <= 0.0 {
+        return Err(format!("invalid number of periods {}, expected a positive number", periods));
+    }
+    Ok((end / start).powf(1.0 / periods) - 1.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -265,6 +1340,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fibonacci_u128() {
+        for n in 0..=MAX_FIBONACCI_INDEX {
+            assert_eq!(calculate_fibonacci_u128(n).unwrap(), calculate_fibonacci(n).unwrap() as u128);
+        }
+        assert_eq!(
+            calculate_fibonacci_u128(186).unwrap(),
+            332825110087067562321196029789634457848
+        );
+        assert!(calculate_fibonacci_u128(187).unwrap_err().contains("too large"));
+    }
+
+    #[test]
+    fn test_fibonacci_mod() {
+        assert_eq!(calculate_fibonacci_mod(0, 7).unwrap(), 0);
+        assert_eq!(calculate_fibonacci_mod(1, 7).unwrap(), 1);
+        assert_eq!(calculate_fibonacci_mod(5, 1).unwrap(), 0);
+        let exact = calculate_fibonacci_u128(186).unwrap();
+        assert_eq!(calculate_fibonacci_mod(186, u64::MAX).unwrap() as u128, exact % u64::MAX as u128);
+        // Near the top of both ranges the recurrence still holds
+        let (n, m) = (u64::MAX - 2, u64::MAX);
+        let f0 = calculate_fibonacci_mod(n, m).unwrap() as u128;
+        let f1 = calculate_fibonacci_mod(n + 1, m).unwrap() as u128;
+        let f2 = calculate_fibonacci_mod(n + 2, m).unwrap() as u128;
+        assert_eq!(f2, (f0 + f1) % m as u128);
+        // The Pisano period for 10 is 60
+        assert_eq!(calculate_fibonacci_mod(60 * 1_000_000 + 7, 10).unwrap(), 3);
+        assert!(calculate_fibonacci_mod(10, 0).unwrap_err().contains("greater than zero"));
+    }
+
+    #[test]
+    fn test_fibonacci_iter_matches_calculate() {
+        let values: Vec<u64> = FibonacciIter::new().collect();
+        assert_eq!(values.len(), MAX_FIBONACCI_INDEX as usize + 1);
+        for (n, value) in values.iter().enumerate() {
+            assert_eq!(calculate_fibonacci(n as u32).unwrap(), *value);
+        }
+        assert_eq!(values.last(), Some(&12200160415121876738));
+    }
+
     #[test]
     fn test_validate_email_valid() {
         assert!(validate_email("test@example.com"));
@@ -288,6 +1403,57 @@ mod tests {
         assert!(!validate_email("user@domain.com."));
     }
 
+    #[test]
+    fn test_validate_email_consecutive_separators() {
+        assert!(!validate_email("user+-tag@example.com"));
+        assert!(!validate_email("user_.name@example.com"));
+        assert!(!validate_email("user+-@example.com"));
+        assert!(validate_email("user-tag@example.com"));
+        assert!(validate_email("first.last+tag@example.com"));
+    }
+
+    #[test]
+    fn test_validate_email_quoted_local_part() {
+        assert!(validate_email("\"user name\"@example.com"));
+        assert!(validate_email("\"user..name\"@example.com"));
+        assert!(validate_email("\"a@b\"@example.com"));
+        assert!(validate_email("\"say \\\"hi\\\"\"@example.com"));
+        assert!(validate_email("\"back\\\\slash\"@example.com"));
+        assert!(!validate_email("\"user\"name\"@example.com"));
+        assert!(!validate_email("\"\"@example.com"));
+        assert!(!validate_email("\"trailing\\\"@example.com"));
+        assert!(!validate_email("\"bad\\escape\"@example.com"));
+        assert!(!validate_email("\"tab\there\"@example.com"));
+        assert!(!validate_email("\"unterminated@example.com"));
+    }
+
+    #[test]
+    fn test_validate_email_detailed_reasons() {
+        assert_eq!(validate_email_detailed("user@example.com"), Ok(()));
+        let reason = |email: &str| validate_email_detailed(email).unwrap_err();
+        assert_eq!(reason("a@b"), "too short (minimum 5 characters)");
+        assert_eq!(reason("invalid-email"), "missing '@'");
+        assert_eq!(reason("@domain.com"), "missing local part before '@'");
+        assert_eq!(reason("user..name@example.com"), "invalid local part 'user..name'");
+        assert_eq!(reason("user@"), "missing domain after '@'");
+        assert_eq!(reason("user@example..com"), "domain 'example..com' has an empty label");
+        assert_eq!(reason("user@exa_mple.com"), "domain contains invalid character '_'");
+    }
+
+    #[test]
+    fn test_is_local_part_valid() {
+        assert!(is_local_part_valid("user"));
+        assert!(is_local_part_valid("user-tag"));
+        assert!(is_local_part_valid("a.b_c-d+e"));
+        assert!(!is_local_part_valid(""));
+        assert!(!is_local_part_valid(".user"));
+        assert!(!is_local_part_valid("user."));
+        assert!(!is_local_part_valid("user..name"));
+        assert!(!is_local_part_valid("user+-tag"));
+        assert!(!is_local_part_valid("user name"));
+        assert!(!is_local_part_valid(&"a".repeat(65)));
+    }
+
     #[test]
     fn test_validate_email_edge_cases() {
         assert!(!validate_email("a@b.c")); // Too short overall
@@ -354,6 +1520,17 @@ mod tests {
         assert_eq!(reverse_string("12345"), "54321");
     }
 
+    #[test]
+    fn test_reverse_string_grapheme_aware() {
+        assert_eq!(reverse_string_grapheme_aware("h\u{e9}llo"), "oll\u{e9}h");
+        assert_eq!(reverse_string_grapheme_aware("he\u{301}llo"), "olle\u{301}h");
+        assert_eq!(reverse_string("he\u{301}llo"), "oll\u{301}eh");
+        assert_eq!(reverse_string_grapheme_aware("a\u{1f44d}\u{1f3fd}b"), "b\u{1f44d}\u{1f3fd}a");
+        assert_eq!(reverse_string_grapheme_aware("ab\r\n"), "\r\nba");
+        assert_eq!(reverse_string_grapheme_aware(""), "");
+        assert_eq!(reverse_string_grapheme_aware("hello"), reverse_string("hello"));
+    }
+
     #[test]
     fn test_is_palindrome() {
         assert!(is_palindrome("racecar"));
@@ -365,6 +1542,335 @@ mod tests {
         assert!(is_palindrome("a"));
     }
 
+    #[test]
+    fn test_is_strict_palindrome() {
+        assert!(is_strict_palindrome("racecar"));
+        assert!(is_strict_palindrome(""));
+        assert!(!is_strict_palindrome("Racecar"));
+        assert!(!is_strict_palindrome("never odd or even"));
+        assert!(is_strict_palindrome("été"));
+    }
+
+    #[test]
+    fn test_is_palindrome_unicode() {
+        let composed = "A\u{f1}o\u{f1}a";
+        let decomposed = "An\u{303}on\u{303}a";
+        let mixed = "A\u{f1}on\u{303}a";
+        assert!(is_palindrome_unicode(composed));
+        assert!(is_palindrome_unicode(decomposed));
+        assert!(is_palindrome_unicode(mixed));
+        assert!(!is_palindrome_unicode("A\u{f1}on\u{303}ox"));
+
+        // Accents stay on their letter instead of being dropped or moved.
+        assert!(is_palindrome_unicode("an\u{308}a"));
+        assert!(!is_palindrome_unicode("xn\u{308}nx"));
+        assert!(is_palindrome("xn\u{308}nx"));
+
+        // Plain text behaves like `is_palindrome`.
+        for s in ["racecar", "A man a plan a canal Panama", "hello", "", "été"] {
+            assert_eq!(is_palindrome_unicode(s), is_palindrome(s), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_humanize_duration() {
+        use std::time::Duration;
+        assert_eq!(humanize_duration(Duration::ZERO), "0s");
+        assert_eq!(humanize_duration(Duration::from_secs(59)), "59s");
+        assert_eq!(humanize_duration(Duration::from_secs(60)), "1m");
+        assert_eq!(humanize_duration(Duration::from_secs(3599)), "59m");
+        assert_eq!(humanize_duration(Duration::from_secs(3600)), "1h");
+        assert_eq!(humanize_duration(Duration::from_secs(47 * 3600 + 3599)), "47h");
+        assert_eq!(humanize_duration(Duration::from_secs(48 * 3600)), "2 days");
+        assert_eq!(humanize_duration(Duration::from_secs(30 * 86_400)), "30 days");
+    }
+
+    #[test]
+    fn test_parse_duration_human() {
+        use chrono::Duration;
+        assert_eq!(parse_duration_human("1 day 12 hours"), Ok(Duration::hours(36)));
+        assert_eq!(parse_duration_human("3 days"), Ok(Duration::days(3)));
+        assert_eq!(parse_duration_human("2 hours 30 minutes"), Ok(Duration::minutes(150)));
+        assert_eq!(parse_duration_human("1 week"), Ok(Duration::weeks(1)));
+        assert_eq!(parse_duration_human("90 seconds"), Ok(Duration::seconds(90)));
+        assert_eq!(parse_duration_human(" 1h30m "), Ok(Duration::minutes(90)));
+        assert_eq!(parse_duration_human("2 Mins 5 SECS"), Ok(Duration::seconds(125)));
+        assert_eq!(parse_duration_human("0 d"), Ok(Duration::zero()));
+
+        assert!(parse_duration_human("foo").unwrap_err().contains("expected a number at 'foo'"));
+        assert!(parse_duration_human("").is_err());
+        assert!(parse_duration_human("3 fortnights").unwrap_err().contains("unknown unit 'fortnights'"));
+        assert!(parse_duration_human("3").unwrap_err().contains("missing unit"));
+        assert!(parse_duration_human("-3 days").is_err());
+        assert!(parse_duration_human("1.5 hours").is_err());
+        assert!(parse_duration_human("99999999999999999999 s").unwrap_err().contains("too large"));
+        assert!(parse_duration_human("9223372036854775807 weeks").unwrap_err().contains("too long"));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.00 KB");
+        assert_eq!(format_bytes(1536), "1.50 KB");
+        assert_eq!(format_bytes(2_411_725), "2.30 MB");
+        // Values that round up to 1024.00 move to the next unit
+        assert_eq!(format_bytes(1024 * 1024 - 1), "1.00 MB");
+        assert_eq!(format_bytes(1024 * 1024 - 6), "1023.99 KB");
+        assert_eq!(format_bytes(1024 * 1024 - 5), "1.00 MB");
+        assert_eq!(format_bytes((1 << 40) - 1), "1.00 TB");
+        assert_eq!(format_bytes(3 << 40), "3.00 TB");
+        // Nothing above TB
+        assert_eq!(format_bytes(2048 << 40), "2048.00 TB");
+        assert_eq!(format_bytes(u64::MAX), "16777216.00 TB");
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(parse_bytes("512 B"), Ok(512));
+        assert_eq!(parse_bytes("512"), Ok(512));
+        assert_eq!(parse_bytes("1.50 KB"), Ok(1536));
+        assert_eq!(parse_bytes(" 2.3mb "), Ok(2_411_725));
+        assert_eq!(parse_bytes("1 Tb"), Ok(1 << 40));
+        assert_eq!(parse_bytes("0.5 B"), Ok(1));
+
+        assert!(parse_bytes("").unwrap_err().contains("expected a number"));
+        assert!(parse_bytes("KB").unwrap_err().contains("expected a number"));
+        assert!(parse_bytes("-1 KB").is_err());
+        assert!(parse_bytes("1e3 B").is_err());
+        assert!(parse_bytes("3 PB").unwrap_err().contains("unknown unit 'PB'"));
+        assert!(parse_bytes("1 KB extra").is_err());
+        assert!(parse_bytes("99999999 TB").unwrap_err().contains("too large"));
+    }
+
+    #[test]
+    fn test_format_duration_human() {
+        use chrono::Duration;
+        assert_eq!(format_duration_human(Duration::zero()), "just now");
+        assert_eq!(format_duration_human(Duration::seconds(59)), "just now");
+        assert_eq!(format_duration_human(Duration::seconds(-300)), "just now");
+        assert_eq!(format_duration_human(Duration::seconds(60)), "1 minute ago");
+        assert_eq!(format_duration_human(Duration::seconds(3599)), "59 minutes ago");
+        assert_eq!(format_duration_human(Duration::hours(2)), "2 hours ago");
+        assert_eq!(format_duration_human(Duration::hours(23)), "23 hours ago");
+        assert_eq!(format_duration_human(Duration::days(3)), "3 days ago");
+        assert_eq!(format_duration_human(Duration::days(14)), "2 weeks ago");
+        assert_eq!(format_duration_human(Duration::days(45)), "1 month ago");
+        assert_eq!(format_duration_human(Duration::days(800)), "2 years ago");
+    }
+
+    #[test]
+    fn test_base64_vectors() {
+        // RFC 4648 section 10
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+            ("Man", "TWFu"),
+        ];
+        for (plain, encoded) in vectors {
+            assert_eq!(base64_encode(plain.as_bytes()), encoded);
+            assert_eq!(base64_decode(encoded).unwrap(), plain.as_bytes());
+        }
+        assert_eq!(base64_encode(&[0xfb, 0xff]), "+/8=");
+    }
+
+    #[test]
+    fn test_base64_decode_rejects_invalid_input() {
+        assert!(base64_decode("Zm9").unwrap_err().contains("length 3"));
+        assert!(base64_decode("Zm9*").unwrap_err().contains("character '*' at position 3"));
+        assert!(base64_decode("Zm 9").unwrap_err().contains("character ' '"));
+        assert!(base64_decode("Zé==").is_err());
+        assert!(base64_decode("Z===").unwrap_err().contains("padding"));
+        assert!(base64_decode("Zg=a").unwrap_err().contains("padding"));
+        assert!(base64_decode("Zg==Zg==").unwrap_err().contains("padding"));
+        // Non-zero bits hidden under the padding
+        assert!(base64_decode("Zh==").unwrap_err().contains("padding"));
+        assert!(base64_decode("Zm9=").unwrap_err().contains("padding"));
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(hex_encode(b"\xde\xad\xbe\xef"), "deadbeef");
+        assert_eq!(hex_encode(&[0x00, 0x0f, 0xf0]), "000ff0");
+        assert_eq!(hex_encode(&[]), "");
+        assert_eq!(hex_decode("DEADBEEF"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(hex_decode("000fF0"), Ok(vec![0x00, 0x0f, 0xf0]));
+        assert_eq!(hex_decode(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_hex_decode_rejects_invalid_input() {
+        assert!(hex_decode("xyz").unwrap_err().contains("character 'x' at position 0"));
+        assert!(hex_decode("abc").unwrap_err().contains("length 3"));
+        assert!(hex_decode("0g").unwrap_err().contains("'g' at position 1"));
+        assert!(hex_decode("+1").is_err());
+        assert!(hex_decode("ab cd").is_err());
+        assert!(hex_decode("é0").is_err());
+    }
+
+    #[test]
+    fn test_simple_checksum() {
+        assert_eq!(simple_checksum(b""), 0);
+        assert_eq!(simple_checksum(b"Wikipedia"), 919);
+        assert_eq!(simple_checksum(&[0xff; 1000]), 255_000);
+    }
+
+    #[test]
+    fn test_adler32() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"a"), 0x0062_0062);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        // Long enough to need several blocks, all bytes at their largest
+        assert_eq!(adler32(&[0xff; 100_000]), 0x149A_302C);
+    }
+
+    #[test]
+    fn test_caesar_cipher() {
+        assert_eq!(caesar_cipher("Hello, World!", 3), "Khoor, Zruog!");
+        assert_eq!(caesar_cipher("xyz XYZ", 3), "abc ABC");
+        assert_eq!(caesar_cipher("abc", -1), "zab");
+        assert_eq!(caesar_cipher("abc", 26), "abc");
+        assert_eq!(caesar_cipher("abc", -27), "zab");
+        assert_eq!(caesar_cipher("abc", i32::MIN), caesar_cipher("abc", i32::MIN % 26));
+        assert_eq!(caesar_cipher("Ünïcödé 123", 5), "Üsïhöié 123");
+        assert_eq!(caesar_cipher("", 7), "");
+    }
+
+    #[test]
+    fn test_rot13() {
+        assert_eq!(rot13("Hello, World!"), "Uryyb, Jbeyq!");
+        assert_eq!(rot13("Uryyb, Jbeyq!"), "Hello, World!");
+    }
+
+    #[test]
+    fn test_run_length_encode() {
+        assert_eq!(run_length_encode("aaabbc"), "3a2b1c");
+        assert_eq!(run_length_encode(""), "");
+        assert_eq!(run_length_encode("abab"), "1a1b1a1b");
+        assert_eq!(run_length_encode(&"x".repeat(12)), "12x");
+        // "e" plus a combining accent is one grapheme, and differs from "e"
+        assert_eq!(run_length_encode("e\u{301}e\u{301}e"), "2e\u{301}1e");
+        assert_eq!(run_length_encode("  !!"), "2 2!");
+    }
+
+    #[test]
+    fn test_run_length_decode() {
+        assert_eq!(run_length_decode("3a2b1c"), Ok("aaabbc".to_string()));
+        assert_eq!(run_length_decode(""), Ok(String::new()));
+        assert_eq!(run_length_decode("12x"), Ok("x".repeat(12)));
+        assert_eq!(run_length_decode("2e\u{301}1e"), Ok("e\u{301}e\u{301}e".to_string()));
+        // A count digit would form one grapheme with a leading combining mark
+        // or joiner, so these only round-trip if counts are read char by char
+        for input in ["\u{301}", "\u{200d}", "\n\u{301}", "\u{301}\u{301}x", "a\u{200d}\u{200d}"] {
+            assert_eq!(run_length_decode(&run_length_encode(input)), Ok(input.to_string()), "{:?}", input);
+        }
+
+        assert!(run_length_decode("a").unwrap_err().contains("missing count before 'a' at position 0"));
+        assert!(run_length_decode("2ab").unwrap_err().contains("missing count before 'b' at position 2"));
+        assert!(run_length_decode("3a2").unwrap_err().contains("count 2 at the end"));
+        assert!(run_length_decode("0a").unwrap_err().contains("count of zero"));
+        assert!(run_length_decode("99999999999999999999999a").unwrap_err().contains("too large"));
+        assert!(run_length_decode(&format!("{}a", usize::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_normalize_to_range() {
+        assert_eq!(normalize_to_range(5.0, 0.0, 10.0), Ok(0.5));
+        assert_eq!(normalize_to_range(0.0, 0.0, 10.0), Ok(0.0));
+        assert_eq!(normalize_to_range(10.0, 0.0, 10.0), Ok(1.0));
+        assert_eq!(normalize_to_range(-3.0, -4.0, 0.0), Ok(0.25));
+        assert_eq!(normalize_to_range(-1.0, 0.0, 10.0), Ok(0.0));
+        assert_eq!(normalize_to_range(f64::INFINITY, 0.0, 10.0), Ok(1.0));
+
+        assert!(normalize_to_range(1.0, 10.0, 0.0).unwrap_err().contains("invalid range 10..0"));
+        assert!(normalize_to_range(1.0, 2.0, 2.0).is_err());
+        assert!(normalize_to_range(1.0, f64::NAN, 2.0).is_err());
+        assert!(normalize_to_range(f64::NAN, 0.0, 2.0).is_err());
+    }
+
+    #[test]
+    fn test_lerp() {
+        assert_eq!(lerp(0.0, 10.0, 0.5), 5.0);
+        assert_eq!(lerp(0.1, 0.7, 0.0), 0.1);
+        assert_eq!(lerp(0.1, 0.7, 1.0), 0.7);
+        assert_eq!(lerp(10.0, 0.0, 0.25), 7.5);
+        assert_eq!(lerp(0.0, 10.0, 2.0), 20.0);
+        for i in 0..=100 {
+            let value = lerp(-3.0, 8.0, i as f64 / 100.0);
+            assert!((-3.0..=8.0).contains(&value));
+        }
+    }
+
+    #[test]
+    fn test_clamp() {
+        assert_eq!(clamp(15.0, 0.0, 10.0), 10.0);
+        assert_eq!(clamp(-1.0, 0.0, 10.0), 0.0);
+        assert_eq!(clamp(5, 0, 10), 5);
+        assert_eq!(clamp("m", "a", "f"), "f");
+        assert!(clamp(f64::NAN, 0.0, 1.0).is_nan());
+    }
+
+    #[test]
+    fn test_percentage_change() {
+        assert_eq!(percentage_change(100.0, 110.0), Ok(10.0));
+        assert_eq!(percentage_change(4.0, 2.0), Ok(-50.0));
+        assert_eq!(percentage_change(-10.0, -5.0), Ok(-50.0));
+        assert_eq!(percentage_change(3.0, 3.0), Ok(0.0));
+        assert!(percentage_change(0.0, 5.0).is_err());
+        assert!(percentage_change(-0.0, 5.0).is_err());
+    }
+
+    #[test]
+    fn test_compound_growth_rate() {
+        let close = |actual: Result<f64, String>, expected: f64| (actual.unwrap() - expected).abs() < 1e-12;
+        assert!(close(compound_growth_rate(1000.0, 1210.0, 2.0), 0.1));
+        assert!(close(compound_growth_rate(100.0, 50.0, 1.0), -0.5));
+        assert!(close(compound_growth_rate(100.0, 100.0, 12.0), 0.0));
+        assert!(close(compound_growth_rate(100.0, 0.0, 3.0), -1.0));
+
+        for (start, periods) in [(0.0, 2.0), (-5.0, 2.0), (f64::NAN, 2.0), (10.0, 0.0), (10.0, -1.0), (10.0, f64::NAN)] {
+            assert!(compound_growth_rate(start, 20.0, periods).is_err(), "{} {}", start, periods);
+        }
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("abcdef", 6), "abcdef");
+        assert_eq!(truncate_to_width("abcdef", 4), "abc…");
+        assert_eq!(truncate_to_width("abcdef", 1), "…");
+        assert_eq!(truncate_to_width("abcdef", 0), "");
+        // Wide characters take two columns each
+        assert_eq!(truncate_to_width("日本語テキスト", 7), "日本語…");
+    }
+
+    #[test]
+    fn test_render_table_alignment() {
+        let rows = vec![
+            vec!["1".to_string(), "日本".to_string(), "yes".to_string()],
+            vec!["22".to_string(), "Bob".to_string(), "".to_string()],
+        ];
+        let table = render_table(&["ID", "Name", "OK"], &rows, 40);
+        assert_eq!(
+            table,
+            "ID  Name  OK\n\
+             --  ----  ---\n\
+             1   日本  yes\n\
+             22  Bob\n"
+        );
+    }
+
+    #[test]
+    fn test_render_table_truncates_long_cells() {
+        let rows = vec![vec!["A very long task title indeed".to_string()]];
+        let table = render_table(&["Title"], &rows, 10);
+        assert_eq!(table, "Title\n----------\nA very lo…\n");
+    }
+
     #[test]
     fn test_count_words() {
         assert_eq!(count_words("hello world"), 2);
@@ -374,6 +1880,51 @@ mod tests {
         assert_eq!(count_words("  hello   world  test  "), 3);
     }
 
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("--already-a-slug--"), "already-a-slug");
+        assert_eq!(slugify("Crème Brûlée"), "crème-brûlée");
+        assert_eq!(slugify("!!!"), "");
+        assert_eq!(slugify(""), "");
+    }
+
+    #[test]
+    fn test_is_valid_identifier() {
+        assert!(is_valid_identifier("_hello123"));
+        assert!(is_valid_identifier("x"));
+        assert!(is_valid_identifier("_"));
+        assert!(is_valid_identifier(&"a".repeat(255)));
+        assert!(!is_valid_identifier(&"a".repeat(256)));
+        assert!(!is_valid_identifier("123bad"));
+        assert!(!is_valid_identifier(""));
+        assert!(!is_valid_identifier("has space"));
+        assert!(!is_valid_identifier("kebab-case"));
+        assert!(!is_valid_identifier("café"));
+    }
+
+    #[test]
+    fn test_case_conversion() {
+        assert_eq!(to_snake_case("HelloWorld"), "hello_world");
+        assert_eq!(to_snake_case("helloWorld"), "hello_world");
+        assert_eq!(to_snake_case("Hello World"), "hello_world");
+        assert_eq!(to_snake_case("XMLHttpRequest"), "xml_http_request");
+        assert_eq!(to_snake_case("version2Beta"), "version2_beta");
+        assert_eq!(to_snake_case("already_snake_case"), "already_snake_case");
+        assert_eq!(to_snake_case("  --kebab-case-- "), "kebab_case");
+        assert_eq!(to_snake_case(""), "");
+
+        assert_eq!(to_camel_case("hello_world"), "HelloWorld");
+        assert_eq!(to_camel_case("hello world"), "HelloWorld");
+        assert_eq!(to_camel_case("HelloWorld"), "HelloWorld");
+        assert_eq!(to_camel_case("__private_field"), "PrivateField");
+        assert_eq!(to_camel_case(""), "");
+
+        for name in ["HelloWorld", "TaskManagerApp", "Utf8Decoder"] {
+            assert_eq!(to_camel_case(&to_snake_case(name)), name);
+        }
+    }
+
     #[test]
     fn test_performance_fibonacci() {
         let start = std::time::Instant::now();
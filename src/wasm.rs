@@ -1,207 +1,1568 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use crate::task::{TaskManager, Task};
-use crate::app::TaskManagerApp;
-use std::sync::Mutex;
+use crate::task::{
+    parse_due_date, Priority, Recurrence, TaskFilter, TaskManager, Task, TaskId, TaskOrder, TaskReport, TaskSpec,
+    TaskSummary, ToggleResult,
+};
+use chrono::{DateTime, Duration, FixedOffset, Offset, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use crate::app::{series_points, STATS_DAYS};
+#[cfg(target_arch = "wasm32")]
+use crate::app::{AppOptions, TaskManagerApp};
+use crate::build_info::BuildInfo;
+use crate::utils::hex_encode;
+use crate::storage::{self, LocalStorage, MemoryStorage, StorageBackend};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(target_arch = "wasm32")]
+use std::cell::RefMut;
+#[cfg(target_arch = "wasm32")]
+use std::thread::LocalKey;
+use std::sync::{Mutex, MutexGuard};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::TryLockError;
+#[cfg(target_arch = "wasm32")]
 use web_sys::HtmlCanvasElement;
 
-// Global task manager instance
+// State shared by the bindings, reached through the `lock_*` functions.
+//
+// The browser runs wasm on one thread, so there each value is a `RefCell`:
+// nothing to lock and nothing for a panic to poison. Native builds, whose
+// tests run on several threads, keep a `Mutex`.
+//
+// Either way a binding must not call JavaScript, or another binding, while it
+// holds one of the guards: the callee would find the value in use, which
+// panics on wasm32 and deadlocks natively. Drop the guard first, as the
+// bindings do before `schedule_save` and `notify_change`.
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    // In a `Cell`, so `reset_task_manager` can swap in a new store
+    static TASK_MANAGER: Cell<&'static RefCell<TaskManager>> = Cell::new(leak(TaskManager::new()));
+    static STORAGE: &'static RefCell<Box<dyn StorageBackend + Send>> = leak(Box::new(LocalStorage));
+    static STORAGE_KEY: &'static RefCell<String> = leak(storage::TASKS_KEY.to_string());
+    static PENDING_SAVE: &'static RefCell<PendingSave> = leak(PendingSave::default());
+    static CLEAR_TOKEN: &'static RefCell<Option<ClearToken>> = leak(None);
+    static REVISIONS: &'static RefCell<Revisions> = leak(Revisions::default());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 lazy_static::lazy_static! {
     static ref TASK_MANAGER: Mutex<TaskManager> = Mutex::new(TaskManager::new());
+    static ref STORAGE: Mutex<Box<dyn StorageBackend + Send>> = Mutex::new(Box::new(LocalStorage));
+    static ref STORAGE_KEY: Mutex<String> = Mutex::new(storage::TASKS_KEY.to_string());
+    static ref PENDING_SAVE: Mutex<PendingSave> = Mutex::new(PendingSave::default());
+    static ref CLEAR_TOKEN: Mutex<Option<ClearToken>> = Mutex::new(None);
+    static ref REVISIONS: Mutex<Revisions> = Mutex::new(Revisions::default());
+}
+
+/// Exclusive access to one of the values above
+#[cfg(target_arch = "wasm32")]
+type Guard<T> = RefMut<'static, T>;
+#[cfg(not(target_arch = "wasm32"))]
+type Guard<T> = MutexGuard<'static, T>;
+
+thread_local! {
+    // Created once and reused by every save timer, so restarting the timer
+    // on each change does not leak a closure
+    static SAVE_CALLBACK: Closure<dyn FnMut()> = Closure::new(|| {
+        lock_pending_save().timer = None;
+        flush_saves();
+    });
+    // Callbacks passed to `register_on_change`, by handle
+    static CHANGE_LISTENERS: RefCell<Vec<(u32, js_sys::Function)>> = const { RefCell::new(Vec::new()) };
+    static NEXT_LISTENER: Cell<u32> = const { Cell::new(1) };
+    // The egui app started by `start_egui_app`, until `stop_egui_app`
+    #[cfg(target_arch = "wasm32")]
+    static RUNNER: RefCell<Option<eframe::WebRunner>> = const { RefCell::new(None) };
+}
+
+/// How long the tasks must stay unchanged before they are written out
+const SAVE_DEBOUNCE_MS: i32 = 500;
+
+/// How long a token from [`request_clear_all`] can be confirmed
+const CLEAR_TOKEN_TTL_SECS: i64 = 30;
+
+/// Tokens issued so far, mixed into the next one so two requests in the
+/// same instant still differ
+static CLEAR_TOKENS_ISSUED: AtomicU64 = AtomicU64::new(0);
+
+/// Numbers every change to the tasks, for [`get_tasks_changed_since`]
+#[derive(Debug, Default)]
+struct Revisions {
+    current: u32,
+    /// Revision of the last change that replaced every task. Clients that
+    /// are older than it get a full snapshot.
+    reset_at: u32,
+    /// Revision of the last change to each task since `reset_at`, and
+    /// whether that change removed it
+    tasks: HashMap<u32, (u32, bool)>,
+}
+
+impl Revisions {
+    /// Starts a new revision for a change to `task_id`, or to every task
+    /// for `None`
+    fn record(&mut self, task_id: Option<u32>, removed: bool) {
+        self.current += 1;
+        match task_id {
+            Some(id) => {
+                self.tasks.insert(id, (self.current, removed));
+            }
+            None => {
+                self.reset_at = self.current;
+                self.tasks.clear();
+            }
+        }
+    }
+    
+    fn changes_since<'a>(&self, manager: &'a TaskManager, since: u32) -> TaskChanges<'a> {
+        if since == 0 || since < self.reset_at || since > self.current {
+            return TaskChanges {
+                revision: self.current,
+                full: true,
+                changed: manager.get_tasks_page(0, usize::MAX, TaskOrder::Id),
+                removed: Vec::new(),
+            };
+        }
+        let mut changed = Vec::new();
+        let mut removed = Vec::new();
+        for (&id, &(revision, was_removed)) in &self.tasks {
+            if revision <= since {
+                continue;
+            }
+            if was_removed {
+                removed.push(id);
+            } else if let Some(task) = manager.get_task(id) {
+                changed.push(task);
+            }
+        }
+        changed.sort_by_key(|task| task.id);
+        removed.sort_unstable();
+        TaskChanges { revision: self.current, full: false, changed, removed }
+    }
+}
+
+/// What [`get_tasks_changed_since`] returns
+#[derive(Debug, Serialize)]
+struct TaskChanges<'a> {
+    /// Pass this to the next call
+    revision: u32,
+    /// `changed` holds every task, and any task the caller has that is not
+    /// in it is gone
+    full: bool,
+    /// Tasks added or edited since the given revision, ordered by ID
+    changed: Vec<&'a Task>,
+    /// IDs of tasks removed since the given revision
+    removed: Vec<u32>,
+}
+
+/// One page of [`get_tasks_page_json`]
+#[derive(Debug, Serialize)]
+struct TaskPage<'a> {
+    /// Tasks on every page
+    total: usize,
+    tasks: Vec<&'a Task>,
+}
+
+/// The token [`confirm_clear_all`] accepts
+#[derive(Debug)]
+struct ClearToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Changes that have not been written to storage yet
+#[derive(Debug)]
+struct PendingSave {
+    dirty: bool,
+    autosave: bool,
+    /// `setTimeout` handle of the scheduled write
+    timer: Option<i32>,
+}
+
+impl Default for PendingSave {
+    fn default() -> Self {
+        PendingSave { dirty: false, autosave: true, timer: None }
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    fn log(s: &str);
+    
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(s: &str);
+}
+
+// Macro for console.log
+macro_rules! console_log {
+    ($($t:tt)*) => (info(&format_args!($($t)*).to_string()))
+}
+
+/// Why a binding failed.
+///
+/// JavaScript receives a thrown `Error` whose `name` is `SerializationError`,
+/// `NotFoundError`, `InvalidBackupError`, `InvalidArgumentError` or
+/// `StorageError`, so the page can tell a broken store from an empty one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WasmError {
+    /// Tasks could not be converted to JSON
+    Serialization(String),
+    /// A [`WasmTask`] was applied after its task had been removed
+    TaskNotFound(u32),
+    /// A backup could not be read; the current tasks were left alone
+    InvalidBackup(String),
+    /// An argument from JavaScript was not one of the accepted values
+    InvalidArgument(String),
+    /// The saved tasks could not be read, so the list starts empty
+    Storage(String),
+}
+
+impl fmt::Display for WasmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WasmError::Serialization(message) => write!(f, "failed to serialize tasks: {}", message),
+            WasmError::TaskNotFound(id) => write!(f, "task {} not found", id),
+            WasmError::InvalidBackup(message) => write!(f, "invalid backup: {}", message),
+            WasmError::InvalidArgument(message) => f.write_str(message),
+            WasmError::Storage(message) => write!(f, "could not load saved tasks: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for WasmError {}
+
+impl From<WasmError> for JsValue {
+    fn from(error: WasmError) -> Self {
+        let js_error = js_sys::Error::new(&error.to_string());
+        js_error.set_name(match error {
+            WasmError::Serialization(_) => "SerializationError",
+            WasmError::TaskNotFound(_) => "NotFoundError",
+            WasmError::InvalidBackup(_) => "InvalidBackupError",
+            WasmError::InvalidArgument(_) => "InvalidArgumentError",
+            WasmError::Storage(_) => "StorageError",
+        });
+        js_error.into()
+    }
+}
+
+/// Locks `mutex`, taking the data back if an earlier panic poisoned it.
+///
+/// The panic may have struck halfway through a change, so the data can be
+/// partly updated, say a task added without the ID counter moving on.
+/// Carrying on with it still beats failing every later call, which would
+/// make the page unusable; [`reset_task_manager`] starts over when the
+/// tasks are beyond repair.
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+fn lock_recovering<'a, T: ?Sized>(mutex: &'a Mutex<T>, name: &str) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        warn(&format!("{} was poisoned by an earlier panic, recovering", name));
+        mutex.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+/// A value for the thread-local state, which lives as long as the page
+#[cfg(target_arch = "wasm32")]
+fn leak<T>(value: T) -> &'static RefCell<T> {
+    Box::leak(Box::new(RefCell::new(value)))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn access<T>(global: &'static LocalKey<&'static RefCell<T>>, name: &str) -> Guard<T> {
+    borrow(global.with(|cell| *cell), name)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn borrow<T>(cell: &'static RefCell<T>, name: &str) -> Guard<T> {
+    cell.try_borrow_mut()
+        .unwrap_or_else(|_| panic!("{} is already in use, a binding was called while another held it", name))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn access<T>(global: &'static Mutex<T>, name: &str) -> Guard<T> {
+    lock_recovering(global, name)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn lock_tasks() -> Guard<TaskManager> {
+    borrow(TASK_MANAGER.with(Cell::get), "task store")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn lock_tasks() -> Guard<TaskManager> {
+    access(&TASK_MANAGER, "task store")
+}
+
+fn lock_storage() -> Guard<Box<dyn StorageBackend + Send>> {
+    access(&STORAGE, "storage")
+}
+
+/// Key the tasks are saved under, set by [`init`]
+fn lock_storage_key() -> Guard<String> {
+    access(&STORAGE_KEY, "storage key")
+}
+
+fn lock_pending_save() -> Guard<PendingSave> {
+    access(&PENDING_SAVE, "save queue")
+}
+
+fn lock_clear_token() -> Guard<Option<ClearToken>> {
+    access(&CLEAR_TOKEN, "clear token")
+}
+
+fn lock_revisions() -> Guard<Revisions> {
+    access(&REVISIONS, "revisions")
+}
+
+fn info(message: &str) {
+    if cfg!(target_arch = "wasm32") {
+        log(message);
+    } else {
+        log::info!("{}", message);
+    }
+}
+
+fn warn(message: &str) {
+    if cfg!(target_arch = "wasm32") {
+        console_warn(message);
+    } else {
+        log::warn!("{}", message);
+    }
+}
+
+// Helper function to save tasks to localStorage
+fn save_tasks() -> Result<(), String> {
+    let manager = lock_tasks();
+    let mut storage = lock_storage();
+    storage::save_tasks(storage.as_mut(), &lock_storage_key(), &manager)
+}
+
+/// Marks the tasks as changed and restarts the save timer, so a burst of
+/// changes is written once, `SAVE_DEBOUNCE_MS` after the last of them
+fn schedule_save() {
+    let mut pending = lock_pending_save();
+    pending.dirty = true;
+    if pending.autosave {
+        restart_save_timer(&mut pending);
+    }
+}
+
+/// Writes the tasks now, for changes too important to leave in a timer
+fn save_now() {
+    lock_pending_save().dirty = true;
+    flush_saves();
+}
+
+/// The browser window, if there is one to run timers on. Elsewhere changes
+/// wait for `flush_saves`.
+fn timer_window() -> Option<web_sys::Window> {
+    if cfg!(target_arch = "wasm32") {
+        web_sys::window()
+    } else {
+        None
+    }
+}
+
+fn restart_save_timer(pending: &mut PendingSave) {
+    cancel_save_timer(pending);
+    let Some(window) = timer_window() else { return };
+    let scheduled = SAVE_CALLBACK.with(|callback| {
+        window.set_timeout_with_callback_and_timeout_and_arguments_0(
+            callback.as_ref().unchecked_ref(),
+            SAVE_DEBOUNCE_MS,
+        )
+    });
+    match scheduled {
+        Ok(handle) => pending.timer = Some(handle),
+        Err(e) => warn(&format!("failed to schedule a save, changes wait for the next flush: {:?}", e)),
+    }
+}
+
+fn cancel_save_timer(pending: &mut PendingSave) {
+    if let (Some(handle), Some(window)) = (pending.timer.take(), timer_window()) {
+        window.clear_timeout_with_handle(handle);
+    }
+}
+
+/// Writes pending changes to localStorage right away. Returns whether there
+/// was anything to write.
+///
+/// When the write fails, say because storage is full, callbacks get a
+/// `storage_error` event and the changes stay pending: the next change, or
+/// the next flush, tries again.
+#[wasm_bindgen]
+pub fn flush_saves() -> bool {
+    let dirty = {
+        let mut pending = lock_pending_save();
+        cancel_save_timer(&mut pending);
+        std::mem::take(&mut pending.dirty)
+    };
+    if dirty {
+        match save_tasks() {
+            Ok(()) => console_log!("Tasks saved to localStorage"),
+            // Keep going with the in-memory tasks
+            Err(e) => {
+                warn(&format!("Failed to save tasks: {}", e));
+                lock_pending_save().dirty = true;
+                notify_storage_error(&e);
+            }
+        }
+    }
+    dirty
+}
+
+/// Whether some changes are not in storage yet, because the save timer has
+/// not fired or the last save failed
+#[wasm_bindgen]
+pub fn has_unsaved_changes() -> bool {
+    lock_pending_save().dirty
+}
+
+/// Saves pending changes before the page goes away. Call it from `pagehide`
+/// or `beforeunload`; a pending save timer would never fire.
+#[wasm_bindgen]
+pub fn on_page_hide() {
+    flush_saves();
+}
+
+/// Turns the delayed save after each change on or off.
+///
+/// While it is off, changes are only written by [`flush_saves`],
+/// [`on_page_hide`] or a backup import. Turning it back on schedules a save
+/// for anything still pending.
+#[wasm_bindgen]
+pub fn set_autosave(enabled: bool) {
+    let mut pending = lock_pending_save();
+    pending.autosave = enabled;
+    if !enabled {
+        cancel_save_timer(&mut pending);
+    } else if pending.dirty {
+        restart_save_timer(&mut pending);
+    }
+}
+
+/// What [`register_on_change`] callbacks receive
+#[derive(Debug, Serialize)]
+struct ChangeEvent {
+    /// `add`, `toggle`, `remove`, `update`, `import`, `clear`, or
+    /// `storage_error` when the tasks could not be saved
+    kind: &'static str,
+    /// `null` for an import, clear or storage error, which may concern every task
+    task_id: Option<u32>,
+    /// Why saving failed, for a `storage_error`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Calls `callback` with `{kind, task_id}` after every change to the tasks
+/// made through this module, and returns a handle for
+/// [`unregister_on_change`].
+///
+/// Callbacks run after the task store is unlocked, so they may call back
+/// into any binding. One that throws is logged and the rest still run.
+#[wasm_bindgen]
+pub fn register_on_change(callback: js_sys::Function) -> u32 {
+    let handle = NEXT_LISTENER.with(|next| next.replace(next.get() + 1));
+    CHANGE_LISTENERS.with(|listeners| listeners.borrow_mut().push((handle, callback)));
+    handle
+}
+
+/// Stops calling the callback registered under `handle`. Returns whether
+/// there was one.
+#[wasm_bindgen]
+pub fn unregister_on_change(handle: u32) -> bool {
+    CHANGE_LISTENERS.with(|listeners| {
+        let mut listeners = listeners.borrow_mut();
+        let before = listeners.len();
+        listeners.retain(|(registered, _)| *registered != handle);
+        listeners.len() != before
+    })
+}
+
+/// Records a change for [`get_tasks_changed_since`] and tells the registered
+/// callbacks about it. Must be called with no lock held.
+fn notify_change(kind: &'static str, task_id: Option<u32>) {
+    lock_revisions().record(task_id, kind == "remove");
+    notify_listeners(&ChangeEvent { kind, task_id, error: None });
+}
+
+/// Tells the registered callbacks that the tasks could not be saved. Must be
+/// called with no lock held.
+fn notify_storage_error(error: &str) {
+    notify_listeners(&ChangeEvent { kind: "storage_error", task_id: None, error: Some(error.to_string()) });
+}
+
+fn notify_listeners(event: &ChangeEvent) {
+    // A copy, so a callback can register or unregister while we iterate
+    let listeners: Vec<js_sys::Function> = CHANGE_LISTENERS
+        .with(|listeners| listeners.borrow().iter().map(|(_, callback)| callback.clone()).collect());
+    if listeners.is_empty() {
+        return;
+    }
+    let event = match to_js(event) {
+        Ok(event) => event,
+        Err(e) => return warn(&format!("failed to build change event: {}", e)),
+    };
+    for callback in listeners {
+        if let Err(e) = callback.call1(&JsValue::NULL, &event) {
+            warn(&format!("change callback threw: {:?}", e));
+        }
+    }
+}
+
+/// What [`init`] found, for the page to tell a first start from a restart.
+///
+/// ```js
+/// const report = init('work');
+/// console.log(`${report.loaded_count} tasks from ${report.storage_kind}`);
+/// report.free();
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitReport {
+    loaded_count: u32,
+    storage_kind: &'static str,
+    migrated: bool,
+}
+
+#[wasm_bindgen]
+impl InitReport {
+    /// Tasks read from storage; 0 when nothing was saved yet
+    #[wasm_bindgen(getter)]
+    pub fn loaded_count(&self) -> u32 {
+        self.loaded_count
+    }
+    
+    /// `localStorage`, `indexedDB`, or `memory` when the browser refuses
+    /// storage and tasks last only as long as the page
+    #[wasm_bindgen(getter)]
+    pub fn storage_kind(&self) -> String {
+        self.storage_kind.to_string()
+    }
+    
+    /// Whether the tasks were moved from localStorage into IndexedDB
+    #[wasm_bindgen(getter)]
+    pub fn migrated(&self) -> bool {
+        self.migrated
+    }
+}
+
+/// Switches to localStorage, or to memory when the browser refuses it.
+/// Memory already in use is kept, along with any other namespace in it.
+fn use_local_storage(key: &str) {
+    let mut backend = lock_storage();
+    if backend.kind() == "memory" {
+        return;
+    }
+    let available = if cfg!(target_arch = "wasm32") {
+        LocalStorage.load(key).map(|_| ())
+    } else {
+        Err("localStorage is only available in a browser".to_string())
+    };
+    match available {
+        Ok(()) => *backend = Box::new(LocalStorage),
+        Err(e) => {
+            warn(&format!("{}, keeping tasks in memory for this page", e));
+            *backend = Box::new(MemoryStorage::new());
+        }
+    }
+}
+
+/// Replaces the tasks with those saved under `key`, which later saves use.
+/// Unreadable tasks are reported and leave an empty list.
+fn load_tasks(key: String, migrated: bool) -> Result<InitReport, WasmError> {
+    let loaded = storage::load_tasks(lock_storage().as_ref(), &key);
+    let storage_kind = lock_storage().kind();
+    *lock_storage_key() = key;
+    let (manager, result) = match loaded {
+        Ok(manager) => {
+            let manager = manager.unwrap_or_else(TaskManager::new);
+            let loaded_count = manager.get_total_count() as u32;
+            console_log!("Loaded {} tasks from {}", loaded_count, storage_kind);
+            (manager, Ok(InitReport { loaded_count, storage_kind, migrated }))
+        }
+        Err(e) => {
+            warn(&format!("Failed to load tasks, starting empty: {}", e));
+            (TaskManager::new(), Err(WasmError::Storage(e)))
+        }
+    };
+    *lock_tasks() = manager;
+    lock_revisions().record(None, false);
+    result
+}
+
+/// Loads the tasks saved under `namespace`, or those saved without one, and
+/// reports what it found. Call it before any other binding.
+///
+/// Namespaces keep apps on one origin apart: each has its own task list,
+/// saved under `gh_actions:{namespace}:tasks`. Calling `init` again switches
+/// to another one, after saving pending changes under the current one.
+///
+/// Tasks are kept in localStorage, or in memory when the browser refuses it.
+/// Saved tasks that cannot be read throw a `StorageError`; the bindings then
+/// work on an empty list, which the next change saves over them.
+#[wasm_bindgen]
+pub fn init(namespace: Option<String>) -> Result<InitReport, WasmError> {
+    // Report panics with their message and location instead of "unreachable executed"
+    #[cfg(all(feature = "console_error_panic_hook", target_arch = "wasm32"))]
+    console_error_panic_hook::set_once();
+    let key = storage::tasks_key(namespace.as_deref()).map_err(WasmError::InvalidArgument)?;
+    flush_saves();
+    use_local_storage(&key);
+    console_log!("WASM Task Manager initialized!");
+    load_tasks(key, false)
+}
+
+/// Like [`init`], but keeps tasks in IndexedDB. The returned promise
+/// resolves once the tasks are loaded; call nothing else before that.
+///
+/// Tasks saved in localStorage by [`init`] move to IndexedDB the first time,
+/// which the report shows as `migrated`. Without IndexedDB this falls back to
+/// localStorage.
+#[cfg(feature = "indexeddb")]
+#[wasm_bindgen]
+pub async fn init_async(namespace: Option<String>) -> Result<InitReport, WasmError> {
+    #[cfg(all(feature = "console_error_panic_hook", target_arch = "wasm32"))]
+    console_error_panic_hook::set_once();
+    let key = storage::tasks_key(namespace.as_deref()).map_err(WasmError::InvalidArgument)?;
+    flush_saves();
+    let migrated = match crate::indexed_db::IndexedDbStorage::open(&[&key]).await {
+        Ok(indexed_db) => {
+            let migrated = indexed_db.was_migrated(&key);
+            *lock_storage() = Box::new(indexed_db);
+            console_log!("WASM Task Manager initialized with IndexedDB storage");
+            migrated
+        }
+        Err(e) => {
+            warn(&format!("IndexedDB is unavailable, using localStorage: {}", e));
+            use_local_storage(&key);
+            false
+        }
+    };
+    load_tasks(key, migrated)
+}
+
+/// Whether the task store can still be used.
+///
+/// On wasm32 a panic aborts instead of unwinding, so a binding that panics
+/// while using the tasks leaves them in use for good. This reports `false`
+/// in that case so the page can ask the user to reload, or call
+/// [`reset_task_manager`]. Natively a poisoned lock counts as healthy, since
+/// the next call recovers it.
+#[wasm_bindgen]
+pub fn is_healthy() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+        TASK_MANAGER.with(|tasks| tasks.get().try_borrow_mut().is_ok())
+            && STORAGE.with(|storage| storage.try_borrow_mut().is_ok())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        !matches!(TASK_MANAGER.try_lock(), Err(TryLockError::WouldBlock))
+            && !matches!(STORAGE.try_lock(), Err(TryLockError::WouldBlock))
+    }
+}
+
+/// Replaces the tasks in memory with an empty list, for when an earlier
+/// panic left them beyond repair.
+///
+/// Nothing is saved and a pending save is dropped, so the stored tasks
+/// survive: call [`init`] to load them again, or the next change saves the
+/// empty list over them. On wasm32 the old store is abandoned rather than
+/// borrowed, so this works even while a panic has left it in use.
+#[wasm_bindgen]
+pub fn reset_task_manager() {
+    #[cfg(target_arch = "wasm32")]
+    TASK_MANAGER.with(|tasks| tasks.set(leak(TaskManager::new())));
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        *lock_tasks() = TaskManager::new();
+    }
+    let mut pending = lock_pending_save();
+    cancel_save_timer(&mut pending);
+    pending.dirty = false;
+    drop(pending);
+    warn("Task store reset, unsaved changes were dropped");
+    notify_change("clear", None);
+}
+
+#[wasm_bindgen]
+pub fn add_task(title: String, description: String) -> Result<u32, WasmError> {
+    let mut manager = lock_tasks();
+    let id = manager.add_task(title, description).map_err(WasmError::InvalidArgument)?;
+    console_log!("Added task with id: {}", id);
+    drop(manager); // Release the lock before saving
+    schedule_save();
+    notify_change("add", Some(id));
+    Ok(id)
+}
+
+/// Returns `{"toggled": bool, "new_task_id": number | null}`, where
+/// `new_task_id` is the next occurrence created for a recurring task.
+#[wasm_bindgen]
+pub fn toggle_task(id: u32) -> Result<String, WasmError> {
+    serde_json::to_string(&toggle(id)).map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+/// Toggles a task and saves, for callers on the Rust side
+pub(crate) fn toggle(id: u32) -> ToggleResult {
+    let mut manager = lock_tasks();
+    let result = manager.toggle_task(id);
+    console_log!("Toggled task {}: {}", id, result.toggled);
+    drop(manager); // Release the lock before saving
+    schedule_save();
+    if result.toggled {
+        notify_change("toggle", Some(id));
+    }
+    if let Some(new_id) = result.new_task_id {
+        notify_change("add", Some(new_id));
+    }
+    result
+}
+
+#[wasm_bindgen]
+pub fn remove_task(id: u32) -> Result<bool, WasmError> {
+    let mut manager = lock_tasks();
+    let success = manager.remove_task(id);
+    console_log!("Removed task {}: {}", id, success);
+    drop(manager); // Release the lock before saving
+    schedule_save();
+    if success {
+        notify_change("remove", Some(id));
+    }
+    Ok(success)
+}
+
+/// Removes every completed task and returns how many there were
+#[wasm_bindgen]
+pub fn retain_incomplete_tasks() -> u32 {
+    let mut manager = lock_tasks();
+    let mut removed = Vec::new();
+    manager.retain(|task| {
+        if task.completed {
+            removed.push(task.id);
+        }
+        !task.completed
+    });
+    console_log!("Removed {} completed tasks", removed.len());
+    drop(manager); // Release the lock before saving
+    if !removed.is_empty() {
+        schedule_save();
+    }
+    for &id in &removed {
+        notify_change("remove", Some(id));
+    }
+    removed.len() as u32
+}
+
+/// One entry of what [`toggle_tasks`] and [`remove_tasks`] return
+#[derive(Debug, PartialEq, Serialize)]
+struct BatchOutcome {
+    id: u32,
+    ok: bool,
+    /// Why the task was left alone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// The next occurrence created by completing a recurring task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_task_id: Option<u32>,
+}
+
+/// Toggles every task in `ids` under one lock and saves once. Returns a JSON
+/// array with an entry per ID, in order: `{"id", "ok", "error"?,
+/// "new_task_id"?}`. A missing or repeated ID gets `ok: false` and an
+/// `error`, and the rest of the batch still applies.
+#[wasm_bindgen]
+pub fn toggle_tasks(ids: Vec<u32>) -> Result<String, WasmError> {
+    let outcomes = apply_batch(&ids, "toggle", |manager, id| manager.toggle_task(id).new_task_id);
+    serde_json::to_string(&outcomes).map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+/// Removes every task in `ids` under one lock and saves once. Returns the
+/// same JSON as [`toggle_tasks`].
+#[wasm_bindgen]
+pub fn remove_tasks(ids: Vec<u32>) -> Result<String, WasmError> {
+    let outcomes = apply_batch(&ids, "remove", |manager, id| {
+        manager.remove_task(id);
+        None
+    });
+    serde_json::to_string(&outcomes).map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+/// Calls `apply` on each existing task in `ids`, skipping repeats, then
+/// saves once and reports each change as `kind`. `apply` returns the ID of a
+/// task it added, if any.
+fn apply_batch(
+    ids: &[u32],
+    kind: &'static str,
+    mut apply: impl FnMut(&mut TaskManager, u32) -> Option<u32>,
+) -> Vec<BatchOutcome> {
+    let mut manager = lock_tasks();
+    let mut seen = HashSet::new();
+    let outcomes: Vec<BatchOutcome> = ids
+        .iter()
+        .map(|&id| {
+            let error = if !seen.insert(id) {
+                Some(format!("task {} appears more than once", id))
+            } else if manager.get_task(id).is_none() {
+                Some(WasmError::TaskNotFound(id).to_string())
+            } else {
+                None
+            };
+            match error {
+                Some(error) => BatchOutcome { id, ok: false, error: Some(error), new_task_id: None },
+                None => BatchOutcome { id, ok: true, error: None, new_task_id: apply(&mut manager, id) },
+            }
+        })
+        .collect();
+    console_log!("Batch {} of {} tasks", kind, ids.len());
+    drop(manager); // Release the lock before saving
+
+    if outcomes.iter().any(|outcome| outcome.ok) {
+        schedule_save();
+    }
+    for outcome in outcomes.iter().filter(|outcome| outcome.ok) {
+        notify_change(kind, Some(outcome.id));
+        if let Some(new_id) = outcome.new_task_id {
+            notify_change("add", Some(new_id));
+        }
+    }
+    outcomes
+}
+
+/// Changes the title and description of a task. Returns the task as JSON.
+/// Throws an `InvalidArgumentError` for a blank title.
+#[wasm_bindgen]
+pub fn update_task(id: u32, title: String, description: String) -> Result<String, WasmError> {
+    if title.trim().is_empty() {
+        return Err(WasmError::InvalidArgument("task title cannot be empty".to_string()));
+    }
+    edit_task(id, |task| {
+        task.title = title;
+        task.description = description;
+        Ok(())
+    })
+}
+
+/// Sets the priority to `low`, `medium` or `high`. Returns the task as JSON.
+#[wasm_bindgen]
+pub fn set_task_priority(id: u32, priority: &str) -> Result<String, WasmError> {
+    let priority = Priority::parse(priority).map_err(WasmError::InvalidArgument)?;
+    edit_task(id, |task| {
+        task.priority = priority;
+        Ok(())
+    })
+}
+
+/// Adds a tag; adding one the task already has changes nothing. Returns the
+/// task as JSON.
+#[wasm_bindgen]
+pub fn add_task_tag(id: u32, tag: &str) -> Result<String, WasmError> {
+    edit_task(id, |task| task.add_tag(tag).map(|_| ()).map_err(WasmError::InvalidArgument))
+}
+
+/// Removes a tag, ignoring case. Returns the task as JSON.
+#[wasm_bindgen]
+pub fn remove_task_tag(id: u32, tag: &str) -> Result<String, WasmError> {
+    edit_task(id, |task| {
+        task.remove_tag(tag);
+        Ok(())
+    })
+}
+
+/// Sets the due date from an RFC 3339 timestamp such as
+/// `2024-03-10T17:00:00+01:00`, or clears it when `iso_string` is empty.
+/// Returns the task as JSON.
+#[wasm_bindgen]
+pub fn set_task_due_date(id: u32, iso_string: &str) -> Result<String, WasmError> {
+    let due_date = parse_due_date(iso_string).map_err(WasmError::InvalidArgument)?;
+    edit_task(id, |task| {
+        task.due_date = due_date;
+        Ok(())
+    })
+}
+
+/// Adds a task from a JSON object in one call and returns it as JSON:
+///
+/// ```js
+/// add_task_full(JSON.stringify({
+///   title: "File taxes",
+///   priority: "high",
+///   due_date: "2024-04-15T00:00:00Z",
+///   tags: ["admin"],
+/// }));
+/// ```
+///
+/// `description`, `priority`, `due_date`, `tags` and `estimated_hours` are
+/// optional. Nothing is added if any field is invalid.
+#[wasm_bindgen]
+pub fn add_task_full(json: &str) -> Result<String, WasmError> {
+    let task = TaskSpec::from_json(json)
+        .and_then(TaskSpec::into_task)
+        .map_err(WasmError::InvalidArgument)?;
+
+    let mut manager = lock_tasks();
+    let id = manager.add_task_from(task).map_err(WasmError::InvalidArgument)?;
+    let json = task_json(&manager[TaskId(id)])?;
+    console_log!("Added task with id: {}", id);
+    drop(manager); // Release the lock before saving
+    schedule_save();
+    notify_change("add", Some(id));
+    Ok(json)
+}
+
+/// Puts back a task removed earlier, given as the JSON of the whole task,
+/// under its own ID. Returns the task as JSON. Throws an
+/// `InvalidArgumentError` if a task with that ID exists.
+#[wasm_bindgen]
+pub fn restore_task(json: &str) -> Result<String, WasmError> {
+    let task: Task = serde_json::from_str(json)
+        .map_err(|e| WasmError::InvalidArgument(format!("invalid task: {}", e)))?;
+    let id = task.id;
+
+    let mut manager = lock_tasks();
+    if manager.get_task(id).is_some() {
+        return Err(WasmError::InvalidArgument(format!("task {} already exists", id)));
+    }
+    manager.extend([task]);
+    let json = task_json(&manager[TaskId(id)])?;
+    console_log!("Restored task {}", id);
+    drop(manager); // Release the lock before saving
+    schedule_save();
+    notify_change("add", Some(id));
+    Ok(json)
+}
+
+/// Applies `change` to the task with `id`, then saves and notifies like
+/// every other mutation. Returns the updated task as JSON.
+fn edit_task(id: u32, change: impl FnOnce(&mut Task) -> Result<(), WasmError>) -> Result<String, WasmError> {
+    let mut manager = lock_tasks();
+    let json = edit_in(&mut manager, id, change)?;
+    console_log!("Updated task {}", id);
+    drop(manager); // Release the lock before saving
+    schedule_save();
+    notify_change("update", Some(id));
+    Ok(json)
+}
+
+/// The store-side half of [`edit_task`], without logging or saving. A
+/// failed change leaves the task as it was.
+fn edit_in(
+    manager: &mut TaskManager,
+    id: u32,
+    change: impl FnOnce(&mut Task) -> Result<(), WasmError>,
+) -> Result<String, WasmError> {
+    let task = manager.get_task_mut(id).ok_or(WasmError::TaskNotFound(id))?;
+    let mut edited = task.clone();
+    change(&mut edited)?;
+    *task = edited;
+    task_json(task)
+}
+
+fn task_json(task: &Task) -> Result<String, WasmError> {
+    serde_json::to_string(task).map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+/// Every task as a versioned JSON backup, for the page to offer as a download
+#[wasm_bindgen]
+pub fn export_backup() -> Result<String, WasmError> {
+    storage::export_backup(&lock_tasks(), Utc::now()).map_err(WasmError::Serialization)
+}
+
+/// Restores a backup made by [`export_backup`] and returns a summary to show
+/// the user.
+///
+/// With `merge`, the backup is merged into the current tasks, keeping the
+/// newer copy of a task present in both. Otherwise it replaces them. A
+/// corrupt backup throws and leaves the current tasks untouched.
+#[wasm_bindgen]
+pub fn import_backup(data: &str, merge: bool) -> Result<String, WasmError> {
+    let tasks = storage::parse_backup(data).map_err(WasmError::InvalidBackup)?;
+    let mut manager = lock_tasks();
+    let summary = storage::restore_backup(&mut manager, tasks, merge).map_err(WasmError::InvalidBackup)?;
+    console_log!("{}", summary);
+    drop(manager); // Release the lock before saving
+    // Written at once, in a single save, so a restore survives closing the tab
+    save_now();
+    notify_change("import", None);
+    Ok(summary)
+}
+
+/// Adds the tasks in another app's JSON export and returns a summary such as
+/// `Imported 12 tasks (1 skipped)`.
+///
+/// `format` is `todoist` or `ticktick`. Imported tasks get new IDs, so
+/// importing the same file twice adds them twice. An unreadable export
+/// throws an `InvalidArgumentError` and adds nothing.
+#[wasm_bindgen]
+pub fn import_external(format: &str, data: &str) -> Result<String, WasmError> {
+    let mut manager = lock_tasks();
+    let summary = match format.trim().to_ascii_lowercase().as_str() {
+        "todoist" => manager.import_todoist(data),
+        "ticktick" => manager.import_ticktick(data),
+        _ => Err(format!("unknown import format '{}', expected todoist or ticktick", format)),
+    }
+    .map_err(WasmError::InvalidArgument)?
+    .to_string();
+    console_log!("{}", summary);
+    drop(manager); // Release the lock before saving
+    save_now();
+    notify_change("import", None);
+    Ok(summary)
+}
+
+/// Adds the tasks in `json`, an array as returned by [`get_all_tasks_json`],
+/// under their own IDs. Tasks whose ID is already taken are left out.
+///
+/// Returns `{added, skipped_ids}` JSON. Input that is not a task array throws
+/// an `InvalidArgumentError` and adds nothing.
+#[wasm_bindgen]
+pub fn merge_tasks_json(json: String) -> Result<String, WasmError> {
+    let tasks: Vec<Task> = serde_json::from_str(&json)
+        .map_err(|e| WasmError::InvalidArgument(format!("invalid task list: {}", e)))?;
+    let result = lock_tasks().merge_manager(tasks.into_iter().collect());
+    console_log!("Merged {} tasks, skipped {}", result.added, result.skipped_ids.len());
+    save_now();
+    notify_change("import", None);
+    serde_json::to_string(&result).map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+/// Starts wiping every task and returns the token that
+/// [`confirm_clear_all`] needs to finish.
+///
+/// The token is good for one use within 30 seconds, and only the latest one
+/// is accepted. It guards against a stray call, not an attacker: it is not
+/// cryptographically random.
+#[wasm_bindgen]
+pub fn request_clear_all() -> String {
+    issue_clear_token(&mut lock_clear_token(), Utc::now())
+}
+
+/// Deletes every task, in memory and in storage, if `token` is the one
+/// [`request_clear_all`] returned last and has not expired. Otherwise throws
+/// an `InvalidArgumentError` and leaves the tasks alone.
+#[wasm_bindgen]
+pub fn confirm_clear_all(token: &str) -> Result<(), WasmError> {
+    take_clear_token(&mut lock_clear_token(), token, Utc::now())?;
+    *lock_tasks() = TaskManager::new();
+    console_log!("Cleared all tasks");
+    // Written at once, so the tasks do not come back with the next page load
+    save_now();
+    notify_change("clear", None);
+    Ok(())
+}
+
+/// Replaces every task with a few examples for first-time users and returns
+/// how many there are. Nothing asks for confirmation, so offer it on an
+/// empty list or after [`confirm_clear_all`].
+#[wasm_bindgen]
+pub fn reset_to_sample_data() -> u32 {
+    let manager = sample_tasks(Utc::now());
+    let count = manager.get_total_count() as u32;
+    *lock_tasks() = manager;
+    console_log!("Loaded {} sample tasks", count);
+    save_now();
+    notify_change("import", None);
+    count
+}
+
+/// Stores a new token in `slot`, replacing any earlier one.
+fn issue_clear_token(slot: &mut Option<ClearToken>, now: DateTime<Utc>) -> String {
+    let issued = CLEAR_TOKENS_ISSUED.fetch_add(1, Ordering::Relaxed);
+    let seed = format!("{}:{}", issued, now.timestamp_nanos_opt().unwrap_or_default());
+    let token = hex_encode(&Sha256::digest(seed.as_bytes())[..8]);
+    *slot = Some(ClearToken { token: token.clone(), expires_at: now + Duration::seconds(CLEAR_TOKEN_TTL_SECS) });
+    token
+}
+
+/// Uses up the token in `slot` if it matches `token` and is still valid.
+/// A wrong token leaves it in place, so the right one still works.
+fn take_clear_token(slot: &mut Option<ClearToken>, token: &str, now: DateTime<Utc>) -> Result<(), WasmError> {
+    let invalid = |message: &str| Err(WasmError::InvalidArgument(message.to_string()));
+    match slot.take() {
+        None => invalid("no clear is pending, call request_clear_all first"),
+        Some(issued) if now >= issued.expires_at => invalid("confirmation token expired, request a new one"),
+        Some(issued) if issued.token != token => {
+            *slot = Some(issued);
+            invalid("confirmation token does not match the latest request")
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// A handful of tasks showing off priorities, tags, due dates and a
+/// recurring task, created over the past few days so the charts have data.
+fn sample_tasks(now: DateTime<Utc>) -> TaskManager {
+    let mut manager = TaskManager::new();
+    let mut add = |title: &str, description: &str, days_ago: i64, tags: &[&str]| {
+        let mut task = Task::new(0, title.to_string(), description.to_string());
+        task.created_at = now - Duration::days(days_ago);
+        task.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        manager.add_task_from(task).expect("a new manager has free IDs")
+    };
+
+    let welcome = add("Welcome to Task Manager", "Tick a task to complete it", 3, &[]);
+    let plan = add("Plan the week", "Pick the three things that matter most", 2, &["planning"]);
+    let groceries = add("Buy groceries", "Milk, eggs, bread", 1, &["errands"]);
+    let report = add("Send the monthly report", "", 1, &["work"]);
+    let read = add("Read a chapter", "", 0, &["personal"]);
+
+    manager[TaskId(welcome)].completed = true;
+    manager[TaskId(welcome)].completed_at = Some(now - Duration::days(2));
+    manager[TaskId(plan)].priority = Priority::High;
+    manager[TaskId(plan)].recurrence = Some(Recurrence::Weekly);
+    manager[TaskId(plan)].due_date = Some(now + Duration::days(1));
+    manager[TaskId(groceries)].due_date = Some(now + Duration::days(2));
+    manager[TaskId(report)].priority = Priority::High;
+    manager[TaskId(report)].estimated_hours = Some(2.0);
+    manager[TaskId(report)].due_date = Some(now + Duration::days(5));
+    manager[TaskId(read)].priority = Priority::Low;
+    manager
+}
+
+/// Headline numbers for the task list, read from JavaScript as properties:
+///
+/// ```js
+/// const stats = get_stats();
+/// label.textContent = `${stats.completed} of ${stats.total} done`;
+/// stats.free();
+/// ```
+///
+/// Velocity and streak cover the last 30 days in the browser's time zone.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WasmStats {
+    total: u32,
+    completed: u32,
+    remaining: u32,
+    completion_rate: f64,
+    average_hours: f64,
+    median_hours: f64,
+    velocity: f64,
+    streak: u32,
+}
+
+#[wasm_bindgen]
+impl WasmStats {
+    #[wasm_bindgen(getter)]
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+    
+    #[wasm_bindgen(getter)]
+    pub fn completed(&self) -> u32 {
+        self.completed
+    }
+    
+    #[wasm_bindgen(getter)]
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+    
+    /// Completed tasks as a fraction of all tasks, from 0 to 1
+    #[wasm_bindgen(getter)]
+    pub fn completion_rate(&self) -> f64 {
+        self.completion_rate
+    }
+    
+    /// Mean hours from creation to completion; 0 before any completion
+    #[wasm_bindgen(getter)]
+    pub fn average_hours(&self) -> f64 {
+        self.average_hours
+    }
+    
+    /// Median hours from creation to completion; 0 before any completion
+    #[wasm_bindgen(getter)]
+    pub fn median_hours(&self) -> f64 {
+        self.median_hours
+    }
+    
+    /// Tasks completed per day
+    #[wasm_bindgen(getter)]
+    pub fn velocity(&self) -> f64 {
+        self.velocity
+    }
+    
+    /// Consecutive UTC days, ending today, with at least one completion; 0
+    /// until a task has been completed today
+    #[wasm_bindgen(getter)]
+    pub fn streak(&self) -> u32 {
+        self.streak
+    }
+}
+
+impl From<&TaskReport> for WasmStats {
+    fn from(report: &TaskReport) -> Self {
+        WasmStats {
+            total: report.total as u32,
+            completed: report.completed as u32,
+            remaining: report.pending as u32,
+            completion_rate: report.completion_rate,
+            average_hours: report.average_completion_hours.unwrap_or(0.0),
+            median_hours: report.median_completion_hours.unwrap_or(0.0),
+            velocity: report.velocity,
+            streak: report.current_streak,
+        }
+    }
+}
+
+/// Every statistic in one call. The single-number getters such as
+/// [`get_task_count`] read the same report, so they always agree with it.
+#[wasm_bindgen]
+pub fn get_stats() -> WasmStats {
+    WasmStats::from(&stats_report())
+}
+
+/// The report behind [`get_stats`], also shown by the egui app
+pub(crate) fn stats_report() -> TaskReport {
+    lock_tasks().get_report(Utc::now(), STATS_DAYS, browser_offset())
+}
+
+/// The browser's current UTC offset, or UTC outside a browser
+fn browser_offset() -> FixedOffset {
+    if cfg!(target_arch = "wasm32") {
+        // getTimezoneOffset is in minutes, positive west of UTC
+        let offset_minutes = js_sys::Date::new_0().get_timezone_offset() as i32;
+        FixedOffset::west_opt(offset_minutes * 60).unwrap_or(Utc.fix())
+    } else {
+        Utc.fix()
+    }
+}
+
+#[wasm_bindgen]
+pub fn get_task_count() -> u32 {
+    get_stats().total
+}
+
+#[wasm_bindgen]
+pub fn get_completed_count() -> u32 {
+    get_stats().completed
+}
+
+#[wasm_bindgen]
+pub fn get_all_tasks_json() -> Result<String, WasmError> {
+    tasks_json(&lock_tasks().get_all_tasks())
+}
+
+/// The task with `id` as JSON, with every field, for a detail view. Throws a
+/// `NotFoundError` if there is none.
+#[wasm_bindgen]
+pub fn get_task_json(id: u32) -> Result<String, WasmError> {
+    let manager = lock_tasks();
+    let task = manager.get_task(id).ok_or(WasmError::TaskNotFound(id))?;
+    serde_json::to_string(task).map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+/// Every task as a JSON array ordered by ID, with only the fields a list
+/// needs: `id`, `title`, `completed`, `priority`, `due_date` and `tags`. Fetch the
+/// rest with [`get_task_json`].
+#[wasm_bindgen]
+pub fn get_task_summaries_json() -> Result<String, WasmError> {
+    let manager = lock_tasks();
+    let summaries: Vec<TaskSummary> =
+        manager.get_tasks_page(0, usize::MAX, TaskOrder::Id).into_iter().map(TaskSummary::from).collect();
+    serde_json::to_string(&summaries).map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+/// Tasks whose title or description contains `query`, ignoring case, as a
+/// JSON array ordered by ID
+#[wasm_bindgen]
+pub fn search_tasks(query: &str) -> Result<String, WasmError> {
+    tasks_json(&lock_tasks().search(query))
+}
+
+/// Tasks matching `filter` (`all`, `active`, `completed` or `overdue`) as a
+/// JSON array ordered by ID
+#[wasm_bindgen]
+pub fn get_tasks_filtered(filter: &str) -> Result<String, WasmError> {
+    let filter = TaskFilter::parse(filter).map_err(WasmError::InvalidArgument)?;
+    tasks_json(&lock_tasks().get_tasks_filtered(filter, Utc::now()))
+}
+
+/// Tasks with `tag`, ignoring case, as a JSON array ordered by ID
+#[wasm_bindgen]
+pub fn get_tasks_with_tag(tag: &str) -> Result<String, WasmError> {
+    tasks_json(&lock_tasks().get_tasks_with_tag(tag))
+}
+
+/// Up to `limit` tasks in `order` (`id`, `newest`, `due`, `priority`,
+/// `oldest`, `title` or `incomplete`), skipping the first `offset`, as
+/// `{total, tasks}` JSON
+#[wasm_bindgen]
+pub fn get_tasks_page_json(offset: u32, limit: u32, order: &str) -> Result<String, WasmError> {
+    let order = TaskOrder::parse(order).map_err(WasmError::InvalidArgument)?;
+    let manager = lock_tasks();
+    let page = TaskPage {
+        total: manager.get_total_count(),
+        tasks: manager.get_tasks_page(offset as usize, limit as usize, order),
+    };
+    serde_json::to_string(&page).map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+/// What changed since `revision`, as `{revision, full, changed, removed}`
+/// JSON, so a page can keep its copy of the tasks up to date without
+/// fetching them all.
+///
+/// Pass 0 the first time and the returned `revision` after that. `changed`
+/// holds the added and edited tasks and `removed` the IDs of removed ones.
+/// When `full` is true, for the first call or after an import or clear,
+/// `changed` is every task and the page should drop the ones it has.
+#[wasm_bindgen]
+pub fn get_tasks_changed_since(revision: u32) -> Result<String, WasmError> {
+    let manager = lock_tasks();
+    let changes = lock_revisions().changes_since(&manager, revision);
+    serde_json::to_string(&changes).map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+/// The revision [`get_tasks_changed_since`] would return now
+pub(crate) fn revision() -> u32 {
+    lock_revisions().current
+}
+
+/// The JSON array shape shared by the task list bindings
+fn tasks_json(tasks: &[&Task]) -> Result<String, WasmError> {
+    serde_json::to_string(tasks).map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+#[wasm_bindgen]
+pub fn get_completed_tasks_time_series(days: u32) -> String {
+    let plot_data = series_points(&lock_tasks().get_completed_tasks_time_series(days));
+    match serde_json::to_string(&plot_data) {
+        Ok(json) => json,
+        Err(_) => "[]".to_string(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn get_incomplete_tasks_time_series(days: u32) -> String {
+    let plot_data = series_points(&lock_tasks().get_incomplete_tasks_time_series(days));
+    match serde_json::to_string(&plot_data) {
+        Ok(json) => json,
+        Err(_) => "[]".to_string(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn get_cumulative_completed_time_series(days: u32) -> String {
+    let plot_data = series_points(&lock_tasks().get_cumulative_completed_time_series(days));
+    match serde_json::to_string(&plot_data) {
+        Ok(json) => json,
+        Err(_) => "[]".to_string(),
+    }
 }
 
 #[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
+pub fn get_creation_heatmap_json() -> String {
+    let manager = lock_tasks();
+    let counts = manager.get_tasks_created_by_day_of_week();
     
-    // localStorage bindings
-    #[wasm_bindgen(js_name = saveToLocalStorage)]
-    fn save_to_local_storage(data: &str);
+    // Convert to format suitable for the heatmap: [{"day": "Mon", "count": 3}, ...]
+    let heatmap: Vec<serde_json::Value> = counts.iter().enumerate()
+        .map(|(i, count)| {
+            let day = chrono::Weekday::try_from(i as u8).expect("index is below 7");
+            serde_json::json!({
+                "day": day.to_string(),
+                "count": count
+            })
+        })
+        .collect();
     
-    #[wasm_bindgen(js_name = loadFromLocalStorage)]
-    fn load_from_local_storage() -> String;
+    match serde_json::to_string(&heatmap) {
+        Ok(json) => json,
+        Err(_) => "[]".to_string(),
+    }
 }
 
-// Macro for console.log
-macro_rules! console_log {
-    ($($t:tt)*) => (log(&format_args!($($t)*).to_string()))
+/// Incomplete high-priority tasks, oldest first, as a JSON array of tasks.
+#[wasm_bindgen]
+pub fn get_high_priority_incomplete_json() -> String {
+    let manager = lock_tasks();
+    match serde_json::to_string(&manager.get_high_priority_incomplete_tasks()) {
+        Ok(json) => json,
+        Err(_) => "[]".to_string(),
+    }
 }
 
-// Helper function to save tasks to localStorage
-fn save_tasks() {
-    let manager = TASK_MANAGER.lock().unwrap();
-    let tasks = manager.get_all_tasks();
-    match serde_json::to_string(&tasks) {
-        Ok(json) => {
-            save_to_local_storage(&json);
-            console_log!("Tasks saved to localStorage");
-        },
-        Err(e) => console_log!("Failed to serialize tasks: {:?}", e),
-    }
-}
-
-// Helper function to load tasks from localStorage
-fn load_tasks() {
-    let json = load_from_local_storage();
-    if !json.is_empty() && json != "null" {
-        match serde_json::from_str::<Vec<Task>>(&json) {
-            Ok(tasks) => {
-                let mut manager = TASK_MANAGER.lock().unwrap();
-                // Clear existing tasks and load from storage
-                *manager = TaskManager::new();
-                for task in tasks {
-                    // Find the maximum ID to continue sequence
-                    if task.id >= manager.next_id {
-                        manager.next_id = task.id + 1;
-                    }
-                    manager.tasks.insert(task.id, task);
-                }
-                console_log!("Loaded {} tasks from localStorage", manager.get_total_count());
-            },
-            Err(e) => console_log!("Failed to parse tasks from localStorage: {:?}", e),
-        }
-    } else {
-        console_log!("No tasks found in localStorage");
+/// Incomplete tasks created more than `days` days ago, oldest first, as a
+/// JSON array of tasks.
+#[wasm_bindgen]
+pub fn get_stale_tasks_json(days: u32) -> String {
+    let manager = lock_tasks();
+    match serde_json::to_string(&manager.get_tasks_stale_for_n_days(days)) {
+        Ok(json) => json,
+        Err(_) => "[]".to_string(),
     }
 }
 
+/// Completed tasks by hours taken, as a JSON array of
+/// `[bucket_start_hours, count]` pairs. See
+/// [`TaskManager::get_completion_histogram`] for the buckets.
 #[wasm_bindgen]
-pub fn init() {
-    console_log!("WASM Task Manager initialized!");
-    load_tasks();
+pub fn get_completion_histogram_json(bucket_hours: f64) -> String {
+    let histogram = lock_tasks().get_completion_histogram(bucket_hours);
+    match serde_json::to_string(&histogram) {
+        Ok(json) => json,
+        Err(_) => "[]".to_string(),
+    }
 }
 
+/// Tasks by the UTC date they were created on, as a JSON object from
+/// `YYYY-MM-DD` dates to arrays of tasks, oldest first.
 #[wasm_bindgen]
-pub fn add_task(title: String, description: String) -> u32 {
-    let mut manager = TASK_MANAGER.lock().unwrap();
-    let id = manager.add_task(title, description);
-    console_log!("Added task with id: {}", id);
-    drop(manager); // Release the lock before saving
-    save_tasks();
-    id
+pub fn get_tasks_grouped_by_day_json() -> String {
+    let manager = lock_tasks();
+    match serde_json::to_string(&manager.group_by_day_created()) {
+        Ok(json) => json,
+        Err(_) => "{}".to_string(),
+    }
 }
 
+/// Tasks created, completed and removed over the last seven days, with the
+/// completion rate and average completion hours, as a JSON object. Removals
+/// are not saved, so `removed` counts only those since the page loaded.
 #[wasm_bindgen]
-pub fn toggle_task(id: u32) -> bool {
-    let mut manager = TASK_MANAGER.lock().unwrap();
-    let success = manager.toggle_task(id);
-    console_log!("Toggled task {}: {}", id, success);
-    drop(manager); // Release the lock before saving
-    save_tasks();
-    success
+pub fn get_weekly_summary_json() -> String {
+    let summary = lock_tasks().weekly_summary();
+    match serde_json::to_string(&summary) {
+        Ok(json) => json,
+        Err(_) => "{}".to_string(),
+    }
 }
 
+/// Version, commit, build time, target and features of this bundle, as JSON.
 #[wasm_bindgen]
-pub fn remove_task(id: u32) -> bool {
-    let mut manager = TASK_MANAGER.lock().unwrap();
-    let success = manager.remove_task(id);
-    console_log!("Removed task {}: {}", id, success);
-    drop(manager); // Release the lock before saving
-    save_tasks();
-    success
+pub fn get_build_info() -> String {
+    match serde_json::to_string(&BuildInfo::current()) {
+        Ok(json) => json,
+        Err(_) => "{}".to_string(),
+    }
 }
 
+/// Same as [`WasmStats::streak`]
 #[wasm_bindgen]
-pub fn get_task_count() -> u32 {
-    let manager = TASK_MANAGER.lock().unwrap();
-    manager.get_total_count() as u32
+pub fn get_task_completion_streak() -> u32 {
+    lock_tasks().get_completion_streak()
 }
 
+/// Same as [`WasmStats::average_hours`]
 #[wasm_bindgen]
-pub fn get_completed_count() -> u32 {
-    let manager = TASK_MANAGER.lock().unwrap();
-    manager.get_completed_count() as u32
+pub fn get_average_completion_time() -> f64 {
+    get_stats().average_hours
 }
 
+/// Mean absolute percentage error of effort estimates, as a fraction, or
+/// `NaN` when no completed task has an estimate.
 #[wasm_bindgen]
-pub fn get_all_tasks_json() -> String {
-    let manager = TASK_MANAGER.lock().unwrap();
-    let tasks = manager.get_all_tasks();
-    match serde_json::to_string(&tasks) {
-        Ok(json) => json,
-        Err(_) => "[]".to_string(),
-    }
+pub fn get_estimation_accuracy() -> f64 {
+    lock_tasks().get_estimation_accuracy().unwrap_or(f64::NAN)
 }
 
 #[wasm_bindgen]
-pub fn get_completed_tasks_time_series(days: u32) -> String {
-    let manager = TASK_MANAGER.lock().unwrap();
-    let series = manager.get_completed_tasks_time_series(days);
-    
-    // Convert to format suitable for plotting: [[day_offset, count], ...]
-    let plot_data: Vec<[f64; 2]> = series.iter().enumerate()
-        .map(|(i, (_, count))| [i as f64, *count as f64])
-        .collect();
-    
-    match serde_json::to_string(&plot_data) {
+pub fn get_task_completion_predictions() -> String {
+    // [{"task_id": 1, "predicted_hours": 2.5}, ...]
+    match serde_json::to_string(&predictions()) {
         Ok(json) => json,
         Err(_) => "[]".to_string(),
     }
 }
 
+/// Like [`get_all_tasks_json`], but returns an array of task objects.
 #[wasm_bindgen]
-pub fn get_incomplete_tasks_time_series(days: u32) -> String {
-    let manager = TASK_MANAGER.lock().unwrap();
-    let series = manager.get_incomplete_tasks_time_series(days);
-    
-    // Convert to format suitable for plotting: [[day_offset, count], ...]
-    let plot_data: Vec<[f64; 2]> = series.iter().enumerate()
-        .map(|(i, (_, count))| [i as f64, *count as f64])
-        .collect();
-    
-    match serde_json::to_string(&plot_data) {
-        Ok(json) => json,
-        Err(_) => "[]".to_string(),
-    }
+pub fn get_all_tasks() -> Result<JsValue, WasmError> {
+    to_js(&lock_tasks().get_all_tasks())
 }
 
+/// The KPI report over the last `days` days, in the browser's time zone.
 #[wasm_bindgen]
-pub fn get_cumulative_completed_time_series(days: u32) -> String {
-    let manager = TASK_MANAGER.lock().unwrap();
-    let series = manager.get_cumulative_completed_time_series(days);
-    
-    // Convert to format suitable for plotting: [[day_offset, count], ...]
-    let plot_data: Vec<[f64; 2]> = series.iter().enumerate()
-        .map(|(i, (_, count))| [i as f64, *count as f64])
-        .collect();
-    
-    match serde_json::to_string(&plot_data) {
-        Ok(json) => json,
-        Err(_) => "[]".to_string(),
-    }
+pub fn get_report(days: u32) -> Result<JsValue, WasmError> {
+    to_js(&lock_tasks().get_report(Utc::now(), days, browser_offset()))
 }
 
+/// Like [`get_task_completion_predictions`], but returns an array of
+/// `{task_id, predicted_hours}` objects.
 #[wasm_bindgen]
-pub fn get_average_completion_time() -> f64 {
-    let manager = TASK_MANAGER.lock().unwrap();
-    manager.get_average_completion_time_hours().unwrap_or(0.0)
+pub fn get_predictions() -> Result<JsValue, WasmError> {
+    to_js(&predictions())
 }
 
+/// Logs how long handing `task_count` tasks to JavaScript takes as a JSON
+/// string that is then parsed versus as a JS value built directly. Call
+/// `benchmark_task_transfer(5000)` from the console.
 #[wasm_bindgen]
-pub fn get_task_completion_predictions() -> String {
-    let manager = TASK_MANAGER.lock().unwrap();
-    let predictions = manager.predict_task_completion_times();
-    
-    // Convert to JSON format: [{"task_id": 1, "predicted_hours": 2.5}, ...]
-    let prediction_objects: Vec<serde_json::Value> = predictions.iter()
-        .map(|(task_id, hours)| {
-            serde_json::json!({
-                "task_id": task_id,
-                "predicted_hours": hours
-            })
-        })
-        .collect();
-    
-    match serde_json::to_string(&prediction_objects) {
-        Ok(json) => json,
-        Err(_) => "[]".to_string(),
+pub fn benchmark_task_transfer(task_count: u32) -> Result<(), WasmError> {
+    let mut manager = TaskManager::new();
+    for i in 0..task_count {
+        manager.add_task(format!("Task {}", i), "Benchmark task".to_string()).map_err(WasmError::InvalidArgument)?;
     }
+    let tasks = manager.get_all_tasks();
+
+    let start = web_time::Instant::now();
+    let json = serde_json::to_string(&tasks).map_err(|e| WasmError::Serialization(e.to_string()))?;
+    js_sys::JSON::parse(&json).map_err(|e| WasmError::Serialization(format!("{:?}", e)))?;
+    let via_json = start.elapsed();
+
+    let start = web_time::Instant::now();
+    to_js(&tasks)?;
+    let direct = start.elapsed();
+
+    console_log!(
+        "{} tasks: JSON string + JSON.parse {:.1} ms, serde_wasm_bindgen {:.1} ms",
+        task_count,
+        via_json.as_secs_f64() * 1000.0,
+        direct.as_secs_f64() * 1000.0
+    );
+    Ok(())
+}
+
+/// Predicted hours until an incomplete task is completed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Prediction {
+    pub task_id: u32,
+    pub predicted_hours: f64,
+}
+
+/// Runs `f` on the shared task manager. The egui app reads tasks through
+/// this instead of round-tripping through the JSON bindings.
+pub(crate) fn with_tasks<R>(f: impl FnOnce(&TaskManager) -> R) -> R {
+    f(&lock_tasks())
 }
 
+pub(crate) fn predictions() -> Vec<Prediction> {
+    lock_tasks()
+        .predict_task_completion_times()
+        .into_iter()
+        .map(|(task_id, predicted_hours)| Prediction { task_id, predicted_hours })
+        .collect()
+}
+
+/// Builds a plain JS value: objects rather than `Map`s, as `JSON.parse` would give
+fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, WasmError> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| WasmError::Serialization(e.to_string()))
+}
+
+/// A copy of one task that JavaScript can edit and write back.
+///
+/// Changes stay local until [`WasmTask::apply`] is called:
+///
+/// ```js
+/// const task = get_task(3);
+/// task.title = "Renamed";
+/// task.completed = true;
+/// task.apply();
+/// ```
+///
+/// The fields are private on purpose: a `pub` field gets a generated getter,
+/// which clashes with the explicit one and fails the bindgen step.
 #[wasm_bindgen]
 pub struct WasmTask {
-    id: u32,
+    /// `None` until a task built with the constructor is first applied
+    id: Option<u32>,
     title: String,
     description: String,
     completed: bool,
@@ -209,8 +1570,20 @@ pub struct WasmTask {
 
 #[wasm_bindgen]
 impl WasmTask {
+    /// A task that is added to the store by its first `apply()`
+    #[wasm_bindgen(constructor)]
+    pub fn new(title: String, description: String) -> WasmTask {
+        WasmTask {
+            id: None,
+            title,
+            description,
+            completed: false,
+        }
+    }
+    
+    /// `undefined` until the task has been applied
     #[wasm_bindgen(getter)]
-    pub fn id(&self) -> u32 {
+    pub fn id(&self) -> Option<u32> {
         self.id
     }
     
@@ -219,21 +1592,52 @@ impl WasmTask {
         self.title.clone()
     }
     
+    #[wasm_bindgen(setter)]
+    pub fn set_title(&mut self, title: String) {
+        self.title = title;
+    }
+    
     #[wasm_bindgen(getter)]
     pub fn description(&self) -> String {
         self.description.clone()
     }
     
+    #[wasm_bindgen(setter)]
+    pub fn set_description(&mut self, description: String) {
+        self.description = description;
+    }
+    
     #[wasm_bindgen(getter)]
     pub fn completed(&self) -> bool {
         self.completed
     }
+    
+    #[wasm_bindgen(setter)]
+    pub fn set_completed(&mut self, completed: bool) {
+        self.completed = completed;
+    }
+    
+    /// Writes the changes into the task store, schedules a save, and returns
+    /// the task ID.
+    ///
+    /// Changing `completed` goes through the same path as `toggle_task`, so
+    /// completing a recurring task still schedules its next occurrence.
+    pub fn apply(&mut self) -> Result<u32, WasmError> {
+        let mut manager = lock_tasks();
+        let added = self.id.is_none();
+        let id = apply_to(self, &mut manager)?;
+        console_log!("Applied changes to task {}", id);
+        drop(manager); // Release the lock before saving
+        schedule_save();
+        notify_change(if added { "add" } else { "update" }, Some(id));
+        Ok(id)
+    }
 }
 
 impl From<&Task> for WasmTask {
     fn from(task: &Task) -> Self {
         WasmTask {
-            id: task.id,
+            id: Some(task.id),
             title: task.title.clone(),
             description: task.description.clone(),
             completed: task.completed,
@@ -241,31 +1645,711 @@ impl From<&Task> for WasmTask {
     }
 }
 
+/// The store-side half of [`WasmTask::apply`], without logging or saving
+fn apply_to(task: &mut WasmTask, manager: &mut TaskManager) -> Result<u32, WasmError> {
+    let id = match task.id {
+        Some(id) => id,
+        None => manager.add_task(task.title.clone(), task.description.clone()).map_err(WasmError::InvalidArgument)?,
+    };
+    let stored = manager.get_task(id).ok_or(WasmError::TaskNotFound(id))?;
+    if stored.completed != task.completed {
+        manager.toggle_task(id);
+    }
+    let stored = &mut manager[TaskId(id)];
+    stored.title = task.title.clone();
+    stored.description = task.description.clone();
+    task.id = Some(id);
+    Ok(id)
+}
+
+/// A copy of the task with `id`, or `undefined` if there is none
 #[wasm_bindgen]
-pub fn start_egui_app(canvas_id: &str) {
+pub fn get_task(id: u32) -> Option<WasmTask> {
+    lock_tasks().get_task(id).map(WasmTask::from)
+}
+
+/// Runs the egui app on the canvas with `canvas_id`. Only exists on wasm32,
+/// the one target `eframe::WebRunner` supports.
+///
+/// `options` is an optional object such as `{initial_view: "kpi", dark_mode:
+/// true}`, see [`AppOptions`]. The returned promise rejects when the canvas
+/// is missing, the options are invalid, the app is already running, or
+/// WebGL fails to start.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub async fn start_egui_app(canvas_id: String, options: JsValue) -> Result<(), JsValue> {
+    let options: AppOptions = if options.is_undefined() || options.is_null() {
+        AppOptions::default()
+    } else {
+        serde_wasm_bindgen::from_value(options)
+            .map_err(|e| WasmError::InvalidArgument(format!("invalid app options: {}", e)))?
+    };
+    if RUNNER.with(|runner| runner.borrow().is_some()) {
+        return Err(WasmError::InvalidArgument(
+            "the egui app is already running, call stop_egui_app first".to_string(),
+        )
+        .into());
+    }
+    let canvas = web_sys::window()
+        .ok_or("no window object")?
+        .document()
+        .ok_or("no document")?
+        .get_element_by_id(&canvas_id)
+        .ok_or_else(|| WasmError::InvalidArgument(format!("canvas '{}' not found", canvas_id)))?
+        .dyn_into::<HtmlCanvasElement>()
+        .map_err(|_| WasmError::InvalidArgument(format!("element '{}' is not a canvas", canvas_id)))?;
     console_log!("Starting egui app on canvas: {}", canvas_id);
     
-    let web_options = eframe::WebOptions::default();
-    let canvas_id = canvas_id.to_string();
-    wasm_bindgen_futures::spawn_local(async move {
-        let window = web_sys::window().unwrap();
-        let document = window.document().unwrap();
-        let canvas = document
-            .get_element_by_id(&canvas_id)
-            .unwrap()
-            .dyn_into::<HtmlCanvasElement>()
+    // Registered before the await, so a second call made meanwhile is refused
+    let runner = eframe::WebRunner::new();
+    RUNNER.with(|slot| *slot.borrow_mut() = Some(runner.clone()));
+    let started = runner
+        .start(
+            canvas,
+            eframe::WebOptions::default(),
+            Box::new(move |cc| {
+                cc.egui_ctx.set_theme(options.theme());
+                let mut app = TaskManagerApp::with_options(&options);
+                if let Some(storage) = cc.storage {
+                    app.load_settings(storage);
+                }
+                Ok(Box::new(app))
+            }),
+        )
+        .await;
+    if started.is_err() {
+        RUNNER.with(|slot| slot.borrow_mut().take());
+    }
+    started
+}
+
+/// Stops the app started by [`start_egui_app`] and releases its event
+/// handlers, so the page can remove the canvas or start the app again.
+/// Returns whether an app was running.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn stop_egui_app() -> bool {
+    match RUNNER.with(|runner| runner.borrow_mut().take()) {
+        Some(runner) => {
+            runner.destroy();
+            console_log!("Stopped egui app");
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_lock_recovering_clears_poison() {
+        let mutex = Arc::new(Mutex::new(vec![1, 2]));
+        let poisoner = Arc::clone(&mutex);
+        let _ = std::thread::spawn(move || {
+            let mut guard = poisoner.lock().unwrap();
+            guard.push(3);
+            panic!("poison the lock");
+        })
+        .join();
+        assert!(mutex.is_poisoned());
+
+        assert_eq!(*lock_recovering(&mutex, "test"), vec![1, 2, 3]);
+        assert!(!mutex.is_poisoned());
+        lock_recovering(&mutex, "test").push(4);
+        assert_eq!(*mutex.lock().unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_panic_inside_lock_does_not_block_later_calls() {
+        let _globals = reset_globals();
+
+        let _ = std::thread::spawn(|| {
+            let mut manager = lock_tasks();
+            manager.add_task("Half done".to_string(), String::new()).unwrap();
+            panic!("simulated panic while holding the task store");
+        })
+        .join();
+        assert!(TASK_MANAGER.is_poisoned());
+        assert!(is_healthy());
+
+        // What happened before the panic is kept
+        assert_eq!(get_task_count(), 1);
+        add_task("After".to_string(), String::new()).unwrap();
+        assert_eq!(get_task_count(), 2);
+        assert!(!TASK_MANAGER.is_poisoned());
+
+        reset_task_manager();
+        assert_eq!(get_task_count(), 0);
+        assert!(!has_unsaved_changes());
+        let key = lock_storage_key().clone();
+        assert!(lock_storage().load(&key).unwrap().is_none());
+        assert_eq!(add_task("Fresh".to_string(), String::new()).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_apply_to() {
+        let mut manager = TaskManager::new();
+        let mut task = WasmTask::new("Draft".to_string(), String::new());
+        let id = apply_to(&mut task, &mut manager).unwrap();
+        assert_eq!(task.id(), Some(id));
+        assert_eq!(manager.get_task(id).unwrap().title, "Draft");
+
+        task.set_title("Final".to_string());
+        task.set_description("Ready to send".to_string());
+        task.set_completed(true);
+        assert_eq!(apply_to(&mut task, &mut manager), Ok(id));
+        assert_eq!(manager.get_total_count(), 1);
+        let stored = manager.get_task(id).unwrap();
+        assert_eq!(stored.title, "Final");
+        assert_eq!(stored.description, "Ready to send");
+        assert!(stored.completed && stored.completed_at.is_some());
+
+        manager.remove_task(id);
+        assert_eq!(apply_to(&mut task, &mut manager), Err(WasmError::TaskNotFound(id)));
+    }
+
+    #[test]
+    fn test_apply_to_recurring_task() {
+        let mut manager = TaskManager::new();
+        let id = manager.add_task("Water plants".to_string(), String::new()).unwrap();
+        manager[TaskId(id)].recurrence = Some(crate::task::Recurrence::Weekly);
+
+        let mut task = WasmTask::from(manager.get_task(id).unwrap());
+        task.set_completed(true);
+        apply_to(&mut task, &mut manager).unwrap();
+        assert_eq!(manager.get_total_count(), 2);
+    }
+
+    #[test]
+    fn test_edit_in() {
+        let mut manager = TaskManager::new();
+        let id = manager.add_task("Draft".to_string(), String::new()).unwrap();
+
+        let json = edit_in(&mut manager, id, |task| task.add_tag("work").map(|_| ()).map_err(WasmError::InvalidArgument))
             .unwrap();
-        
-        let result = eframe::WebRunner::new()
-            .start(
-                canvas,
-                web_options,
-                Box::new(|_cc| Ok(Box::new(TaskManagerApp::new()))),
-            )
-            .await;
-            
-        if let Err(e) = result {
-            console_log!("Failed to start egui app: {:?}", e);
+        let returned: Task = serde_json::from_str(&json).unwrap();
+        assert_eq!(returned.tags, vec!["work"]);
+
+        let failed = edit_in(&mut manager, id, |task| {
+            task.title = "Half done".to_string();
+            Err(WasmError::InvalidArgument("nope".to_string()))
+        });
+        assert!(failed.is_err());
+        assert_eq!(manager[TaskId(id)].title, "Draft");
+        assert_eq!(edit_in(&mut manager, 99, |_| Ok(())), Err(WasmError::TaskNotFound(99)));
+    }
+
+    /// Held by tests that use the global task store, so they do not see each
+    /// other's changes or locks
+    static GLOBALS: Mutex<()> = Mutex::new(());
+
+    /// Takes [`GLOBALS`] and empties the global task store, with in-memory
+    /// storage and no save pending. Tests hold the returned guard throughout.
+    fn reset_globals() -> MutexGuard<'static, ()> {
+        let globals = lock_recovering(&GLOBALS, "test globals");
+        *lock_storage() = Box::new(MemoryStorage::new());
+        *lock_tasks() = TaskManager::new();
+        *lock_pending_save() = PendingSave::default();
+        globals
+    }
+
+    /// Memory storage that counts its writes
+    struct CountingStorage(MemoryStorage, Arc<AtomicUsize>);
+
+    impl StorageBackend for CountingStorage {
+        fn kind(&self) -> &'static str {
+            "memory"
         }
-    });
-}
\ No newline at end of file
+
+        fn load(&self, key: &str) -> Result<Option<String>, String> {
+            self.0.load(key)
+        }
+
+        fn save(&mut self, key: &str, value: &str) -> Result<(), String> {
+            self.1.fetch_add(1, Ordering::SeqCst);
+            self.0.save(key, value)
+        }
+    }
+
+    /// Memory storage that refuses values longer than its capacity, like a
+    /// full localStorage
+    struct LimitedStorage(MemoryStorage, Arc<AtomicUsize>);
+
+    impl StorageBackend for LimitedStorage {
+        fn kind(&self) -> &'static str {
+            "memory"
+        }
+
+        fn load(&self, key: &str) -> Result<Option<String>, String> {
+            self.0.load(key)
+        }
+
+        fn save(&mut self, key: &str, value: &str) -> Result<(), String> {
+            if value.len() > self.1.load(Ordering::SeqCst) {
+                return Err(format!("storage is full, '{}' was not written ({} bytes)", key, value.len()));
+            }
+            self.0.save(key, value)
+        }
+    }
+
+    /// The tasks last written under the current storage key
+    fn stored_tasks() -> TaskManager {
+        let key = lock_storage_key().clone();
+        storage::load_tasks(lock_storage().as_ref(), &key).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_saves_are_batched() {
+        let _globals = reset_globals();
+        let writes = Arc::new(AtomicUsize::new(0));
+        *lock_storage() = Box::new(CountingStorage(MemoryStorage::new(), Arc::clone(&writes)));
+        let count = || writes.load(Ordering::SeqCst);
+
+        let first = add_task("One".to_string(), String::new()).unwrap();
+        add_task("Two".to_string(), String::new()).unwrap();
+        toggle(first);
+        remove_task(first).unwrap();
+        assert_eq!(count(), 0);
+        assert!(flush_saves());
+        assert_eq!(count(), 1);
+        assert!(!flush_saves());
+        on_page_hide();
+        assert_eq!(count(), 1);
+
+        let backup = storage::export_backup(&lock_tasks(), Utc::now()).unwrap();
+        import_backup(&backup, false).unwrap();
+        assert_eq!(count(), 2);
+        assert!(import_backup("{}", true).is_err());
+        assert_eq!(count(), 2);
+
+        set_autosave(false);
+        let mut task = WasmTask::new("Three".to_string(), String::new());
+        task.apply().unwrap();
+        assert_eq!(count(), 2);
+        on_page_hide();
+        assert_eq!(count(), 3);
+        set_autosave(true);
+
+        let stored = stored_tasks();
+        assert_eq!(stored.get_total_count(), 2);
+    }
+
+    #[test]
+    fn test_failed_saves_stay_pending() {
+        let _globals = reset_globals();
+        let capacity = Arc::new(AtomicUsize::new(usize::MAX));
+        *lock_storage() = Box::new(LimitedStorage(MemoryStorage::new(), Arc::clone(&capacity)));
+
+        add_task("Fits".to_string(), String::new()).unwrap();
+        assert!(has_unsaved_changes());
+        assert!(flush_saves());
+        assert!(!has_unsaved_changes());
+
+        let key = lock_storage_key().clone();
+        let stored_len = lock_storage().load(&key).unwrap().unwrap().len();
+        capacity.store(stored_len, Ordering::SeqCst);
+        let id = add_task("Too much".to_string(), "x".repeat(100)).unwrap();
+        assert!(flush_saves());
+        assert!(has_unsaved_changes());
+        assert_eq!(stored_tasks().get_total_count(), 1);
+
+        // Still full: the next flush tries again and keeps the changes pending
+        on_page_hide();
+        assert!(has_unsaved_changes());
+
+        // Room again: the next change saves everything
+        capacity.store(usize::MAX, Ordering::SeqCst);
+        toggle(id);
+        assert!(flush_saves());
+        assert!(!has_unsaved_changes());
+        let stored = stored_tasks();
+        assert_eq!(stored.get_total_count(), 2);
+        assert!(stored.get_task(id).unwrap().completed);
+    }
+
+    #[test]
+    fn test_task_json_and_summaries() {
+        let _globals = reset_globals();
+        let first = lock_tasks().add_task("Short".to_string(), String::new()).unwrap();
+        let second = lock_tasks().add_task("Long".to_string(), "x".repeat(5_000)).unwrap();
+
+        let summaries: Vec<serde_json::Value> = serde_json::from_str(&get_task_summaries_json().unwrap()).unwrap();
+        let ids: Vec<_> = summaries.iter().map(|summary| summary["id"].as_u64().unwrap() as u32).collect();
+        assert_eq!(ids, vec![first, second]);
+        assert!(summaries.iter().all(|summary| summary.get("description").is_none()));
+        assert_eq!(summaries[1]["title"], "Long");
+
+        let task: serde_json::Value = serde_json::from_str(&get_task_json(second).unwrap()).unwrap();
+        assert_eq!(task["description"].as_str().unwrap().len(), 5_000);
+        assert_eq!(get_task_json(99), Err(WasmError::TaskNotFound(99)));
+    }
+
+    #[test]
+    fn test_completion_histogram_json() {
+        let _globals = reset_globals();
+        assert_eq!(get_completion_histogram_json(4.0), "[]");
+        let id = lock_tasks().add_task("Report".to_string(), String::new()).unwrap();
+        {
+            let mut manager = lock_tasks();
+            let task = manager.get_task_mut(id).unwrap();
+            task.completed = true;
+            task.completed_at = Some(task.created_at + chrono::Duration::hours(9));
+        }
+
+        assert_eq!(get_completion_histogram_json(4.0), "[[0.0,0],[4.0,0],[8.0,1]]");
+        assert_eq!(get_completion_histogram_json(-1.0), "[]");
+    }
+
+    #[test]
+    fn test_tasks_grouped_by_day_json() {
+        let _globals = reset_globals();
+        assert_eq!(get_tasks_grouped_by_day_json(), "{}");
+
+        let id = lock_tasks().add_task("Today".to_string(), String::new()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&get_tasks_grouped_by_day_json()).unwrap();
+        let created = lock_tasks().get_task(id).unwrap().created_at.format("%Y-%m-%d").to_string();
+        assert_eq!(json[&created][0]["id"], id);
+    }
+
+    #[test]
+    fn test_retain_incomplete_tasks() {
+        let _globals = reset_globals();
+
+        let done = add_task("Done".to_string(), String::new()).unwrap();
+        let open = add_task("Open".to_string(), String::new()).unwrap();
+        toggle(done);
+        let revision = revision();
+
+        assert_eq!(retain_incomplete_tasks(), 1);
+        assert!(lock_tasks().get_task(done).is_none());
+        assert!(lock_tasks().get_task(open).is_some());
+        assert_eq!(lock_revisions().changes_since(&lock_tasks(), revision).removed, vec![done]);
+        assert!(flush_saves());
+        assert_eq!(stored_tasks().get_total_count(), 1);
+
+        assert_eq!(retain_incomplete_tasks(), 0);
+        assert!(!has_unsaved_changes());
+    }
+
+    #[test]
+    fn test_restore_task() {
+        let _globals = reset_globals();
+        let id = add_task("Removed".to_string(), "Notes".to_string()).unwrap();
+        let json = get_task_json(id).unwrap();
+        assert!(remove_task(id).unwrap());
+        assert!(flush_saves());
+        let revision = revision();
+
+        let restored: Task = serde_json::from_str(&restore_task(&json).unwrap()).unwrap();
+        assert_eq!((restored.id, restored.title.as_str()), (id, "Removed"));
+        assert_eq!(lock_revisions().changes_since(&lock_tasks(), revision).changed.len(), 1);
+        assert!(flush_saves());
+        assert_eq!(stored_tasks().get_task(id).unwrap().description, "Notes");
+
+        assert!(matches!(restore_task(&json), Err(WasmError::InvalidArgument(_))));
+        assert!(matches!(restore_task("{}"), Err(WasmError::InvalidArgument(_))));
+        assert!(add_task("Next".to_string(), String::new()).unwrap() > id);
+    }
+
+    #[test]
+    fn test_batch_toggle_and_remove() {
+        let _globals = reset_globals();
+        let writes = Arc::new(AtomicUsize::new(0));
+        *lock_storage() = Box::new(CountingStorage(MemoryStorage::new(), Arc::clone(&writes)));
+
+        let first = add_task("One".to_string(), String::new()).unwrap();
+        let second = add_task("Two".to_string(), String::new()).unwrap();
+        let third = add_task("Three".to_string(), String::new()).unwrap();
+        assert!(flush_saves());
+        writes.store(0, Ordering::SeqCst);
+
+        let json = toggle_tasks(vec![first, 99, second, first]).unwrap();
+        let outcomes: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(outcomes.len(), 4);
+        assert_eq!(outcomes[0], serde_json::json!({"id": first, "ok": true}));
+        assert_eq!(outcomes[1], serde_json::json!({"id": 99, "ok": false, "error": "task 99 not found"}));
+        assert_eq!(outcomes[2]["ok"], true);
+        assert_eq!(outcomes[3]["ok"], false);
+        assert!(outcomes[3]["error"].as_str().unwrap().contains("more than once"));
+        {
+            let manager = lock_tasks();
+            assert!(manager.get_task(first).unwrap().completed);
+            assert!(manager.get_task(second).unwrap().completed);
+            assert!(!manager.get_task(third).unwrap().completed);
+        }
+
+        let json = remove_tasks(vec![42, third, first]).unwrap();
+        let outcomes: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        let ok: Vec<bool> = outcomes.iter().map(|outcome| outcome["ok"].as_bool().unwrap()).collect();
+        assert_eq!(ok, vec![false, true, true]);
+        assert_eq!(lock_tasks().get_total_count(), 1);
+
+        // Nothing valid, nothing to save
+        let json = toggle_tasks(vec![first, 7]).unwrap();
+        assert!(json.contains("\"ok\":false") && !json.contains("\"ok\":true"), "{}", json);
+        assert_eq!(toggle_tasks(Vec::new()).unwrap(), "[]");
+
+        assert_eq!(writes.load(Ordering::SeqCst), 0);
+        assert!(flush_saves());
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+        let stored = stored_tasks();
+        assert_eq!(stored.get_total_count(), 1);
+        assert!(stored.get_task(second).unwrap().completed);
+    }
+
+    #[test]
+    fn test_clear_token_mismatch_and_expiry() {
+        let now = Utc::now();
+        let mut slot = None;
+        let err = |result: Result<(), WasmError>| result.unwrap_err().to_string();
+        assert!(err(take_clear_token(&mut slot, "", now)).contains("no clear is pending"));
+
+        let first = issue_clear_token(&mut slot, now);
+        let latest = issue_clear_token(&mut slot, now);
+        assert_eq!(latest.len(), 16);
+        assert_ne!(first, latest);
+        assert!(err(take_clear_token(&mut slot, &first, now)).contains("does not match"));
+        assert_eq!(take_clear_token(&mut slot, &latest, now + Duration::seconds(29)), Ok(()));
+        assert!(err(take_clear_token(&mut slot, &latest, now)).contains("no clear is pending"));
+
+        let expiring = issue_clear_token(&mut slot, now);
+        assert!(err(take_clear_token(&mut slot, &expiring, now + Duration::seconds(30))).contains("expired"));
+        assert!(slot.is_none());
+    }
+
+    #[test]
+    fn test_confirm_clear_all() {
+        let _globals = reset_globals();
+        add_task("Keep me".to_string(), String::new()).unwrap();
+        flush_saves();
+
+        let token = request_clear_all();
+        assert!(confirm_clear_all("not the token").is_err());
+        assert_eq!(get_task_count(), 1);
+        confirm_clear_all(&token).unwrap();
+        assert_eq!(get_task_count(), 0);
+        let stored = stored_tasks();
+        assert_eq!(stored.get_total_count(), 0);
+        assert!(confirm_clear_all(&token).is_err());
+    }
+
+    #[test]
+    fn test_sample_data() {
+        let _globals = reset_globals();
+
+        let count = reset_to_sample_data();
+        assert_eq!(count, 5);
+        assert_eq!(get_task_count(), count);
+        assert_eq!(get_completed_count(), 1);
+        let stored = stored_tasks();
+        assert_eq!(stored.get_total_count(), 5);
+        assert_eq!(add_task("Mine".to_string(), String::new()).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_import_external() {
+        let _globals = reset_globals();
+
+        let export = include_str!("../tests/fixtures/ticktick.json");
+        assert_eq!(import_external("TickTick", export).unwrap(), "Imported 3 tasks (1 skipped)");
+        let stored = stored_tasks();
+        assert_eq!(stored.get_total_count(), 3);
+
+        assert!(import_external("todoist", export).unwrap_err().to_string().contains("`items`"));
+        assert!(import_external("asana", "[]").unwrap_err().to_string().starts_with("unknown import format 'asana'"));
+        assert_eq!(get_task_count(), 3);
+    }
+
+    #[test]
+    fn test_merge_tasks_json() {
+        let _globals = reset_globals();
+        add_task("Mine".to_string(), String::new()).unwrap();
+
+        let other: TaskManager = [
+            Task::new(1, "Clash".to_string(), String::new()),
+            Task::new(7, "New".to_string(), String::new()),
+        ]
+        .into_iter()
+        .collect();
+        let exported = serde_json::to_string(&other.get_all_tasks()).unwrap();
+        let result: serde_json::Value = serde_json::from_str(&merge_tasks_json(exported).unwrap()).unwrap();
+        assert_eq!(result, serde_json::json!({"added": 1, "skipped_ids": [1]}));
+        assert_eq!(stored_tasks().get_total_count(), 2);
+        assert_eq!(add_task("Next".to_string(), String::new()).unwrap(), 8);
+
+        for bad in ["{}", "[{\"title\": \"No ID\"}]", "not json"] {
+            assert!(matches!(merge_tasks_json(bad.to_string()), Err(WasmError::InvalidArgument(_))), "{}", bad);
+        }
+        assert_eq!(get_task_count(), 3);
+    }
+
+    #[test]
+    fn test_init_namespaces() {
+        let _globals = reset_globals();
+
+        let empty = InitReport { loaded_count: 0, storage_kind: "memory", migrated: false };
+        assert_eq!(init(Some("work".to_string())).unwrap(), empty);
+        add_task("Write report".to_string(), String::new()).unwrap();
+        // Switching saves the pending change under the old namespace first
+        assert_eq!(init(Some("home".to_string())).unwrap(), empty);
+        add_task("Mow lawn".to_string(), String::new()).unwrap();
+        add_task("Fix tap".to_string(), String::new()).unwrap();
+        assert_eq!(init(Some("work".to_string())).unwrap().loaded_count(), 1);
+        assert!(get_all_tasks_json().unwrap().contains("Write report"));
+        assert_eq!(init(Some("home".to_string())).unwrap().loaded_count(), 2);
+
+        assert_eq!(init(None).unwrap(), empty);
+        let stored = lock_storage().load("gh_actions:home:tasks").unwrap().unwrap();
+        assert!(stored.contains("Mow lawn") && !stored.contains("Write report"), "{}", stored);
+        assert!(lock_storage().load(storage::TASKS_KEY).unwrap().is_none());
+        assert!(matches!(init(Some(String::new())), Err(WasmError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_init_without_local_storage_or_readable_tasks() {
+        let _globals = reset_globals();
+        // There is no browser here, so localStorage is refused
+        *lock_storage() = Box::new(LocalStorage);
+        let report = init(None).unwrap();
+        assert_eq!((report.loaded_count(), report.storage_kind(), report.migrated()), (0, "memory".into(), false));
+
+        lock_storage().save(storage::TASKS_KEY, "{not json").unwrap();
+        assert!(matches!(init(None), Err(WasmError::Storage(_))));
+        assert_eq!(get_task_count(), 0);
+        add_task("Fresh start".to_string(), String::new()).unwrap();
+        flush_saves();
+        assert_eq!(stored_tasks().get_total_count(), 1);
+    }
+
+    #[test]
+    fn test_stats_match_legacy_getters() {
+        let _globals = reset_globals();
+        assert_eq!(get_stats().average_hours(), 0.0);
+
+        let done = add_task("Done".to_string(), String::new()).unwrap();
+        add_task("Open".to_string(), String::new()).unwrap();
+        lock_tasks()[TaskId(done)].created_at = Utc::now() - Duration::hours(3);
+        toggle(done);
+
+        let stats = get_stats();
+        assert_eq!((stats.total(), stats.completed(), stats.remaining()), (2, 1, 1));
+        assert_eq!(stats.total(), get_task_count());
+        assert_eq!(stats.completed(), get_completed_count());
+        assert_eq!(stats.streak(), get_task_completion_streak());
+        assert_eq!(stats.streak(), 1);
+        assert_eq!(stats.average_hours(), get_average_completion_time());
+        assert!((stats.average_hours() - 3.0).abs() < 0.01);
+        assert_eq!(stats.median_hours(), stats.average_hours());
+        assert_eq!(stats.completion_rate(), 0.5);
+        assert_eq!(stats.velocity(), 1.0 / STATS_DAYS as f64);
+    }
+
+    /// A page's copy of the tasks, kept up to date from
+    /// `get_tasks_changed_since`
+    #[derive(Default)]
+    struct Mirror {
+        revision: u32,
+        tasks: HashMap<u32, Task>,
+    }
+
+    impl Mirror {
+        fn refresh(&mut self) -> usize {
+            let changes: serde_json::Value =
+                serde_json::from_str(&get_tasks_changed_since(self.revision).unwrap()).unwrap();
+            if changes["full"].as_bool().unwrap() {
+                self.tasks.clear();
+            }
+            let changed: Vec<Task> = serde_json::from_value(changes["changed"].clone()).unwrap();
+            let removed: Vec<u32> = serde_json::from_value(changes["removed"].clone()).unwrap();
+            let fetched = changed.len() + removed.len();
+            for id in removed {
+                self.tasks.remove(&id);
+            }
+            self.tasks.extend(changed.into_iter().map(|task| (task.id, task)));
+            self.revision = changes["revision"].as_u64().unwrap() as u32;
+            fetched
+        }
+
+        fn assert_matches_store(&self) {
+            let mut mirrored: Vec<&Task> = self.tasks.values().collect();
+            mirrored.sort_by_key(|task| task.id);
+            let stored = get_tasks_page_json(0, u32::MAX, "id").unwrap();
+            let stored: serde_json::Value = serde_json::from_str(&stored).unwrap();
+            assert_eq!(serde_json::to_value(mirrored).unwrap(), stored["tasks"]);
+        }
+    }
+
+    #[test]
+    fn test_incremental_fetch() {
+        let _globals = reset_globals();
+        lock_revisions().record(None, false);
+
+        let mut mirror = Mirror::default();
+        let first = add_task("One".to_string(), String::new()).unwrap();
+        let second = add_task("Two".to_string(), String::new()).unwrap();
+        assert_eq!(mirror.refresh(), 2);
+        mirror.assert_matches_store();
+        assert_eq!(mirror.refresh(), 0);
+
+        toggle(first);
+        update_task(second, "Two, renamed".to_string(), "Edited".to_string()).unwrap();
+        let third = add_task("Three".to_string(), String::new()).unwrap();
+        remove_task(third).unwrap();
+        assert_eq!(mirror.refresh(), 3);
+        mirror.assert_matches_store();
+
+        lock_tasks()[TaskId(second)].recurrence = Some(Recurrence::Daily);
+        toggle(second);
+        set_task_priority(first, "high").unwrap();
+        let mut task = WasmTask::new("Four".to_string(), String::new());
+        task.apply().unwrap();
+        remove_task(first).unwrap();
+        mirror.refresh();
+        mirror.assert_matches_store();
+        assert!(!mirror.tasks.contains_key(&first));
+
+        let backup = storage::export_backup(&lock_tasks(), Utc::now()).unwrap();
+        let stale = mirror.revision;
+        import_backup(&backup, false).unwrap();
+        let changes: serde_json::Value = serde_json::from_str(&get_tasks_changed_since(stale).unwrap()).unwrap();
+        assert_eq!(changes["full"], true);
+        mirror.refresh();
+        mirror.assert_matches_store();
+
+        let token = request_clear_all();
+        confirm_clear_all(&token).unwrap();
+        add_task("After the clear".to_string(), String::new()).unwrap();
+        mirror.refresh();
+        mirror.assert_matches_store();
+        assert_eq!(mirror.tasks.len(), 1);
+    }
+
+    #[test]
+    fn test_tasks_page_json() {
+        let _globals = reset_globals();
+        for title in ["One", "Two", "Three"] {
+            add_task(title.to_string(), String::new()).unwrap();
+        }
+
+        let page: serde_json::Value = serde_json::from_str(&get_tasks_page_json(1, 5, "id").unwrap()).unwrap();
+        assert_eq!(page["total"], 3);
+        let titles: Vec<&str> =
+            page["tasks"].as_array().unwrap().iter().map(|task| task["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, vec!["Two", "Three"]);
+        let page: serde_json::Value = serde_json::from_str(&get_tasks_page_json(0, 5, "title").unwrap()).unwrap();
+        let titles: Vec<&str> =
+            page["tasks"].as_array().unwrap().iter().map(|task| task["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, vec!["One", "Three", "Two"]);
+        assert!(matches!(get_tasks_page_json(0, 5, "alphabetical"), Err(WasmError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_healthy_when_unlocked() {
+        let _globals = reset_globals();
+        assert!(is_healthy());
+    }
+}
@@ -0,0 +1,86 @@
+//! JavaScript bindings for the helpers in [`crate::utils`], for interactive
+//! widgets on the demo page.
+//!
+//! JavaScript numbers are doubles, exact only up to `Number.MAX_SAFE_INTEGER`
+//! (2^53 - 1). Arguments beyond it are rejected, and results beyond it are
+//! returned as decimal strings.
+
+use crate::utils::{calculate_fibonacci, is_palindrome, is_prime, validate_email};
+use crate::wasm::WasmError;
+use wasm_bindgen::prelude::*;
+
+/// `Number.MAX_SAFE_INTEGER`, the largest integer a JavaScript number holds
+/// exactly, along with every integer below it
+pub const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// Whether `email` looks like a valid address, see [`validate_email`]
+#[wasm_bindgen]
+pub fn wasm_validate_email(email: &str) -> bool {
+    validate_email(email)
+}
+
+/// The `n`th Fibonacci number: a number up to `F(78)`, and a string from
+/// `F(79)`, the first above `Number.MAX_SAFE_INTEGER`. Throws an
+/// `InvalidArgumentError` from `F(94)`, which does not fit in 64 bits.
+#[wasm_bindgen]
+pub fn wasm_fibonacci(n: u32) -> Result<JsValue, WasmError> {
+    calculate_fibonacci(n)
+        .map(u64_to_js)
+        .map_err(|e| WasmError::InvalidArgument(format!("cannot compute Fibonacci number {}: {}", n, e)))
+}
+
+/// Whether `n` is prime. Throws an `InvalidArgumentError` unless `n` is a
+/// whole number from 0 to `Number.MAX_SAFE_INTEGER`.
+#[wasm_bindgen]
+pub fn wasm_is_prime(n: f64) -> Result<bool, WasmError> {
+    safe_integer(n).map(is_prime)
+}
+
+/// Whether `s` reads the same backwards, see [`is_palindrome`]
+#[wasm_bindgen]
+pub fn wasm_is_palindrome(s: &str) -> bool {
+    is_palindrome(s)
+}
+
+/// `value` as a JavaScript number, or as a string when a number would round it
+fn u64_to_js(value: u64) -> JsValue {
+    if value <= MAX_SAFE_INTEGER {
+        JsValue::from_f64(value as f64)
+    } else {
+        JsValue::from_str(&value.to_string())
+    }
+}
+
+/// `value` as a `u64`, if it is a whole number JavaScript holds exactly
+fn safe_integer(value: f64) -> Result<u64, WasmError> {
+    if value.fract() == 0.0 && (0.0..=MAX_SAFE_INTEGER as f64).contains(&value) {
+        Ok(value as u64)
+    } else {
+        Err(WasmError::InvalidArgument(format!(
+            "{} is not a whole number from 0 to {}",
+            value, MAX_SAFE_INTEGER
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_integer() {
+        assert_eq!(safe_integer(0.0), Ok(0));
+        assert_eq!(safe_integer(97.0), Ok(97));
+        assert_eq!(safe_integer(9_007_199_254_740_991.0), Ok(MAX_SAFE_INTEGER));
+        for bad in [-1.0, 2.5, 9_007_199_254_740_992.0, f64::NAN, f64::INFINITY] {
+            assert!(safe_integer(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_fibonacci_boundary() {
+        // The last Fibonacci number returned as a JavaScript number, and the first as a string
+        assert!(calculate_fibonacci(78).unwrap() <= MAX_SAFE_INTEGER);
+        assert!(calculate_fibonacci(79).unwrap() > MAX_SAFE_INTEGER);
+    }
+}
@@ -0,0 +1,48 @@
+//! `AppConfig` reads the real process environment, so everything here runs
+//! in one test to keep the variables from leaking between threads.
+
+use gh_actions::AppConfig;
+use std::env;
+use std::fs;
+
+const VARS: [&str; 4] = [
+    "GHACTIONS_USERS_FILE",
+    "GHACTIONS_TASKS_FILE",
+    "GHACTIONS_LOG_LEVEL",
+    "GHACTIONS_MAX_USERS",
+];
+
+#[test]
+fn env_vars_override_defaults_and_file() {
+    for var in VARS {
+        env::remove_var(var);
+    }
+    assert_eq!(AppConfig::from_env(), AppConfig::default());
+    assert_eq!(AppConfig::load(None), AppConfig::default());
+
+    env::set_var("GHACTIONS_USERS_FILE", "/srv/users.json");
+    env::set_var("GHACTIONS_LOG_LEVEL", "debug");
+    env::set_var("GHACTIONS_MAX_USERS", "many");
+    let config = AppConfig::from_env();
+    assert_eq!(config.users_file, "/srv/users.json");
+    assert_eq!(config.log_level, "debug");
+    assert_eq!(config.max_users, AppConfig::default().max_users);
+    assert_eq!(config.tasks_file, "tasks.json");
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "tasks_file = \"/srv/tasks.json\"\nlog_level = \"error\"\nmax_users = 5\n").unwrap();
+    env::set_var("GHACTIONS_MAX_USERS", "10");
+    let config = AppConfig::load(Some(&path));
+    assert_eq!(config.tasks_file, "/srv/tasks.json");
+    assert_eq!(config.users_file, "/srv/users.json");
+    assert_eq!(config.log_level, "debug");
+    assert_eq!(config.max_users, 10);
+
+    fs::write(&path, "not toml at all").unwrap();
+    assert_eq!(AppConfig::load(Some(&path)).users_file, "/srv/users.json");
+
+    for var in VARS {
+        env::remove_var(var);
+    }
+}
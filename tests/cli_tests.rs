@@ -1,6 +1,17 @@
 use assert_cmd::Command;
 use predicates::prelude::*;
-use tempfile::NamedTempFile;
+use std::fs;
+use tempfile::TempDir;
+
+/// Runs the binary with HOME pointed at a scratch directory so tests never
+/// read or write the real config and data files.
+fn cli(home: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
+    cmd.env("HOME", home.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME");
+    cmd
+}
 
 #[test]
 fn test_cli_help() {
@@ -20,10 +31,41 @@ fn test_cli_version() {
         .stdout(predicate::str::contains("0.1.0"));
 }
 
+#[test]
+fn test_cli_version_includes_commit() {
+    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
+    cmd.arg("--version")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"^gh_actions \d+\.\d+\.\d+ \(([0-9a-f]{7,}|unknown)\)\n$").unwrap());
+}
+
+#[test]
+fn test_version_subcommand() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .arg("version")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"(?m)^version: +\d+\.\d+\.\d+$").unwrap())
+        .stdout(predicate::str::is_match(r"(?m)^commit: +([0-9a-f]{7,}|unknown)$").unwrap())
+        .stdout(predicate::str::is_match(r"(?m)^built: +\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2} UTC$").unwrap())
+        .stdout(predicate::str::is_match(r"(?m)^target: +\S+$").unwrap())
+        .stdout(predicate::str::is_match(r"(?m)^features: +\S").unwrap());
+
+    let output = cli(&home).args(["--output-format", "json", "version"]).output().unwrap();
+    assert!(output.status.success());
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    for key in ["version", "commit", "built_at", "target", "features"] {
+        assert!(info.get(key).is_some(), "missing {} in {}", key, info);
+    }
+    assert!(info["features"].is_array());
+}
+
 #[test]
 fn test_fibonacci_command() {
     let mut cmd = Command::cargo_bin("gh_actions").unwrap();
-    cmd.args(&["fib", "10"])
+    cmd.args(["fib", "10"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Fibonacci of 10 is: 55"));
@@ -32,25 +74,104 @@ fn test_fibonacci_command() {
 #[test]
 fn test_fibonacci_invalid_input() {
     let mut cmd = Command::cargo_bin("gh_actions").unwrap();
-    cmd.args(&["fib", "abc"])
+    cmd.args(["fib", "abc"])
         .assert()
-        .failure()
+        .code(2)
         .stderr(predicate::str::contains("Invalid number"));
 }
 
 #[test]
 fn test_fibonacci_too_large() {
     let mut cmd = Command::cargo_bin("gh_actions").unwrap();
-    cmd.args(&["fib", "100"])
+    cmd.args(["fib", "187"])
         .assert()
-        .failure()
+        .code(2)
         .stderr(predicate::str::contains("too large"));
 }
 
 #[test]
-fn test_user_add_command() {
+fn test_fibonacci_falls_back_to_u128() {
+    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
+    cmd.args(["fib", "93"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
+    cmd.args(["fib", "100"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fibonacci of 100 is: 354224848179261915075"))
+        .stderr(predicate::str::contains("Warning: result too large for u64, using 128-bit"));
+}
+
+#[test]
+fn test_fibonacci_upto() {
+    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
+    cmd.args(["fib", "--upto", "6"])
+        .assert()
+        .success()
+        .stdout("0\n1\n1\n2\n3\n5\n8\n");
+}
+
+#[test]
+fn test_fibonacci_upto_inline() {
+    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
+    cmd.args(["fib", "--upto", "6", "--inline"])
+        .assert()
+        .success()
+        .stdout("0, 1, 1, 2, 3, 5, 8\n");
+}
+
+#[test]
+fn test_fibonacci_between_json() {
+    let home = TempDir::new().unwrap();
+    let output = cli(&home)
+        .args(["--output-format", "json", "fib", "--between", "10", "12"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let values: Vec<u64> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(values, vec![55, 89, 144]);
+}
+
+#[test]
+fn test_fibonacci_range_boundary() {
+    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
+    let output = cmd.args(["fib", "--upto", "93"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 94);
+    assert_eq!(stdout.lines().last(), Some("12200160415121876738"));
+
+    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
+    cmd.args(["fib", "--between", "93", "93"])
+        .assert()
+        .success()
+        .stdout("12200160415121876738\n");
+
+    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
+    cmd.args(["fib", "--upto", "94"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("too large"));
+}
+
+#[test]
+fn test_fibonacci_between_reversed() {
     let mut cmd = Command::cargo_bin("gh_actions").unwrap();
-    cmd.args(&["user", "add", "1", "John Doe", "john@example.com"])
+    cmd.args(["fib", "--between", "5", "2"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid range"));
+}
+
+#[test]
+fn test_user_add_command() {
+    let home = TempDir::new().unwrap();
+    let mut cmd = cli(&home);
+    cmd.args(["user", "add", "1", "John Doe", "john@example.com"])
         .assert()
         .success()
         .stdout(predicate::str::contains("User added successfully!"));
@@ -58,26 +179,114 @@ fn test_user_add_command() {
 
 #[test]
 fn test_user_add_invalid_email() {
-    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
-    cmd.args(&["user", "add", "1", "John Doe", "invalid-email"])
+    let home = TempDir::new().unwrap();
+    let mut cmd = cli(&home);
+    cmd.args(["user", "add", "1", "John Doe", "invalid-email"])
         .assert()
-        .failure()
+        .code(5)
         .stderr(predicate::str::contains("Invalid email format"));
 }
 
+#[test]
+fn test_user_add_duplicate_id() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["user", "add", "1", "John Doe", "john@example.com"])
+        .assert()
+        .success();
+    cli(&home)
+        .args(["user", "add", "1", "Jane Doe", "jane@example.com"])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("User with ID 1 already exists"));
+}
+
+#[test]
+fn test_user_add_duplicate_email() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["user", "add", "1", "John Doe", "john@example.com"])
+        .assert()
+        .success();
+    cli(&home)
+        .args(["user", "add", "2", "Johnny", "John@Example.com"])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("User with email John@Example.com already exists"));
+}
+
+#[test]
+fn test_user_add_batch() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["user", "add", "--batch"])
+        .write_stdin("# team\nAnn,ann@example.com\n\nBob,bob@example.com\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added 2 users"));
+
+    cli(&home)
+        .args(["user", "list", "--columns", "id,name"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1   Ann"))
+        .stdout(predicate::str::contains("2   Bob"));
+}
+
+#[test]
+fn test_user_add_batch_partial_failure() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["user", "add", "--batch"])
+        .write_stdin("Ann,ann@example.com\nBob,not-an-email\nno comma here\nCid,cid@example.com\n")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Added 2 users"))
+        .stderr(predicate::str::contains("line 2: Invalid email format"))
+        .stderr(predicate::str::contains("line 3: expected `name,email`"))
+        .stderr(predicate::str::contains("2 of 4 lines failed"));
+
+    cli(&home)
+        .args(["user", "list", "--columns", "id,name"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2   Cid"));
+}
+
+#[test]
+fn test_task_add_batch() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["task", "add", "--batch"])
+        .write_stdin("Write docs|Usage guide\n# later\nShip release\n |no title\n")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Added 2 tasks"))
+        .stderr(predicate::str::contains("line 4: Task title cannot be empty"));
+
+    cli(&home)
+        .args(["task", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Write docs    Usage guide"))
+        .stdout(predicate::str::contains("Ship release"));
+}
+
 #[test]
 fn test_user_add_invalid_id() {
-    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
-    cmd.args(&["user", "add", "abc", "John Doe", "john@example.com"])
+    let home = TempDir::new().unwrap();
+    let mut cmd = cli(&home);
+    cmd.args(["user", "add", "abc", "John Doe", "john@example.com"])
         .assert()
-        .failure()
+        .code(2)
         .stderr(predicate::str::contains("Invalid user ID"));
 }
 
 #[test]
 fn test_user_list_empty() {
-    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
-    cmd.args(&["user", "list"])
+    let home = TempDir::new().unwrap();
+    let mut cmd = cli(&home);
+    cmd.args(["user", "list"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Users:"));
@@ -86,7 +295,7 @@ fn test_user_list_empty() {
 #[test]
 fn test_user_help() {
     let mut cmd = Command::cargo_bin("gh_actions").unwrap();
-    cmd.args(&["user", "--help"])
+    cmd.args(["user", "--help"])
         .assert()
         .success()
         .stdout(predicate::str::contains("User management operations"));
@@ -106,5 +315,1073 @@ fn test_invalid_command() {
     let mut cmd = Command::cargo_bin("gh_actions").unwrap();
     cmd.arg("invalid-command")
         .assert()
-        .failure();
-}
\ No newline at end of file
+        .code(2);
+}
+
+#[test]
+fn test_config_show_defaults() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("not found"))
+        .stdout(predicate::str::contains("output_format   = text  (default)"));
+}
+
+#[test]
+fn test_config_init_and_show() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["config", "init"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Wrote config template"));
+
+    let path = home.path().join(".config/gh_actions/config.toml");
+    assert!(fs::read_to_string(&path).unwrap().contains("# output_format = \"text\""));
+
+    cli(&home)
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(loaded)"));
+
+    cli(&home)
+        .args(["config", "init"])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("already exists"));
+
+    cli(&home)
+        .args(["config", "init", "--force"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_config_provenance_and_flag_precedence() {
+    let home = TempDir::new().unwrap();
+    let config = home.path().join("custom.toml");
+    fs::write(&config, "output_format = \"json\"\ntimezone_offset = \"+02:00\"\n").unwrap();
+
+    cli(&home)
+        .args(["--config", config.to_str().unwrap(), "--output-format", "text", "config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("output_format   = text  (flag)"))
+        .stdout(predicate::str::contains("timezone_offset = +02:00  (file)"))
+        .stdout(predicate::str::contains("data_dir").and(predicate::str::contains("(default)")));
+
+    cli(&home)
+        .args(["--config", config.to_str().unwrap(), "user", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("["));
+}
+
+#[test]
+fn test_config_data_dir_persists_users() {
+    let home = TempDir::new().unwrap();
+    let data_dir = home.path().join("data");
+    let config = home.path().join("config.toml");
+    fs::write(&config, format!("data_dir = {:?}\n", data_dir.to_str().unwrap())).unwrap();
+
+    cli(&home)
+        .args(["--config", config.to_str().unwrap(), "user", "add", "7", "Jane", "jane@example.com"])
+        .assert()
+        .success();
+    assert!(data_dir.join("users.json").exists());
+
+    cli(&home)
+        .args(["--config", config.to_str().unwrap(), "user", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("7   Jane  jane@example.com  true"));
+}
+
+#[test]
+fn test_malformed_config_names_key() {
+    let home = TempDir::new().unwrap();
+    let config = home.path().join("config.toml");
+    fs::write(&config, "output_format = 3\n").unwrap();
+
+    cli(&home)
+        .args(["--config", config.to_str().unwrap(), "user", "list"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid value for `output_format`"));
+}
+
+#[test]
+fn test_missing_explicit_config() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["--config", "does-not-exist.toml", "config", "show"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("not found"));
+}
+
+#[test]
+fn test_task_add_list_toggle() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["task", "add", "Write docs", "Usage guide"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 1 added successfully!"));
+    cli(&home)
+        .args(["task", "add", "Ship release"])
+        .assert()
+        .success();
+    cli(&home)
+        .args(["task", "toggle", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 1 marked as completed"));
+
+    cli(&home)
+        .args(["task", "list"])
+        .assert()
+        .success()
+        .stdout(
+            "Tasks:\n\
+             ID  Done  Title         Description\n\
+             --  ----  ------------  -----------\n\
+             1   x     Write docs    Usage guide\n\
+             2         Ship release\n",
+        );
+}
+
+#[test]
+fn test_task_toggle_missing() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["task", "toggle", "42"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Task with ID 42 not found"));
+}
+
+/// Writes a task store with fixed timestamps: three of four tasks completed,
+/// taking 170, 60 and 6 hours.
+fn seed_tasks(home: &TempDir) -> std::path::PathBuf {
+    let data_dir = home.path().join("data");
+    fs::create_dir_all(&data_dir).unwrap();
+    let task = |id: u32, created_at: &str, completed_at: Option<&str>| {
+        serde_json::json!({
+            "id": id,
+            "title": format!("Task {}", id),
+            "description": "",
+            "completed": completed_at.is_some(),
+            "created_at": created_at,
+            "completed_at": completed_at,
+        })
+    };
+    let tasks = serde_json::json!([
+        task(1, "2024-03-01T08:00:00Z", Some("2024-03-08T10:00:00Z")),
+        task(2, "2024-03-07T08:00:00Z", Some("2024-03-09T20:00:00Z")),
+        task(3, "2024-03-09T08:00:00Z", Some("2024-03-09T14:00:00Z")),
+        task(4, "2024-03-09T09:00:00Z", None),
+    ]);
+    fs::write(data_dir.join("tasks.json"), tasks.to_string()).unwrap();
+    data_dir
+}
+
+#[test]
+fn test_task_stats() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_tasks(&home);
+
+    cli(&home)
+        .args(["--data-dir", data_dir.to_str().unwrap(), "task", "stats", "--days", "7"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task statistics (last 7 days):"))
+        .stdout(predicate::str::contains("Total tasks:          4"))
+        .stdout(predicate::str::contains("Completed:            3"))
+        .stdout(predicate::str::contains("Pending:              1"))
+        .stdout(predicate::str::contains("Completion rate:      75.0%"))
+        .stdout(predicate::str::contains("Average completion:   78.7 hours"))
+        .stdout(predicate::str::contains("Median completion:    60.0 hours"))
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn test_task_stats_json() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_tasks(&home);
+
+    let output = cli(&home)
+        .args(["--data-dir", data_dir.to_str().unwrap(), "--output-format", "json", "task", "stats"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["days"], 30);
+    assert_eq!(report["total"], 4);
+    assert_eq!(report["completion_rate"], 0.75);
+    assert_eq!(report["median_completion_hours"], 60.0);
+    assert_eq!(report["completions_per_day"].as_array().unwrap().len(), 30);
+}
+
+#[test]
+fn test_task_stats_top_tags_and_oldest_task() {
+    let home = TempDir::new().unwrap();
+    let data_dir = home.path().join("data");
+    fs::create_dir_all(&data_dir).unwrap();
+    let task = |id: u32, title: &str, created_at: &str, completed: bool, tags: &[&str]| {
+        serde_json::json!({
+            "id": id,
+            "title": title,
+            "description": "",
+            "completed": completed,
+            "created_at": created_at,
+            "completed_at": if completed { Some("2024-03-09T12:00:00Z") } else { None },
+            "tags": tags,
+        })
+    };
+    let tasks = serde_json::json!([
+        task(1, "Ship release", "2024-03-01T08:00:00Z", true, &["work", "urgent"]),
+        task(2, "Book dentist", "2024-02-01T08:00:00Z", false, &["home"]),
+        task(3, "Review PR", "2024-03-05T08:00:00Z", false, &["work"]),
+    ]);
+    fs::write(data_dir.join("tasks.json"), tasks.to_string()).unwrap();
+
+    cli(&home)
+        .args(["--data-dir", data_dir.to_str().unwrap(), "task", "stats"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Top tags:             work (2), home (1), urgent (1)"))
+        .stdout(predicate::str::contains("Oldest incomplete:    Book dentist (#2, created "));
+
+    let output = cli(&home)
+        .args(["--data-dir", data_dir.to_str().unwrap(), "--output-format", "json", "task", "stats"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["top_tags"][0], serde_json::json!({"tag": "work", "count": 2}));
+    assert_eq!(report["top_tags"].as_array().unwrap().len(), 3);
+    assert_eq!(report["oldest_incomplete"]["title"], "Book dentist");
+    // Counted up to now, so at least the time to the end of February
+    assert!(report["oldest_incomplete"]["age_hours"].as_f64().unwrap() > 28.0 * 24.0);
+}
+
+#[test]
+fn test_task_stats_rejects_zero_days() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["task", "stats", "--days", "0"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_file_flags_keep_stores_isolated() {
+    let home = TempDir::new().unwrap();
+    let project_a = home.path().join("project-a").join("tasks.json");
+    let project_b = home.path().join("project-b").join("tasks.json");
+
+    cli(&home)
+        .args(["--file", project_a.to_str().unwrap(), "task", "add", "Alpha"])
+        .assert()
+        .success();
+    cli(&home)
+        .args(["--file", project_b.to_str().unwrap(), "task", "add", "Beta"])
+        .assert()
+        .success();
+
+    assert!(project_a.exists() && project_b.exists());
+    cli(&home)
+        .args(["--file", project_a.to_str().unwrap(), "task", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Alpha"))
+        .stdout(predicate::str::contains("Beta").not());
+    cli(&home)
+        .args(["--file", project_b.to_str().unwrap(), "task", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Beta"))
+        .stdout(predicate::str::contains("Alpha").not());
+
+    let users = home.path().join("team").join("users.json");
+    cli(&home)
+        .args(["--users-file", users.to_str().unwrap(), "user", "add", "1", "Ann", "ann@example.com"])
+        .assert()
+        .success();
+    assert!(users.exists());
+    cli(&home)
+        .args(["user", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No users found."));
+}
+
+#[test]
+fn test_file_conflicts_with_data_dir() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["--data-dir", "data", "--file", "tasks.json", "task", "list"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+    cli(&home)
+        .args(["--data-dir", "data", "user", "list", "--users-file", "users.json"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+fn seed_search_data(home: &TempDir) {
+    cli(home).args(["task", "add", "Write DOCS", "Usage guide"]).assert().success();
+    cli(home).args(["task", "add", "Ship release", "Update the docs site"]).assert().success();
+    cli(home).args(["task", "add", "Fix login bug"]).assert().success();
+    cli(home).args(["user", "add", "1", "Doc Brown", "doc@example.com"]).assert().success();
+    cli(home).args(["user", "add", "2", "Ann Lee", "ann@example.com"]).assert().success();
+}
+
+#[test]
+fn test_search_is_case_insensitive() {
+    let home = TempDir::new().unwrap();
+    seed_search_data(&home);
+
+    cli(&home)
+        .args(["search", "dOcS"])
+        .assert()
+        .success()
+        .stdout(
+            "Tasks:\n\
+             ID  Done  Title         Description\n\
+             --  ----  ------------  --------------------\n\
+             1         Write DOCS    Usage guide\n\
+             2         Ship release  Update the docs site\n",
+        );
+
+    cli(&home)
+        .args(["search", "DOC", "--users"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Users:"))
+        .stdout(predicate::str::contains("Doc Brown"))
+        .stdout(predicate::str::contains("Tasks:").not());
+}
+
+#[test]
+fn test_search_json_and_no_matches() {
+    let home = TempDir::new().unwrap();
+    seed_search_data(&home);
+
+    let output = cli(&home)
+        .args(["--output-format", "json", "search", "ANN"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["tasks"].as_array().unwrap().len(), 0);
+    assert_eq!(result["users"][0]["name"], "Ann Lee");
+
+    cli(&home)
+        .args(["search", "nothing like this"])
+        .assert()
+        .success()
+        .stdout("no matches\n");
+}
+
+#[test]
+fn test_verbose_logging_mentions_store_path() {
+    let home = TempDir::new().unwrap();
+    let store = home.path().join("store").join("tasks.json");
+
+    cli(&home)
+        .args(["--file", store.to_str().unwrap(), "task", "add", "Logged"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    cli(&home)
+        .args(["-vv", "--file", store.to_str().unwrap(), "task", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Logged"))
+        .stderr(predicate::str::contains(format!("[info] loading tasks from {}", store.display())))
+        .stderr(predicate::str::contains(format!("[debug] parsed 1 tasks from {}", store.display())));
+
+    cli(&home)
+        .args(["-v", "--file", store.to_str().unwrap(), "task", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("[info] loading tasks"))
+        .stderr(predicate::str::contains("[debug]").not());
+}
+
+#[test]
+fn test_quiet_suppresses_warnings() {
+    let mut cmd = Command::cargo_bin("gh_actions").unwrap();
+    cmd.args(["--quiet", "fib", "100"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("354224848179261915075"))
+        .stderr(predicate::str::is_empty());
+
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["-q", "-vv", "task", "toggle", "3"])
+        .assert()
+        .code(3)
+        .stderr("Error: Task with ID 3 not found\n");
+}
+
+#[test]
+fn test_piped_output_has_no_ansi_escapes() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["user", "add", "1", "John Doe", "john@example.com"])
+        .assert()
+        .stdout(predicate::str::contains("\x1b[").not());
+    cli(&home)
+        .args(["task", "add", "Colorless"])
+        .assert()
+        .success();
+    cli(&home)
+        .args(["task", "toggle", "1"])
+        .assert()
+        .success();
+
+    cli(&home)
+        .args(["user", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[").not());
+    cli(&home)
+        .args(["task", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[").not());
+    cli(&home)
+        .args(["task", "toggle", "99"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn test_no_color_flag_and_env_are_accepted() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["--no-color", "task", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[").not());
+    cli(&home)
+        .env("NO_COLOR", "1")
+        .args(["task", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+/// Seeds a fixed set of users for the table snapshot tests.
+fn seed_users(home: &TempDir) {
+    for (id, name, email) in [
+        ("3", "Carol Danvers", "carol@example.com"),
+        ("1", "Alice", "alice@example.org"),
+        ("2", "Bob With An Extraordinarily Long Display Name", "bob@example.net"),
+    ] {
+        cli(home).args(["user", "add", id, name, email]).assert().success();
+    }
+}
+
+#[test]
+fn test_user_list_table_snapshot() {
+    let home = TempDir::new().unwrap();
+    seed_users(&home);
+
+    cli(&home)
+        .args(["user", "list"])
+        .assert()
+        .success()
+        .stdout(
+            "Users:\n\
+             ID  Name                                      Email              Active\n\
+             --  ----------------------------------------  -----------------  ------\n\
+             3   Carol Danvers                             carol@example.com  true\n\
+             1   Alice                                     alice@example.org  true\n\
+             2   Bob With An Extraordinarily Long Displa…  bob@example.net    true\n\
+             showing 3 of 3 users\n",
+        );
+}
+
+#[test]
+fn test_user_list_columns_and_sort() {
+    let home = TempDir::new().unwrap();
+    seed_users(&home);
+
+    cli(&home)
+        .args(["user", "list", "--columns", "email,id", "--sort", "id"])
+        .assert()
+        .success()
+        .stdout(
+            "Users:\n\
+             Email              ID\n\
+             -----------------  --\n\
+             alice@example.org  1\n\
+             bob@example.net    2\n\
+             carol@example.com  3\n\
+             showing 3 of 3 users\n",
+        );
+
+    cli(&home)
+        .args(["user", "list", "--columns", "name", "--sort", "name"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("(?s)Alice.*Bob.*Carol").unwrap());
+}
+
+#[test]
+fn test_list_rejects_unknown_column() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["task", "list", "--columns", "id,priority"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("unknown column 'priority'"));
+    cli(&home)
+        .args(["user", "list", "--sort", "age"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("unknown column 'age'"));
+}
+
+#[test]
+fn test_string_commands_with_argument() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["string", "reverse", "hello"])
+        .assert()
+        .success()
+        .stdout("olleh\n");
+    cli(&home)
+        .args(["string", "words", "  one two   three "])
+        .assert()
+        .success()
+        .stdout("3\n");
+    cli(&home)
+        .args(["string", "slug", "Hello, World!"])
+        .assert()
+        .success()
+        .stdout("hello-world\n");
+}
+
+#[test]
+fn test_string_commands_read_stdin() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["string", "reverse"])
+        .write_stdin("abc\n")
+        .assert()
+        .success()
+        .stdout("cba\n");
+    cli(&home)
+        .args(["string", "words"])
+        .write_stdin("first line\nsecond line here\n")
+        .assert()
+        .success()
+        .stdout("5\n");
+    cli(&home)
+        .args(["string", "palindrome"])
+        .write_stdin("Never odd or even\n")
+        .assert()
+        .success()
+        .stdout("yes\n");
+}
+
+#[test]
+fn test_string_palindrome_exit_codes() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["string", "palindrome", "Madam"])
+        .assert()
+        .code(0)
+        .stdout("yes\n");
+    cli(&home)
+        .args(["string", "palindrome", "--strict", "Madam"])
+        .assert()
+        .code(1)
+        .stdout("no\n")
+        .stderr("");
+    cli(&home)
+        .args(["string", "palindrome", "--strict", "level"])
+        .assert()
+        .code(0)
+        .stdout("yes\n");
+}
+
+#[test]
+fn test_email_validate_single_address() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["email", "validate", "user@example.com"])
+        .assert()
+        .code(0)
+        .stdout("valid\n");
+    cli(&home)
+        .args(["email", "validate", "user@localhost"])
+        .assert()
+        .code(1)
+        .stdout("domain 'localhost' has no '.'\n")
+        .stderr("");
+}
+
+#[test]
+fn test_email_validate_batch() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["email", "validate", "--batch"])
+        .write_stdin("ann@example.com\n# skipped\nbob@@example.com\n\ncid@example.org\n")
+        .assert()
+        .code(1)
+        .stdout(
+            "ann@example.com: valid\n\
+             bob@@example.com: invalid local part 'bob@'\n\
+             cid@example.org: valid\n\
+             2 of 3 addresses valid\n",
+        );
+    cli(&home)
+        .args(["email", "validate", "--batch"])
+        .write_stdin("ann@example.com\n")
+        .assert()
+        .code(0);
+}
+
+#[test]
+fn test_email_validate_json() {
+    let home = TempDir::new().unwrap();
+    let output = cli(&home)
+        .args(["--output-format", "json", "email", "validate", "--batch"])
+        .write_stdin("ann@example.com\nnope\n")
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!([
+            { "email": "ann@example.com", "valid": true, "reason": null },
+            { "email": "nope", "valid": false, "reason": "too short (minimum 5 characters)" },
+        ])
+    );
+}
+
+fn read_tasks(path: &std::path::Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_task_export_import_into_empty_store() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_tasks(&home);
+    let empty_dir = home.path().join("empty");
+
+    for name in ["tasks-export.json", "tasks-export.csv"] {
+        let export = home.path().join(name);
+        cli(&home)
+            .args(["--data-dir", data_dir.to_str().unwrap(), "task", "export", export.to_str().unwrap()])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Exported 4 tasks"));
+
+        let target = empty_dir.join(name);
+        cli(&home)
+            .args(["--file", target.to_str().unwrap(), "task", "import", export.to_str().unwrap()])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Imported 4 tasks (0 replaced, 0 skipped)"));
+        // Every field, including the unset optional ones, survives the trip
+        let exported_json = read_tasks(&home.path().join("tasks-export.json"));
+        assert_eq!(read_tasks(&target), exported_json, "{}", name);
+    }
+}
+
+#[test]
+fn test_task_import_with_id_collisions() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_tasks(&home);
+    let data_dir = data_dir.to_str().unwrap();
+    let export = home.path().join("export.json");
+    cli(&home)
+        .args(["--data-dir", data_dir, "task", "export", export.to_str().unwrap()])
+        .assert()
+        .success();
+    let export = export.to_str().unwrap();
+
+    cli(&home)
+        .args(["--data-dir", data_dir, "task", "import", export])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("Task IDs already in use: 1, 2, 3, 4"));
+
+    cli(&home)
+        .args(["--data-dir", data_dir, "task", "import", export, "--merge", "keep-newest"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 0 tasks (0 replaced, 4 skipped)"));
+
+    cli(&home)
+        .args(["--data-dir", data_dir, "task", "import", export, "--merge", "keep-both"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 4 tasks (0 replaced, 0 skipped)"));
+    let tasks = read_tasks(&home.path().join("data").join("tasks.json"));
+    let ids: Vec<u64> = tasks.as_array().unwrap().iter().map(|t| t["id"].as_u64().unwrap()).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn test_task_markdown_export_cannot_be_imported() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_tasks(&home);
+    let export = home.path().join("TODO.md");
+    cli(&home)
+        .args(["--data-dir", data_dir.to_str().unwrap(), "task", "export", export.to_str().unwrap()])
+        .assert()
+        .success();
+    assert!(fs::read_to_string(&export).unwrap().contains("- [x] #1 Task 1"));
+
+    cli(&home)
+        .args(["--data-dir", data_dir.to_str().unwrap(), "task", "import", export.to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Markdown exports cannot be imported"));
+}
+
+#[test]
+fn test_task_export_import_with_format_flag() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_tasks(&home);
+    let data_dir = data_dir.to_str().unwrap();
+
+    // Without an extension the flag is the only way to pick a format
+    let export = home.path().join("tasks-backup");
+    let export = export.to_str().unwrap();
+    cli(&home)
+        .args(["--data-dir", data_dir, "task", "export", export])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("pass --format"));
+    cli(&home)
+        .args(["--data-dir", data_dir, "task", "export", export, "--format", "csv"])
+        .assert()
+        .success();
+    assert!(fs::read_to_string(export).unwrap().starts_with("id,title,description,"));
+
+    let target = home.path().join("empty").join("tasks.json");
+    cli(&home)
+        .args(["--file", target.to_str().unwrap(), "task", "import", export, "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 4 tasks"));
+
+    cli(&home)
+        .args(["--data-dir", data_dir, "task", "export", export, "--format", "yaml"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid value 'yaml'"));
+}
+
+#[test]
+fn test_task_format_flag_overrides_extension() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_tasks(&home);
+    let data_dir = data_dir.to_str().unwrap();
+
+    // JSON written to a .csv file
+    let export = home.path().join("tasks.csv");
+    let export = export.to_str().unwrap();
+    cli(&home)
+        .args(["--data-dir", data_dir, "task", "export", export, "--format", "json"])
+        .assert()
+        .success();
+    assert_eq!(read_tasks(std::path::Path::new(export)).as_array().unwrap().len(), 4);
+
+    // The extension alone reads it as CSV and fails
+    let target = home.path().join("empty").join("tasks.json");
+    let target = target.to_str().unwrap();
+    cli(&home)
+        .args(["--file", target, "task", "import", export])
+        .assert()
+        .code(2);
+    cli(&home)
+        .args(["--file", target, "task", "import", export, "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 4 tasks"));
+
+    let checklist = home.path().join("tasks.json");
+    cli(&home)
+        .args(["--data-dir", data_dir, "task", "export", checklist.to_str().unwrap(), "--format", "md"])
+        .assert()
+        .success();
+    assert!(fs::read_to_string(&checklist).unwrap().contains("- [x] #1 Task 1"));
+}
+
+#[test]
+fn test_task_rm_requires_confirmation() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_tasks(&home);
+    let data_dir_arg = data_dir.to_str().unwrap();
+    let before = fs::read_to_string(data_dir.join("tasks.json")).unwrap();
+
+    // Piped stdin cannot answer a prompt, so nothing is deleted
+    cli(&home)
+        .args(["--data-dir", data_dir_arg, "task", "rm", "2"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("pass --yes"));
+    assert_eq!(fs::read_to_string(data_dir.join("tasks.json")).unwrap(), before);
+
+    cli(&home)
+        .args(["--data-dir", data_dir_arg, "task", "rm", "2", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 2 deleted"));
+    let ids: Vec<u64> = read_tasks(&data_dir.join("tasks.json"))
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![1, 3, 4]);
+
+    cli(&home)
+        .args(["--data-dir", data_dir_arg, "task", "rm", "2", "-y"])
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn test_user_rm_requires_confirmation() {
+    let home = TempDir::new().unwrap();
+    cli(&home)
+        .args(["user", "add", "1", "Alice", "alice@example.com"])
+        .assert()
+        .success();
+
+    cli(&home).args(["user", "rm", "1"]).assert().code(1);
+    cli(&home)
+        .args(["user", "list"])
+        .assert()
+        .stdout(predicate::str::contains("Alice"));
+
+    cli(&home)
+        .args(["user", "rm", "1", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("User 1 deleted"));
+    cli(&home)
+        .args(["user", "list"])
+        .assert()
+        .stdout(predicate::str::contains("Alice").not());
+}
+
+#[test]
+fn test_clear_completed_and_purge() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_tasks(&home);
+    let data_dir_arg = data_dir.to_str().unwrap();
+    let before = fs::read_to_string(data_dir.join("tasks.json")).unwrap();
+
+    cli(&home)
+        .args(["--data-dir", data_dir_arg, "task", "clear-completed", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(
+            "Would delete task 1 'Task 1'\n\
+             Would delete task 2 'Task 2'\n\
+             Would delete task 3 'Task 3'\n\
+             3 completed tasks would be deleted\n",
+        );
+    cli(&home)
+        .args(["--data-dir", data_dir_arg, "task", "clear-completed"])
+        .assert()
+        .code(1);
+    assert_eq!(fs::read_to_string(data_dir.join("tasks.json")).unwrap(), before);
+
+    cli(&home)
+        .args(["--data-dir", data_dir_arg, "task", "clear-completed", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deleted 3 completed tasks"));
+
+    cli(&home)
+        .args(["--data-dir", data_dir_arg, "purge", "--dry-run"])
+        .assert()
+        .success()
+        .stdout("Would delete 1 tasks and 0 users\n");
+    cli(&home).args(["--data-dir", data_dir_arg, "purge"]).assert().code(1);
+    cli(&home)
+        .args(["--data-dir", data_dir_arg, "purge", "-y"])
+        .assert()
+        .success();
+    assert_eq!(read_tasks(&data_dir.join("tasks.json")), serde_json::json!([]));
+}
+
+fn seed_mixed_users(home: &TempDir) -> String {
+    let data_dir = home.path().join("data");
+    fs::create_dir_all(&data_dir).unwrap();
+    let users = serde_json::json!([
+        { "id": 1, "name": "Ann", "email": "ann@example.com", "active": true },
+        { "id": 2, "name": "Bob", "email": "bob@example.com", "active": false },
+        { "id": 3, "name": "Cid", "email": "cid@other.org", "active": true },
+        { "id": 4, "name": "Dee", "email": "dee@example.com", "active": true },
+        { "id": 5, "name": "Eve", "email": "eve@other.org", "active": false },
+    ]);
+    fs::write(data_dir.join("users.json"), users.to_string()).unwrap();
+    data_dir.to_str().unwrap().to_string()
+}
+
+fn listed_user_ids(home: &TempDir, data_dir: &str, filters: &[&str]) -> Vec<u64> {
+    let output = cli(home)
+        .args(["--data-dir", data_dir, "--output-format", "json", "user", "list"])
+        .args(filters)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let users: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    users.as_array().unwrap().iter().map(|u| u["id"].as_u64().unwrap()).collect()
+}
+
+#[test]
+fn test_user_list_filters() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_mixed_users(&home);
+
+    assert_eq!(listed_user_ids(&home, &data_dir, &["--active"]), vec![1, 3, 4]);
+    assert_eq!(listed_user_ids(&home, &data_dir, &["--inactive"]), vec![2, 5]);
+    assert_eq!(listed_user_ids(&home, &data_dir, &["--domain", "example.com"]), vec![1, 2, 4]);
+    assert_eq!(listed_user_ids(&home, &data_dir, &["--active", "--domain", "other.org"]), vec![3]);
+    assert_eq!(listed_user_ids(&home, &data_dir, &["--limit", "2"]), vec![1, 2]);
+    assert_eq!(
+        listed_user_ids(&home, &data_dir, &["--domain", "example.com", "--sort", "name", "--limit", "1"]),
+        vec![1]
+    );
+
+    cli(&home)
+        .args(["--data-dir", &data_dir, "user", "list", "--inactive", "--columns", "name"])
+        .assert()
+        .success()
+        .stdout("Users:\nName\n----\nBob\nEve\nshowing 2 of 5 users\n");
+    cli(&home)
+        .args(["--data-dir", &data_dir, "user", "list", "--domain", "nowhere.net"])
+        .assert()
+        .success()
+        .stdout("Users:\nNo users found.\nshowing 0 of 5 users\n");
+    cli(&home)
+        .args(["--data-dir", &data_dir, "user", "list", "--active", "--inactive"])
+        .assert()
+        .code(2);
+}
+
+/// Writes open tasks due 2 days ago, in 3 hours and in 4 days, plus a
+/// completed overdue task and one without a due date.
+fn seed_due_tasks(home: &TempDir) -> String {
+    let data_dir = home.path().join("data");
+    fs::create_dir_all(&data_dir).unwrap();
+    let task = |id: u32, due_date: Option<&str>, completed: bool| {
+        serde_json::json!({
+            "id": id,
+            "title": format!("Task {}", id),
+            "description": "",
+            "completed": completed,
+            "created_at": "2024-03-01T08:00:00Z",
+            "completed_at": if completed { Some("2024-03-02T08:00:00Z") } else { None },
+            "due_date": due_date,
+        })
+    };
+    let tasks = serde_json::json!([
+        task(1, Some("2024-03-14T12:00:00Z"), false),
+        task(2, Some("2024-03-10T15:00:00Z"), false),
+        task(3, None, false),
+        task(4, Some("2024-03-08T12:00:00Z"), false),
+        task(5, Some("2024-03-05T12:00:00Z"), true),
+    ]);
+    fs::write(data_dir.join("tasks.json"), tasks.to_string()).unwrap();
+    data_dir.to_str().unwrap().to_string()
+}
+
+const DUE_NOW: &str = "2024-03-10T12:00:00Z";
+
+#[test]
+fn test_task_due() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_due_tasks(&home);
+
+    cli(&home)
+        .args(["--data-dir", &data_dir, "task", "due", "--now", DUE_NOW])
+        .assert()
+        .success()
+        .stdout(
+            "ID  Title   Due               When\n\
+             --  ------  ----------------  -----------------\n\
+             4   Task 4  2024-03-08 12:00  overdue by 2 days\n\
+             2   Task 2  2024-03-10 15:00  due in 3h\n\
+             1   Task 1  2024-03-14 12:00  due in 4 days\n",
+        );
+}
+
+#[test]
+fn test_task_due_filters() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_due_tasks(&home);
+    let due_ids = |extra: &[&str]| -> Vec<u64> {
+        let mut args = vec!["--data-dir", &data_dir, "--output-format", "json", "task", "due", "--now", DUE_NOW];
+        args.extend_from_slice(extra);
+        let output = cli(&home).args(args).output().unwrap();
+        assert!(output.status.success());
+        let tasks: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        tasks.as_array().unwrap().iter().map(|t| t["id"].as_u64().unwrap()).collect()
+    };
+
+    assert_eq!(due_ids(&["--within", "48h"]), vec![4, 2]);
+    assert_eq!(due_ids(&["--within", "1w"]), vec![4, 2, 1]);
+    assert_eq!(due_ids(&["--overdue"]), vec![4]);
+
+    cli(&home)
+        .args(["--data-dir", &data_dir, "task", "due", "--within", "2 days"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid duration '2 days'"));
+    cli(&home)
+        .args(["--data-dir", &data_dir, "task", "due", "--within", "1h", "--overdue"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_task_due_check() {
+    let home = TempDir::new().unwrap();
+    let data_dir = seed_due_tasks(&home);
+
+    cli(&home)
+        .args(["--data-dir", &data_dir, "task", "due", "--check", "--now", DUE_NOW])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("overdue by 2 days"))
+        .stderr("");
+    cli(&home)
+        .args(["--data-dir", &data_dir, "task", "due", "--check", "--now", "2024-03-08T11:00:00Z"])
+        .assert()
+        .success();
+    cli(&home)
+        .args(["--data-dir", &data_dir, "task", "due", "--overdue", "--now", "2024-03-01T00:00:00Z"])
+        .assert()
+        .success()
+        .stdout("No tasks due.\n");
+}
@@ -1,11 +1,31 @@
 mod common;
 
 use gh_actions::{User, UserManager, calculate_fibonacci, validate_email, is_prime, factorial, gcd, lcm};
+use gh_actions::utils::{
+    adler32, base64_decode, base64_encode, caesar_cipher, calculate_fibonacci_mod, format_bytes, hex_decode, hex_encode,
+    parse_bytes, rot13, run_length_decode, run_length_encode, simple_checksum,
+};
+use gh_actions::{Task, TaskManager};
+use chrono::{Duration, Utc};
 use common::*;
 
 // Property-based testing without external crates
 // We'll use a simple approach with loops and random-like data
 
+/// Pseudo-random numbers from a fixed seed, so failures are reproducible
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        self.0 >> 32
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| (self.next() >> 24) as u8).collect()
+    }
+}
+
 #[test]
 fn property_fibonacci_sequence() {
     // Test that fibonacci sequence follows the mathematical property: F(n) = F(n-1) + F(n-2)
@@ -31,6 +51,172 @@ fn property_fibonacci_monotonic() {
     }
 }
 
+#[test]
+fn property_fibonacci_mod_matches_exact() {
+    // F(n) mod m must agree with the exact u64 value wherever that exists
+    let modulus = 1_000_000_000_000_000_009;
+    for n in 0..=93 {
+        assert_eq!(calculate_fibonacci_mod(n, modulus).unwrap(),
+                   calculate_fibonacci(n as u32).unwrap() % modulus,
+                   "Fibonacci mod property failed for n={}", n);
+    }
+}
+
+#[test]
+fn property_base64_round_trip() {
+    let mut rng = Lcg(0x9e37_79b9_7f4a_7c15);
+    for len in 0..=200usize {
+        for _ in 0..5 {
+            let data = rng.bytes(len);
+            let encoded = base64_encode(&data);
+            assert_eq!(encoded.len(), len.div_ceil(3) * 4, "Wrong length for len={}", len);
+            assert_eq!(base64_decode(&encoded).unwrap(), data, "Round trip failed for {:?}", data);
+        }
+    }
+}
+
+#[test]
+fn property_bytes_round_trip() {
+    // Exact with two decimals: counts below 1 KB and whole multiples of a unit
+    let mut sizes: Vec<u64> = (0..1024).collect();
+    for shift in [10, 20, 30, 40] {
+        sizes.extend((1..1024u64).map(|k| k << shift));
+    }
+    for n in sizes {
+        assert_eq!(parse_bytes(&format_bytes(n)), Ok(n), "Round trip failed for {} ({})", n, format_bytes(n));
+    }
+}
+
+#[test]
+fn property_hex_round_trip() {
+    let mut rng = Lcg(0x2545_f491_4f6c_dd1d);
+    for len in 0..=200usize {
+        let data = rng.bytes(len);
+        let encoded = hex_encode(&data);
+        assert_eq!(encoded.len(), len * 2);
+        assert_eq!(hex_decode(&encoded), Ok(data.clone()));
+        assert_eq!(hex_decode(&encoded.to_uppercase()), Ok(data));
+    }
+}
+
+#[test]
+fn property_checksums() {
+    assert_eq!(adler32(&[]), 1);
+    assert_eq!(simple_checksum(&[]), 0);
+
+    // Against the textbook definitions, which reduce after every byte
+    let mut rng = Lcg(0x853c_49e6_748f_ea9b);
+    for len in [1usize, 2, 100, 5551, 5552, 5553, 20_000] {
+        let data = rng.bytes(len);
+        let (mut a, mut b) = (1u32, 0u32);
+        for &byte in &data {
+            a = (a + byte as u32) % 65521;
+            b = (b + a) % 65521;
+        }
+        assert_eq!(adler32(&data), (b << 16) | a, "Adler-32 mismatch for len={}", len);
+        let sum: u64 = data.iter().map(|&byte| byte as u64).sum();
+        assert_eq!(simple_checksum(&data) as u64, sum % u32::MAX as u64);
+    }
+}
+
+#[test]
+fn property_caesar_cipher_round_trip() {
+    let texts = ["", "Hello, World!", "The quick brown fox jumps over the lazy dog", "Ünïcödé ✓ 12345 @[`{"];
+    let shifts = (-60..=60).chain([i32::MAX, i32::MAX - 25, i32::MIN + 27]);
+    for n in shifts {
+        for text in texts {
+            assert_eq!(caesar_cipher(&caesar_cipher(text, n), 26 - n), text, "Round trip failed for shift {}", n);
+        }
+    }
+    for text in texts {
+        assert_eq!(rot13(&rot13(text)), text);
+    }
+}
+
+#[test]
+fn property_run_length_round_trip() {
+    // Few distinct graphemes, so runs are common; no digits
+    let alphabet = ["a", "b", " ", "é", "e\u{301}", "🇫🇷", "👍🏽", "\r\n", "日"];
+    let mut rng = Lcg(0xda94_2042_e4dd_58b5);
+    for len in 0..=100 {
+        for _ in 0..5 {
+            let s: String = (0..len).map(|_| alphabet[rng.next() as usize % alphabet.len()]).collect();
+            let encoded = run_length_encode(&s);
+            assert_eq!(run_length_decode(&encoded), Ok(s.clone()), "Round trip failed for {:?}", s);
+            assert!(encoded.len() <= s.len() * 2);
+        }
+    }
+}
+
+#[test]
+fn property_task_manager_collects_every_task() {
+    // Any set of distinct IDs survives collection, and new IDs never collide
+    for size in 0..20u32 {
+        let tasks: Vec<Task> = (0..size)
+            .rev()
+            .map(|i| Task::new(i * 3 + 1, format!("Task {}", i), String::new()))
+            .collect();
+        let max_id = tasks.iter().map(|task| task.id).max().unwrap_or(0);
+        let count = tasks.len();
+
+        let mut manager: TaskManager = tasks.into_iter().collect();
+        assert_eq!(manager.get_total_count(), count, "Lost tasks for size={}", size);
+        assert_eq!(manager.add_task("New".to_string(), String::new()).unwrap(), max_id + 1);
+    }
+}
+
+#[test]
+fn property_stale_tasks_are_old_and_incomplete() {
+    let mut rng = Lcg(1668);
+    let now = Utc::now();
+    let manager: TaskManager = (1..=200)
+        .map(|id| {
+            let mut task = Task::new(id, format!("Task {}", id), String::new());
+            task.created_at = now - Duration::minutes((rng.next() % (90 * 24 * 60)) as i64);
+            task.completed = rng.next().is_multiple_of(3);
+            task
+        })
+        .collect();
+
+    for n in [0, 1, 7, 30, 60, 89, 90, 365] {
+        let stale = manager.get_tasks_stale_for_n_days(n);
+        let cutoff = Utc::now() - Duration::days(n as i64);
+        for task in &stale {
+            assert!(!task.completed, "Completed task {} returned for n={}", task.id, n);
+            assert!(task.created_at < cutoff, "Task {} is not stale for n={}", task.id, n);
+        }
+        assert!(
+            stale.windows(2).all(|pair| pair[0].created_at <= pair[1].created_at),
+            "Not oldest first for n={}", n
+        );
+    }
+}
+
+#[test]
+fn property_retain_removes_exactly_the_rejected_tasks() {
+    let mut rng = Lcg(1674);
+    for size in [0, 1, 10, 100] {
+        let tasks: Vec<Task> = (1..=size)
+            .map(|id| {
+                let mut task = Task::new(id, format!("Task {}", id), String::new());
+                task.completed = rng.next().is_multiple_of(2);
+                task
+            })
+            .collect();
+        let mut manager: TaskManager = tasks.into_iter().collect();
+        let count_before = manager.get_total_count();
+
+        assert_eq!(manager.retain(|_| true), 0, "Kept everything for size={}", size);
+        assert_eq!(manager.get_total_count(), count_before);
+
+        let completed = manager.get_completed_count();
+        let removed = manager.retain(|task| !task.completed);
+        assert_eq!(removed, completed, "Wrong count for size={}", size);
+        assert_eq!(manager.get_total_count(), count_before - removed);
+        assert_eq!(manager.get_completed_count(), 0);
+    }
+}
+
 #[test]
 fn property_gcd_properties() {
     let test_pairs = vec![
@@ -187,6 +373,28 @@ fn property_user_manager_invariants() {
     }
 }
 
+#[test]
+fn property_collect_users_never_grows() {
+    // IDs and emails drawn from small ranges, so many repeat
+    let mut rng = Lcg(1677);
+    for size in [0, 1, 5, 50] {
+        let users: Vec<User> = (0..size)
+            .map(|_| {
+                let mut user = create_test_user((rng.next() % 10) as u32);
+                user.email = format!("user{}@example.com", rng.next() % 10);
+                user
+            })
+            .collect();
+        let manager = users.iter().cloned().collect::<UserManager>();
+        assert!(manager.count() <= users.len(), "Grew for size={}", size);
+        assert_eq!(manager.count() == 0, users.is_empty(), "Lost every user for size={}", size);
+
+        let ids: Vec<u32> = manager.get_users().iter().map(|u| u.id).collect();
+        let unique: std::collections::HashSet<u32> = ids.iter().copied().collect();
+        assert_eq!(ids.len(), unique.len(), "Repeated an ID for size={}", size);
+    }
+}
+
 #[test]
 fn property_user_manager_state_consistency() {
     let mut manager = create_test_manager_with_users(20);
@@ -0,0 +1,58 @@
+//! Browser tests for the change callbacks. Run with
+//! `wasm-pack test --headless --firefox -- --features web`; otherwise this
+//! file is empty.
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use gh_actions::wasm;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Registers a callback that records each event as `(kind, task_id, task count)`.
+fn record_events() -> (u32, Rc<RefCell<Vec<(String, Option<u32>, u32)>>>, Closure<dyn FnMut(JsValue)>) {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let recorded = events.clone();
+    let callback = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+        let kind = js_sys::Reflect::get(&event, &"kind".into()).unwrap().as_string().unwrap();
+        let task_id = js_sys::Reflect::get(&event, &"task_id".into()).unwrap().as_f64().map(|id| id as u32);
+        // Calling back in would deadlock if the store were still locked
+        recorded.borrow_mut().push((kind, task_id, wasm::get_task_count()));
+    });
+    let handle = wasm::register_on_change(callback.as_ref().unchecked_ref::<js_sys::Function>().clone());
+    (handle, events, callback)
+}
+
+#[wasm_bindgen_test]
+fn fires_on_add_task() {
+    wasm::init(None).unwrap();
+    let (handle, events, _callback) = record_events();
+
+    let before = wasm::get_task_count();
+    let id = wasm::add_task("Call the plumber".to_string(), String::new()).unwrap();
+    assert_eq!(*events.borrow(), vec![("add".to_string(), Some(id), before + 1)]);
+
+    assert!(wasm::unregister_on_change(handle));
+    assert!(!wasm::unregister_on_change(handle));
+    wasm::remove_task(id).unwrap();
+    assert_eq!(events.borrow().len(), 1);
+}
+
+#[wasm_bindgen_test]
+fn reports_each_kind_of_change() {
+    wasm::init(None).unwrap();
+    let id = wasm::add_task("Book flights".to_string(), String::new()).unwrap();
+    let (handle, events, _callback) = record_events();
+
+    wasm::toggle_task(id).unwrap();
+    assert!(!wasm::remove_task(u32::MAX - 1).unwrap());
+    assert!(wasm::remove_task(id).unwrap());
+    let kinds: Vec<(String, Option<u32>)> =
+        events.borrow().iter().map(|(kind, id, _)| (kind.clone(), *id)).collect();
+    assert_eq!(kinds, vec![("toggle".to_string(), Some(id)), ("remove".to_string(), Some(id))]);
+
+    wasm::unregister_on_change(handle);
+}
@@ -0,0 +1,57 @@
+//! Browser tests for the IndexedDB backend. Run with
+//! `wasm-pack test --headless --firefox -- --features indexeddb`; otherwise
+//! this file is empty.
+#![cfg(all(target_arch = "wasm32", feature = "indexeddb"))]
+
+use gh_actions::indexed_db::{IndexedDbStorage, DB_NAME};
+use gh_actions::storage::{self, LocalStorage, StorageBackend, TASKS_KEY};
+use gh_actions::{wasm, TaskManager};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn reset_browser_storage() {
+    // Write out changes left by an earlier test, which `init_async` would save
+    wasm::flush_saves();
+    let window = web_sys::window().unwrap();
+    window.local_storage().unwrap().unwrap().clear().unwrap();
+    let request = window.indexed_db().unwrap().unwrap().delete_database(DB_NAME).unwrap();
+    let deleted = js_sys::Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    JsFuture::from(deleted).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn migrates_local_storage_once() {
+    reset_browser_storage().await;
+    let mut manager = TaskManager::new();
+    manager.add_task("Saved before IndexedDB".to_string(), String::new()).unwrap();
+    storage::save_tasks(&mut LocalStorage, TASKS_KEY, &manager).unwrap();
+
+    let report = wasm::init_async(None).await.unwrap();
+    assert_eq!((report.loaded_count(), report.storage_kind(), report.migrated()), (1, "indexedDB".to_string(), true));
+    assert!(wasm::get_all_tasks_json().unwrap().contains("Saved before IndexedDB"));
+    assert!(LocalStorage.load(TASKS_KEY).unwrap().is_none());
+
+    // A second start reads IndexedDB and finds nothing left to migrate
+    let reopened = IndexedDbStorage::open(&[TASKS_KEY]).await.unwrap();
+    assert!(!reopened.was_migrated(TASKS_KEY));
+    let tasks = storage::load_tasks(&reopened, TASKS_KEY).unwrap().unwrap();
+    assert_eq!(tasks.get_task(1).unwrap().title, "Saved before IndexedDB");
+}
+
+#[wasm_bindgen_test]
+async fn saves_reach_indexed_db() {
+    reset_browser_storage().await;
+    wasm::init_async(None).await.unwrap();
+    wasm::add_task("Stored in IndexedDB".to_string(), String::new()).unwrap();
+    assert!(wasm::flush_saves());
+    assert!(LocalStorage.load(TASKS_KEY).unwrap().is_none());
+
+    let reopened = IndexedDbStorage::open(&[TASKS_KEY]).await.unwrap();
+    let stored = reopened.load(TASKS_KEY).unwrap().unwrap();
+    assert!(stored.contains("Stored in IndexedDB"), "{}", stored);
+}
@@ -0,0 +1,75 @@
+//! Browser tests for the search and filter bindings. Run with
+//! `wasm-pack test --headless --firefox -- --features web`; otherwise this
+//! file is empty.
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use gh_actions::wasm;
+use gh_actions::Task;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn titles(json: &str) -> Vec<String> {
+    let tasks: Vec<Task> = serde_json::from_str(json).unwrap();
+    tasks.into_iter().map(|task| task.title).collect()
+}
+
+#[wasm_bindgen_test]
+fn search_ignores_case() {
+    wasm::init(None).unwrap();
+    let ids = [
+        wasm::add_task("Write DOCS".to_string(), String::new()).unwrap(),
+        wasm::add_task("Release".to_string(), "Update the Docs site".to_string()).unwrap(),
+        wasm::add_task("Fix bug".to_string(), String::new()).unwrap(),
+    ];
+
+    for query in ["docs", "DOCS", "DoCs"] {
+        let found = titles(&wasm::search_tasks(query).unwrap());
+        assert!(found.contains(&"Write DOCS".to_string()), "{}: {:?}", query, found);
+        assert!(found.contains(&"Release".to_string()), "{}: {:?}", query, found);
+        assert!(!found.contains(&"Fix bug".to_string()), "{}: {:?}", query, found);
+    }
+
+    for id in ids {
+        wasm::remove_task(id).unwrap();
+    }
+}
+
+#[wasm_bindgen_test]
+fn filters_by_tag_ignoring_case() {
+    wasm::init(None).unwrap();
+    let tagged = wasm::add_task("Tagged report".to_string(), String::new()).unwrap();
+    wasm::add_task_tag(tagged, "Work").unwrap();
+    let untagged = wasm::add_task("Untagged chore".to_string(), String::new()).unwrap();
+
+    for tag in ["work", "WORK", "Work"] {
+        let found = titles(&wasm::get_tasks_with_tag(tag).unwrap());
+        assert!(found.contains(&"Tagged report".to_string()), "{}: {:?}", tag, found);
+        assert!(!found.contains(&"Untagged chore".to_string()), "{}: {:?}", tag, found);
+    }
+
+    wasm::remove_task(tagged).unwrap();
+    wasm::remove_task(untagged).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn filters_by_status() {
+    wasm::init(None).unwrap();
+    let open = wasm::add_task("Filter open".to_string(), String::new()).unwrap();
+    let done = wasm::add_task("Filter done".to_string(), String::new()).unwrap();
+    wasm::toggle_task(done).unwrap();
+
+    let active = titles(&wasm::get_tasks_filtered("Active").unwrap());
+    assert!(active.contains(&"Filter open".to_string()) && !active.contains(&"Filter done".to_string()));
+    let completed = titles(&wasm::get_tasks_filtered("completed").unwrap());
+    assert!(completed.contains(&"Filter done".to_string()) && !completed.contains(&"Filter open".to_string()));
+    assert!(!titles(&wasm::get_tasks_filtered("overdue").unwrap()).contains(&"Filter open".to_string()));
+
+    let error = JsValue::from(wasm::get_tasks_filtered("pending").unwrap_err());
+    let name = js_sys::Reflect::get(&error, &"name".into()).unwrap();
+    assert_eq!(name.as_string().as_deref(), Some("InvalidArgumentError"));
+
+    wasm::remove_task(open).unwrap();
+    wasm::remove_task(done).unwrap();
+}
@@ -0,0 +1,30 @@
+//! Browser tests for `get_stats`. Run with
+//! `wasm-pack test --headless --firefox -- --features web`; otherwise this
+//! file is empty.
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use gh_actions::wasm;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn stats_agree_with_single_getters() {
+    wasm::init(None).unwrap();
+    let done = wasm::add_task("File taxes".to_string(), String::new()).unwrap();
+    let open = wasm::add_task("Renew passport".to_string(), String::new()).unwrap();
+    wasm::toggle_task(done).unwrap();
+
+    let stats = wasm::get_stats();
+    assert_eq!(stats.total(), wasm::get_task_count());
+    assert_eq!(stats.completed(), wasm::get_completed_count());
+    assert_eq!(stats.remaining(), stats.total() - stats.completed());
+    assert_eq!(stats.streak(), wasm::get_task_completion_streak());
+    assert!(stats.streak() >= 1);
+    assert_eq!(stats.average_hours(), wasm::get_average_completion_time());
+    assert!(stats.velocity() > 0.0);
+    assert!(stats.completion_rate() > 0.0 && stats.completion_rate() <= 1.0);
+
+    wasm::remove_task(done).unwrap();
+    wasm::remove_task(open).unwrap();
+}
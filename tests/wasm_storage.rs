@@ -0,0 +1,145 @@
+//! Browser tests for the localStorage backend. Run with
+//! `wasm-pack test --headless --firefox -- --features web`; otherwise this
+//! file is empty.
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use gh_actions::storage::{self, LocalStorage, StorageBackend, TASKS_KEY};
+use gh_actions::{wasm, TaskManager};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn clear_local_storage() {
+    // Write out changes left by an earlier test, which `init` would save
+    wasm::flush_saves();
+    web_sys::window().unwrap().local_storage().unwrap().unwrap().clear().unwrap();
+}
+
+#[wasm_bindgen_test]
+fn local_storage_round_trip() {
+    clear_local_storage();
+    let mut backend = LocalStorage;
+    assert!(storage::load_tasks(&backend, TASKS_KEY).unwrap().is_none());
+
+    let mut manager = TaskManager::new();
+    manager.add_task("Water plants".to_string(), "Balcony".to_string()).unwrap();
+    storage::save_tasks(&mut backend, TASKS_KEY, &manager).unwrap();
+
+    let loaded = storage::load_tasks(&backend, TASKS_KEY).unwrap().unwrap();
+    assert_eq!(loaded.get_task(1).unwrap().title, "Water plants");
+}
+
+#[wasm_bindgen_test]
+fn bindings_persist_to_local_storage() {
+    clear_local_storage();
+    wasm::init(None).unwrap();
+    let id = wasm::add_task("Pay rent".to_string(), String::new()).unwrap();
+    // Saves are delayed to batch changes
+    assert!(LocalStorage.load(TASKS_KEY).unwrap().is_none());
+    assert!(wasm::flush_saves());
+
+    let stored = LocalStorage.load(TASKS_KEY).unwrap().unwrap();
+    assert!(stored.contains("Pay rent"), "{}", stored);
+
+    wasm::init(None).unwrap();
+    assert!(wasm::get_all_tasks_json().unwrap().contains("Pay rent"));
+    assert!(wasm::remove_task(id).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn namespaces_keep_separate_tasks() {
+    clear_local_storage();
+    let report = wasm::init(Some("work".to_string())).unwrap();
+    assert_eq!(report.loaded_count(), 0);
+    assert_eq!(report.storage_kind(), "localStorage");
+    wasm::add_task("Quarterly report".to_string(), String::new()).unwrap();
+
+    assert_eq!(wasm::init(Some("home".to_string())).unwrap().loaded_count(), 0);
+    let stored = LocalStorage.load("gh_actions:work:tasks").unwrap().unwrap();
+    assert!(stored.contains("Quarterly report"), "{}", stored);
+    assert!(LocalStorage.load(TASKS_KEY).unwrap().is_none());
+
+    assert_eq!(wasm::init(Some("work".to_string())).unwrap().loaded_count(), 1);
+    assert_eq!(wasm::init(None).unwrap().loaded_count(), 0);
+}
+
+#[wasm_bindgen_test]
+fn unreadable_tasks_throw_and_start_empty() {
+    clear_local_storage();
+    LocalStorage.save(TASKS_KEY, "{not json").unwrap();
+    assert!(wasm::init(None).is_err());
+    assert_eq!(wasm::get_task_count(), 0);
+    clear_local_storage();
+}
+
+#[wasm_bindgen_test]
+fn healthy_after_init() {
+    wasm::init(None).unwrap();
+    assert!(wasm::is_healthy());
+    assert!(wasm::get_all_tasks_json().is_ok());
+}
+
+#[wasm_bindgen_test]
+fn typed_bindings_return_plain_objects() {
+    clear_local_storage();
+    wasm::init(None).unwrap();
+    wasm::add_task("Typed".to_string(), String::new()).unwrap();
+
+    let tasks = js_sys::Array::from(&wasm::get_all_tasks().unwrap());
+    let first = tasks.iter().find(|task| {
+        js_sys::Reflect::get(task, &"title".into()).unwrap().as_string().as_deref() == Some("Typed")
+    });
+    assert!(first.is_some());
+
+    let report = wasm::get_report(7).unwrap();
+    assert_eq!(js_sys::Reflect::get(&report, &"days".into()).unwrap().as_f64(), Some(7.0));
+    assert!(js_sys::Array::is_array(&wasm::get_predictions().unwrap()));
+}
+
+#[wasm_bindgen_test]
+fn full_storage_keeps_changes_pending() {
+    clear_local_storage();
+    wasm::init(None).unwrap();
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let recorded = errors.clone();
+    let callback = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+        let field = |name: &str| js_sys::Reflect::get(&event, &name.into()).unwrap().as_string();
+        if field("kind").as_deref() == Some("storage_error") {
+            recorded.borrow_mut().push(field("error").unwrap());
+        }
+    });
+    let handle = wasm::register_on_change(callback.as_ref().unchecked_ref::<js_sys::Function>().clone());
+
+    // Fill localStorage, halving the filler until not even a byte fits
+    let mut filler = "x".repeat(1 << 20);
+    let mut fillers = 0;
+    while !filler.is_empty() {
+        match LocalStorage.save(&format!("filler-{}", fillers), &filler) {
+            Ok(()) => fillers += 1,
+            Err(_) => filler.truncate(filler.len() / 2),
+        }
+    }
+
+    wasm::add_task("Does not fit".to_string(), String::new()).unwrap();
+    assert!(wasm::flush_saves());
+    assert!(wasm::has_unsaved_changes());
+    assert_eq!(errors.borrow().len(), 1);
+    assert!(errors.borrow()[0].contains("storage is full"), "{}", errors.borrow()[0]);
+
+    // With room again, the next change saves the earlier one too
+    for filler in 0..fillers {
+        LocalStorage::remove(&format!("filler-{}", filler)).unwrap();
+    }
+    wasm::add_task("Fits".to_string(), String::new()).unwrap();
+    assert!(wasm::flush_saves());
+    assert!(!wasm::has_unsaved_changes());
+    let stored = LocalStorage.load(TASKS_KEY).unwrap().unwrap();
+    assert!(stored.contains("Does not fit"), "{}", stored);
+    assert_eq!(errors.borrow().len(), 1);
+
+    wasm::unregister_on_change(handle);
+}
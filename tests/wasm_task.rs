@@ -0,0 +1,65 @@
+//! Browser tests for the `WasmTask` bindings. Run with
+//! `wasm-pack test --headless --firefox -- --features web`; otherwise this
+//! file is empty.
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use gh_actions::wasm::{self, WasmTask};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn fetch_mutate_and_apply() {
+    wasm::init(None).unwrap();
+    let id = wasm::add_task("Pay rent".to_string(), String::new()).unwrap();
+
+    let mut task = wasm::get_task(id).unwrap();
+    assert_eq!(task.id(), Some(id));
+    task.set_title("Pay rent and bills".to_string());
+    task.set_completed(true);
+    assert_eq!(task.apply().unwrap(), id);
+
+    let stored = wasm::get_task(id).unwrap();
+    assert_eq!(stored.title(), "Pay rent and bills");
+    assert!(stored.completed());
+    assert!(wasm::remove_task(id).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn constructed_task_is_added_on_apply() {
+    wasm::init(None).unwrap();
+    let mut task = WasmTask::new("Draft".to_string(), "From JS".to_string());
+    assert_eq!(task.id(), None);
+
+    let id = task.apply().unwrap();
+    assert_eq!(task.id(), Some(id));
+    assert_eq!(wasm::get_task(id).unwrap().description(), "From JS");
+    assert!(wasm::remove_task(id).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn applying_a_removed_task_throws() {
+    wasm::init(None).unwrap();
+    let id = wasm::add_task("Gone".to_string(), String::new()).unwrap();
+    let mut task = wasm::get_task(id).unwrap();
+    assert!(wasm::remove_task(id).unwrap());
+    assert!(wasm::get_task(id).is_none());
+
+    let error = JsValue::from(task.apply().unwrap_err());
+    let name = js_sys::Reflect::get(&error, &"name".into()).unwrap();
+    assert_eq!(name.as_string().as_deref(), Some("NotFoundError"));
+}
+
+#[wasm_bindgen_test]
+fn property_accessors_are_exposed_to_js() {
+    let task = JsValue::from(WasmTask::new("Typed".to_string(), String::new()));
+    js_sys::Reflect::set(&task, &"title".into(), &"Renamed".into()).unwrap();
+    js_sys::Reflect::set(&task, &"completed".into(), &true.into()).unwrap();
+    assert_eq!(
+        js_sys::Reflect::get(&task, &"title".into()).unwrap().as_string().as_deref(),
+        Some("Renamed")
+    );
+    assert_eq!(js_sys::Reflect::get(&task, &"completed".into()).unwrap().as_bool(), Some(true));
+    assert!(js_sys::Reflect::get(&task, &"id".into()).unwrap().is_undefined());
+}
@@ -0,0 +1,59 @@
+//! Browser tests for the basic task bindings, run against the compiled wasm
+//! module. Run with `wasm-pack test --headless --chrome -- --features web`,
+//! or `cargo test --target wasm32-unknown-unknown --features web` with the
+//! runner set up in `.cargo/config.toml`; otherwise this file is empty.
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use gh_actions::wasm;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn add_task_increments_count() {
+    wasm::init(None).unwrap();
+    let before = wasm::get_task_count();
+    let id = wasm::add_task("Buy milk".to_string(), "Semi-skimmed".to_string()).unwrap();
+    assert_eq!(wasm::get_task_count(), before + 1);
+    assert!(wasm::remove_task(id).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn toggle_task_increments_completed_count() {
+    wasm::init(None).unwrap();
+    let id = wasm::add_task("Renew passport".to_string(), String::new()).unwrap();
+    let before = wasm::get_completed_count();
+
+    let result: serde_json::Value = serde_json::from_str(&wasm::toggle_task(id).unwrap()).unwrap();
+    assert_eq!(result["toggled"], true);
+    assert_eq!(wasm::get_completed_count(), before + 1);
+
+    wasm::toggle_task(id).unwrap();
+    assert_eq!(wasm::get_completed_count(), before);
+    assert!(wasm::remove_task(id).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn remove_task_decrements_count() {
+    wasm::init(None).unwrap();
+    let id = wasm::add_task("Cancel gym".to_string(), String::new()).unwrap();
+    let before = wasm::get_task_count();
+
+    assert!(wasm::remove_task(id).unwrap());
+    assert_eq!(wasm::get_task_count(), before - 1);
+    assert!(!wasm::remove_task(id).unwrap());
+    assert_eq!(wasm::get_task_count(), before - 1);
+}
+
+#[wasm_bindgen_test]
+fn all_tasks_json_is_valid() {
+    wasm::init(None).unwrap();
+    let id = wasm::add_task("Quote \"this\"".to_string(), "Line\nbreak".to_string()).unwrap();
+
+    let tasks: Vec<serde_json::Value> = serde_json::from_str(&wasm::get_all_tasks_json().unwrap()).unwrap();
+    assert_eq!(tasks.len() as u32, wasm::get_task_count());
+    let task = tasks.iter().find(|task| task["id"] == id).unwrap();
+    assert_eq!(task["title"], "Quote \"this\"");
+    assert_eq!(task["description"], "Line\nbreak");
+    assert!(wasm::remove_task(id).unwrap());
+}
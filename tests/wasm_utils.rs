@@ -0,0 +1,50 @@
+//! Browser tests for the `wasm_utils` bindings. Run with
+//! `wasm-pack test --headless --firefox -- --features web`; otherwise this
+//! file is empty.
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use gh_actions::wasm_utils::{self, MAX_SAFE_INTEGER};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn error_name(error: JsValue) -> String {
+    js_sys::Reflect::get(&error, &"name".into()).unwrap().as_string().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn fibonacci_switches_to_strings_above_safe_integers() {
+    assert_eq!(wasm_utils::wasm_fibonacci(10).unwrap().as_f64(), Some(55.0));
+    // F(78) is the last one below 2^53, F(79) the first above
+    assert_eq!(wasm_utils::wasm_fibonacci(78).unwrap().as_f64(), Some(8_944_394_323_791_464.0));
+    let f79 = wasm_utils::wasm_fibonacci(79).unwrap();
+    assert_eq!(f79.as_f64(), None);
+    assert_eq!(f79.as_string().as_deref(), Some("14472334024676221"));
+    assert_eq!(
+        wasm_utils::wasm_fibonacci(93).unwrap().as_string().as_deref(),
+        Some("12200160415121876738")
+    );
+
+    let error = wasm_utils::wasm_fibonacci(94).unwrap_err();
+    assert_eq!(error_name(error.into()), "InvalidArgumentError");
+}
+
+#[wasm_bindgen_test]
+fn is_prime_accepts_safe_integers_only() {
+    assert_eq!(wasm_utils::wasm_is_prime(97.0), Ok(true));
+    assert_eq!(wasm_utils::wasm_is_prime(1.0), Ok(false));
+    assert!(wasm_utils::wasm_is_prime(MAX_SAFE_INTEGER as f64).is_ok());
+    for bad in [MAX_SAFE_INTEGER as f64 + 1.0, -7.0, 7.5, f64::NAN] {
+        let error = wasm_utils::wasm_is_prime(bad).unwrap_err();
+        assert_eq!(error_name(error.into()), "InvalidArgumentError");
+    }
+}
+
+#[wasm_bindgen_test]
+fn string_checks() {
+    assert!(wasm_utils::wasm_validate_email("ada@example.com"));
+    assert!(!wasm_utils::wasm_validate_email("not an email"));
+    assert!(wasm_utils::wasm_is_palindrome("racecar"));
+    assert!(!wasm_utils::wasm_is_palindrome("rust"));
+}